@@ -0,0 +1,89 @@
+use crate::errors::*;
+use crate::events::*;
+use crate::{AdminContract, ADMIN, ADMIN_SIGNERS, PAUSED, PENDING_ADMIN};
+use soroban_sdk::{panic_with_error, Address, Env};
+
+// Admin rotation is done in two steps so a typo in the new address cannot lock
+// the contract: the current admin proposes, and the proposed address has to
+// accept before it takes over.
+
+pub fn init_admin(env: &Env, admin: &Address) {
+    env.storage().instance().set(&ADMIN, admin);
+}
+
+pub fn admin(env: &Env) -> Address {
+    env.storage().instance().get(&ADMIN).unwrap()
+}
+
+pub fn pending_admin(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&PENDING_ADMIN)
+}
+
+// Once the admin is a multisig, admin entrypoints can only be reached through
+// `execute_admin_action`.
+pub fn require_admin(env: &Env) -> Address {
+    if env.storage().instance().has(&ADMIN_SIGNERS) {
+        panic_with_error!(env, AdminError::NotAuthorized);
+    }
+    let admin = admin(env);
+    admin.require_auth();
+    admin
+}
+
+pub fn propose_admin<C: AdminContract>(env: &Env, admin: Address, new_admin: Address) {
+    env.storage().instance().set(&PENDING_ADMIN, &new_admin);
+
+    ProposeAdminEvent {
+        admin,
+        pending_admin: new_admin,
+    }
+    .publish::<C>(env);
+}
+
+pub fn accept_admin<C: AdminContract>(env: &Env) {
+    let pending_admin =
+        pending_admin(env).unwrap_or_else(|| panic_with_error!(env, AdminError::NoPendingAdmin));
+    pending_admin.require_auth();
+    let previous_admin = admin(env);
+    env.storage().instance().set(&ADMIN, &pending_admin);
+    env.storage().instance().remove(&PENDING_ADMIN);
+    // Handing over to a single key ends multisig mode.
+    env.storage().instance().remove(&ADMIN_SIGNERS);
+
+    AcceptAdminEvent {
+        previous_admin,
+        admin: pending_admin,
+    }
+    .publish::<C>(env);
+}
+
+pub fn cancel_admin_proposal<C: AdminContract>(env: &Env, admin: Address) {
+    let pending_admin =
+        pending_admin(env).unwrap_or_else(|| panic_with_error!(env, AdminError::NoPendingAdmin));
+    env.storage().instance().remove(&PENDING_ADMIN);
+
+    CancelAdminProposalEvent {
+        admin,
+        pending_admin,
+    }
+    .publish::<C>(env);
+}
+
+// Holders of `Role::Pauser` can stop every state-changing entrypoint in an
+// emergency while reads keep working. A multisig admin pauses through the
+// `Pause` and `Unpause` admin actions.
+pub fn is_paused(env: &Env) -> bool {
+    env.storage().instance().get(&PAUSED).unwrap_or(false)
+}
+
+pub fn pause<C: AdminContract>(env: &Env, pauser: Address) {
+    env.storage().instance().set(&PAUSED, &true);
+
+    PauseEvent { pauser }.publish::<C>(env);
+}
+
+pub fn unpause<C: AdminContract>(env: &Env, pauser: Address) {
+    env.storage().instance().remove(&PAUSED);
+
+    UnpauseEvent { pauser }.publish::<C>(env);
+}
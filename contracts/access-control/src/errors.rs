@@ -0,0 +1,21 @@
+use soroban_sdk::contracterror;
+
+// Same codes as the `Error` enums of the contracts using this crate, which
+// export them.
+#[contracterror(export = false)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum AdminError {
+    NotAuthorized = 10,
+    NoPendingAdmin = 26,
+    UpgradeEtaTooEarly = 27,
+    UpgradeNotReady = 28,
+    NoPendingUpgrade = 29,
+    Paused = 30,
+    AdminActionNotFound = 32,
+    NotEnoughApprovals = 33,
+    AlreadyApproved = 34,
+    AdminSignersInvalid = 35,
+    UpgradePending = 49,
+    RoleNotGranted = 50,
+}
@@ -0,0 +1,187 @@
+use crate::{AdminContract, Role};
+use soroban_sdk::{contracttype, Address, BytesN, Env, IntoVal, Map, Symbol, Val, Vec};
+
+// Published under the event version of the contract they come from, so they
+// sit alongside the contract's own events.
+pub trait Event: IntoVal<Env, Val> + Sized {
+    const NAME: &'static str;
+
+    fn publish<C: AdminContract>(self, env: &Env) {
+        env.events()
+            .publish((Symbol::new(env, Self::NAME), C::EVENT_VERSION), self);
+    }
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposeAdminEvent {
+    pub admin: Address,
+    pub pending_admin: Address,
+}
+
+impl Event for ProposeAdminEvent {
+    const NAME: &'static str = "propose_admin";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AcceptAdminEvent {
+    pub previous_admin: Address,
+    pub admin: Address,
+}
+
+impl Event for AcceptAdminEvent {
+    const NAME: &'static str = "accept_admin";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CancelAdminProposalEvent {
+    pub admin: Address,
+    pub pending_admin: Address,
+}
+
+impl Event for CancelAdminProposalEvent {
+    const NAME: &'static str = "cancel_admin_proposal";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SetAdminSignersEvent {
+    pub admin: Address,
+    pub signers: Vec<Address>,
+    pub threshold: u32,
+}
+
+impl Event for SetAdminSignersEvent {
+    const NAME: &'static str = "set_admin_signers";
+}
+
+// `action` is the contract's own `AdminAction`, so this event is built as the
+// map `#[contracttype]` would encode it to.
+pub(crate) fn publish_propose_admin_action<C: AdminContract>(
+    env: &Env,
+    signer: Address,
+    id: u32,
+    action: C::Action,
+) {
+    let mut event: Map<Symbol, Val> = Map::new(env);
+    event.set(Symbol::new(env, "signer"), signer.into_val(env));
+    event.set(Symbol::new(env, "id"), id.into_val(env));
+    event.set(Symbol::new(env, "action"), action.into_val(env));
+    env.events().publish(
+        (Symbol::new(env, "propose_admin_action"), C::EVENT_VERSION),
+        event,
+    );
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ApproveAdminActionEvent {
+    pub signer: Address,
+    pub id: u32,
+}
+
+impl Event for ApproveAdminActionEvent {
+    const NAME: &'static str = "approve_admin_action";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExecuteAdminActionEvent {
+    pub signer: Address,
+    pub id: u32,
+}
+
+impl Event for ExecuteAdminActionEvent {
+    const NAME: &'static str = "execute_admin_action";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PauseEvent {
+    pub pauser: Address,
+}
+
+impl Event for PauseEvent {
+    const NAME: &'static str = "pause";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnpauseEvent {
+    pub pauser: Address,
+}
+
+impl Event for UnpauseEvent {
+    const NAME: &'static str = "unpause";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GrantRoleEvent {
+    pub admin: Address,
+    pub role: Role,
+    pub account: Address,
+}
+
+impl Event for GrantRoleEvent {
+    const NAME: &'static str = "grant_role";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RevokeRoleEvent {
+    pub admin: Address,
+    pub role: Role,
+    pub account: Address,
+}
+
+impl Event for RevokeRoleEvent {
+    const NAME: &'static str = "revoke_role";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RenounceRoleEvent {
+    pub account: Address,
+    pub role: Role,
+}
+
+impl Event for RenounceRoleEvent {
+    const NAME: &'static str = "renounce_role";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScheduleUpgradeEvent {
+    pub admin: Address,
+    pub new_wasm_hash: BytesN<32>,
+    pub eta: u64,
+}
+
+impl Event for ScheduleUpgradeEvent {
+    const NAME: &'static str = "schedule_upgrade";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CancelUpgradeEvent {
+    pub admin: Address,
+    pub new_wasm_hash: BytesN<32>,
+}
+
+impl Event for CancelUpgradeEvent {
+    const NAME: &'static str = "cancel_upgrade";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpgradeEvent {
+    pub admin: Address,
+    pub new_wasm_hash: BytesN<32>,
+}
+
+impl Event for UpgradeEvent {
+    const NAME: &'static str = "upgrade";
+}
@@ -0,0 +1,43 @@
+#![no_std]
+// Admin handling shared by the contracts: two-step admin rotation, roles,
+// pausing, timelocked upgrades, and the multisig that can replace the admin
+// key. Each contract
+// keeps its own entrypoints, which only check the caller and call in here, so
+// all of them run the same code.
+mod admin;
+mod errors;
+mod events;
+mod multisig;
+mod roles;
+mod upgrade;
+
+pub use crate::admin::*;
+pub use crate::errors::*;
+pub use crate::events::*;
+pub use crate::multisig::*;
+pub use crate::roles::*;
+pub use crate::upgrade::*;
+
+use soroban_sdk::{symbol_short, Address, Env, IntoVal, Symbol, TryFromVal, Val};
+
+const ADMIN: Symbol = symbol_short!("admin");
+const PENDING_ADMIN: Symbol = symbol_short!("pend_adm");
+const ADMIN_SIGNERS: Symbol = symbol_short!("signers");
+const ADMIN_ACTION_COUNT: Symbol = symbol_short!("act_cnt");
+const MAX_ADMIN_SIGNERS: u32 = 10;
+const PAUSED: Symbol = symbol_short!("paused");
+const ROLE_EPOCH: Symbol = symbol_short!("role_ep");
+const PENDING_UPGRADE: Symbol = symbol_short!("pend_upg");
+// Minimum notice given to name holders before new contract code goes live.
+pub const MIN_UPGRADE_DELAY: u64 = 2 * 24 * 60 * 60;
+
+// Implemented by every contract using this crate.
+pub trait AdminContract {
+    // Second topic of the contract's events, see `Event`.
+    const EVENT_VERSION: u32;
+    // The admin entrypoints the signers can run together.
+    type Action: Clone + IntoVal<Env, Val> + TryFromVal<Env, Val>;
+
+    // Runs an action the signers approved. `admin` is the contract itself.
+    fn execute(env: &Env, admin: Address, action: Self::Action);
+}
@@ -0,0 +1,164 @@
+use crate::errors::*;
+use crate::events::*;
+use crate::{
+    admin, clear_roles, AdminContract, ADMIN, ADMIN_ACTION_COUNT, ADMIN_SIGNERS, MAX_ADMIN_SIGNERS,
+    PENDING_ADMIN,
+};
+use soroban_sdk::{contracttype, panic_with_error, Address, Env, IntoVal, TryFromVal, Val, Vec};
+
+// The admin can be replaced by a set of signers and a threshold. From then on
+// the admin is the contract itself: single-key calls to admin entrypoints are
+// refused, roles granted by the single key are dropped, and an admin action
+// only runs once `threshold` signers approved it.
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminSigners {
+    pub signers: Vec<Address>,
+    pub threshold: u32,
+}
+
+// A proposed action, decoded as the contract's `AdminAction`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Proposal<A> {
+    pub action: A,
+    pub approvals: Vec<Address>,
+}
+
+#[contracttype(export = false)]
+enum DataKey {
+    AdminAction(u32),
+}
+
+pub fn admin_signers(env: &Env) -> Option<AdminSigners> {
+    env.storage().instance().get(&ADMIN_SIGNERS)
+}
+
+pub fn set_admin_signers<C: AdminContract>(
+    env: &Env,
+    admin: Address,
+    signers: Vec<Address>,
+    threshold: u32,
+) {
+    if threshold == 0 || threshold > signers.len() || signers.len() > MAX_ADMIN_SIGNERS {
+        panic_with_error!(env, AdminError::AdminSignersInvalid);
+    }
+    for (index, signer) in signers.iter().enumerate() {
+        if signers.first_index_of(&signer) != Some(index as u32) {
+            panic_with_error!(env, AdminError::AdminSignersInvalid);
+        }
+    }
+    // Grants made by the single key are dropped, the signers' own are kept
+    // when they change the signer set.
+    if admin_signers(env).is_none() {
+        clear_roles(env);
+    }
+    env.storage().instance().set(
+        &ADMIN_SIGNERS,
+        &AdminSigners {
+            signers: signers.clone(),
+            threshold,
+        },
+    );
+    env.storage()
+        .instance()
+        .set(&ADMIN, &env.current_contract_address());
+    env.storage().instance().remove(&PENDING_ADMIN);
+
+    SetAdminSignersEvent {
+        admin,
+        signers,
+        threshold,
+    }
+    .publish::<C>(env);
+}
+
+pub fn admin_action<C: AdminContract>(env: &Env, id: u32) -> Proposal<C::Action> {
+    let (action, approvals): (Val, Vec<Address>) = env
+        .storage()
+        .instance()
+        .get(&DataKey::AdminAction(id))
+        .unwrap_or_else(|| panic_with_error!(env, AdminError::AdminActionNotFound));
+    Proposal {
+        action: C::Action::try_from_val(env, &action).unwrap(),
+        approvals,
+    }
+}
+
+// Proposing counts as the proposer's approval.
+pub fn propose_admin_action<C: AdminContract>(
+    env: &Env,
+    signer: Address,
+    action: C::Action,
+) -> u32 {
+    require_signer(env, &signer);
+    let id: u32 = env
+        .storage()
+        .instance()
+        .get(&ADMIN_ACTION_COUNT)
+        .unwrap_or(0);
+    env.storage().instance().set(&ADMIN_ACTION_COUNT, &(id + 1));
+    store_proposal::<C>(
+        env,
+        id,
+        &Proposal {
+            action: action.clone(),
+            approvals: Vec::from_array(env, [signer.clone()]),
+        },
+    );
+
+    publish_propose_admin_action::<C>(env, signer, id, action);
+    id
+}
+
+pub fn approve_admin_action<C: AdminContract>(env: &Env, signer: Address, id: u32) {
+    require_signer(env, &signer);
+    let mut proposal = admin_action::<C>(env, id);
+    if proposal.approvals.contains(&signer) {
+        panic_with_error!(env, AdminError::AlreadyApproved);
+    }
+    proposal.approvals.push_back(signer.clone());
+    store_proposal::<C>(env, id, &proposal);
+
+    ApproveAdminActionEvent { signer, id }.publish::<C>(env);
+}
+
+// Approvals from signers that were removed since are not counted.
+pub fn execute_admin_action<C: AdminContract>(env: &Env, signer: Address, id: u32) {
+    let admin_signers = require_signer(env, &signer);
+    let proposal = admin_action::<C>(env, id);
+    let approvals = proposal
+        .approvals
+        .iter()
+        .filter(|approver| admin_signers.signers.contains(approver))
+        .count() as u32;
+    if approvals < admin_signers.threshold {
+        panic_with_error!(env, AdminError::NotEnoughApprovals);
+    }
+    env.storage().instance().remove(&DataKey::AdminAction(id));
+
+    C::execute(env, admin::admin(env), proposal.action);
+
+    ExecuteAdminActionEvent { signer, id }.publish::<C>(env);
+}
+
+// Stored as an (action, approvals) pair, since `Proposal` is generic over the
+// contract's action type.
+fn store_proposal<C: AdminContract>(env: &Env, id: u32, proposal: &Proposal<C::Action>) {
+    let entry: (Val, Vec<Address>) = (
+        proposal.action.clone().into_val(env),
+        proposal.approvals.clone(),
+    );
+    env.storage()
+        .instance()
+        .set(&DataKey::AdminAction(id), &entry);
+}
+fn require_signer(env: &Env, signer: &Address) -> AdminSigners {
+    let admin_signers =
+        admin_signers(env).unwrap_or_else(|| panic_with_error!(env, AdminError::NotAuthorized));
+    if !admin_signers.signers.contains(signer) {
+        panic_with_error!(env, AdminError::NotAuthorized);
+    }
+    signer.require_auth();
+    admin_signers
+}
@@ -0,0 +1,104 @@
+use crate::errors::*;
+use crate::events::*;
+use crate::{admin, AdminContract, ROLE_EPOCH};
+use soroban_sdk::{contracttype, panic_with_error, Address, Env};
+
+// Narrow permissions for the ops team, so the admin key does not have to be
+// shared. The admin holds every role, and a `SuperAdmin` does too. Only the
+// admin grants and revokes roles, so once the admin is a multisig that takes an
+// admin action. Switching to a multisig drops every earlier grant, so no
+// single key keeps powers the signers did not approve.
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Role {
+    SuperAdmin,
+    // Prices and the referral share, on the registrar controller.
+    PricingManager,
+    // TLD registrars, on the registrar controller.
+    TldManager,
+    // Withdrawals of collected fees, on the registrar controller.
+    Treasurer,
+    Pauser,
+    // Scheduling, cancelling and executing upgrades, and migrations.
+    Upgrader,
+}
+
+// role, account -> the epoch the grant was made in
+#[contracttype(export = false)]
+enum DataKey {
+    Role(Role, Address),
+}
+
+pub fn has_role(env: &Env, role: Role, account: &Address) -> bool {
+    *account == admin(env)
+        || is_granted(env, role, account)
+        || is_granted(env, Role::SuperAdmin, account)
+}
+
+// Grants only count in the epoch they were made in, which lets
+// `clear_roles` drop all of them without listing them.
+fn is_granted(env: &Env, role: Role, account: &Address) -> bool {
+    let epoch: Option<u32> = env
+        .storage()
+        .instance()
+        .get(&DataKey::Role(role, account.clone()));
+    epoch == Some(role_epoch(env))
+}
+
+fn role_epoch(env: &Env) -> u32 {
+    env.storage().instance().get(&ROLE_EPOCH).unwrap_or(0)
+}
+
+pub(crate) fn clear_roles(env: &Env) {
+    env.storage()
+        .instance()
+        .set(&ROLE_EPOCH, &(role_epoch(env) + 1));
+}
+
+pub fn require_role(env: &Env, caller: &Address, role: Role) {
+    caller.require_auth();
+    if !has_role(env, role, caller) {
+        panic_with_error!(env, AdminError::NotAuthorized);
+    }
+}
+
+pub fn grant_role<C: AdminContract>(env: &Env, admin: Address, role: Role, account: Address) {
+    env.storage()
+        .instance()
+        .set(&DataKey::Role(role, account.clone()), &role_epoch(env));
+
+    GrantRoleEvent {
+        admin,
+        role,
+        account,
+    }
+    .publish::<C>(env);
+}
+
+pub fn revoke_role<C: AdminContract>(env: &Env, admin: Address, role: Role, account: Address) {
+    remove_role(env, role, &account);
+
+    RevokeRoleEvent {
+        admin,
+        role,
+        account,
+    }
+    .publish::<C>(env);
+}
+
+pub fn renounce_role<C: AdminContract>(env: &Env, account: Address, role: Role) {
+    account.require_auth();
+    remove_role(env, role, &account);
+
+    RenounceRoleEvent { account, role }.publish::<C>(env);
+}
+
+// The admin's implicit roles cannot be taken away, only explicit grants.
+fn remove_role(env: &Env, role: Role, account: &Address) {
+    if !is_granted(env, role, account) {
+        panic_with_error!(env, AdminError::RoleNotGranted);
+    }
+    env.storage()
+        .instance()
+        .remove(&DataKey::Role(role, account.clone()));
+}
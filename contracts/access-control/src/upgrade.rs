@@ -0,0 +1,78 @@
+use crate::errors::*;
+use crate::events::*;
+use crate::{AdminContract, MIN_UPGRADE_DELAY, PENDING_UPGRADE};
+use soroban_sdk::{contracttype, panic_with_error, Address, BytesN, Env};
+
+// Upgrades are announced first and can only be executed once `eta` has passed,
+// so holders can review a code change before it takes effect.
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScheduledUpgrade {
+    pub new_wasm_hash: BytesN<32>,
+    pub eta: u64,
+}
+
+pub fn pending_upgrade(env: &Env) -> Option<ScheduledUpgrade> {
+    env.storage().instance().get(&PENDING_UPGRADE)
+}
+
+pub fn schedule_upgrade<C: AdminContract>(
+    env: &Env,
+    admin: Address,
+    new_wasm_hash: BytesN<32>,
+    eta: u64,
+) {
+    if eta < env.ledger().timestamp().saturating_add(MIN_UPGRADE_DELAY) {
+        panic_with_error!(env, AdminError::UpgradeEtaTooEarly);
+    }
+    // A pending upgrade has to be cancelled first, so replacing it is never
+    // silent: holders see the cancel event before the new schedule.
+    if env.storage().instance().has(&PENDING_UPGRADE) {
+        panic_with_error!(env, AdminError::UpgradePending);
+    }
+    env.storage().instance().set(
+        &PENDING_UPGRADE,
+        &ScheduledUpgrade {
+            new_wasm_hash: new_wasm_hash.clone(),
+            eta,
+        },
+    );
+
+    ScheduleUpgradeEvent {
+        admin,
+        new_wasm_hash,
+        eta,
+    }
+    .publish::<C>(env);
+}
+
+pub fn cancel_upgrade<C: AdminContract>(env: &Env, admin: Address) {
+    let scheduled = pending_upgrade(env)
+        .unwrap_or_else(|| panic_with_error!(env, AdminError::NoPendingUpgrade));
+    env.storage().instance().remove(&PENDING_UPGRADE);
+
+    CancelUpgradeEvent {
+        admin,
+        new_wasm_hash: scheduled.new_wasm_hash,
+    }
+    .publish::<C>(env);
+}
+
+pub fn execute_upgrade<C: AdminContract>(env: &Env, admin: Address) {
+    let scheduled = pending_upgrade(env)
+        .unwrap_or_else(|| panic_with_error!(env, AdminError::NoPendingUpgrade));
+    if env.ledger().timestamp() < scheduled.eta {
+        panic_with_error!(env, AdminError::UpgradeNotReady);
+    }
+    env.storage().instance().remove(&PENDING_UPGRADE);
+
+    env.deployer()
+        .update_current_contract_wasm(scheduled.new_wasm_hash.clone());
+
+    UpgradeEvent {
+        admin,
+        new_wasm_hash: scheduled.new_wasm_hash,
+    }
+    .publish::<C>(env);
+}
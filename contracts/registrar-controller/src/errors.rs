@@ -0,0 +1,34 @@
+use soroban_sdk::contracterror;
+
+// Codes are shared with the registry and resolver, see the list in
+// `sns-client`. A contract spec holds at most 50 error cases, so only the
+// errors this contract raises are listed here.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    NameInvalid = 3,
+    NotAuthorized = 10,
+    NoPendingAdmin = 26,
+    UpgradeEtaTooEarly = 27,
+    UpgradeNotReady = 28,
+    NoPendingUpgrade = 29,
+    Paused = 30,
+    AdminActionNotFound = 32,
+    NotEnoughApprovals = 33,
+    AlreadyApproved = 34,
+    AdminSignersInvalid = 35,
+    PriceInvalid = 36,
+    NameReserved = 37,
+    NameNotReserved = 38,
+    RegistrationPeriodInvalid = 39,
+    RegistrationClosed = 40,
+    NotAllowlisted = 41,
+    LaunchScheduleInvalid = 42,
+    ReferralShareInvalid = 43,
+    ReferrerInvalid = 44,
+    NoReferralRewards = 45,
+    UpgradePending = 49,
+    RoleNotGranted = 50,
+    WithdrawalTooLarge = 51,
+}
@@ -0,0 +1,186 @@
+use crate::types::{ExpiryPremium, LaunchSchedule, TldPolicy};
+use soroban_sdk::{contracttype, Address, Bytes, Env, IntoVal, Symbol, Val, Vec};
+
+// Bumped whenever the fields of an existing event change. It is published as the
+// second topic so indexers can keep decoding old events after an upgrade.
+pub const EVENT_VERSION: u32 = 1;
+
+pub trait Event: IntoVal<Env, Val> + Sized {
+    const NAME: &'static str;
+
+    fn publish(self, env: &Env) {
+        env.events()
+            .publish((Symbol::new(env, Self::NAME), EVENT_VERSION), self);
+    }
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RegisterNameEvent {
+    pub owner: Address,
+    pub name: Bytes,
+    pub tld: Bytes,
+    pub number_of_years: u64,
+    pub price: i128,
+}
+
+impl Event for RegisterNameEvent {
+    const NAME: &'static str = "register_name";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RenewNameEvent {
+    pub payer: Address,
+    pub name: Bytes,
+    pub tld: Bytes,
+    pub number_of_years: u64,
+    pub price: i128,
+}
+
+impl Event for RenewNameEvent {
+    const NAME: &'static str = "renew_name";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SetPriceEvent {
+    pub admin: Address,
+    pub price_per_year: i128,
+}
+
+impl Event for SetPriceEvent {
+    const NAME: &'static str = "set_price";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithdrawEvent {
+    pub admin: Address,
+    pub to: Address,
+    pub amount: i128,
+}
+
+impl Event for WithdrawEvent {
+    const NAME: &'static str = "withdraw";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SetTldRegistrarEvent {
+    pub admin: Address,
+    pub tld: Bytes,
+    pub registrar: Address,
+}
+
+impl Event for SetTldRegistrarEvent {
+    const NAME: &'static str = "set_tld_registrar";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RemoveTldRegistrarEvent {
+    pub admin: Address,
+    pub tld: Bytes,
+}
+
+impl Event for RemoveTldRegistrarEvent {
+    const NAME: &'static str = "remove_tld_registrar";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SetTldPolicyEvent {
+    pub registrar: Address,
+    pub tld: Bytes,
+    pub policy: TldPolicy,
+}
+
+impl Event for SetTldPolicyEvent {
+    const NAME: &'static str = "set_tld_policy";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SetReservedNamesEvent {
+    pub registrar: Address,
+    pub tld: Bytes,
+    pub names: Vec<Bytes>,
+    pub reserved: bool,
+}
+
+impl Event for SetReservedNamesEvent {
+    const NAME: &'static str = "set_reserved_names";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RegisterReservedNameEvent {
+    pub registrar: Address,
+    pub owner: Address,
+    pub name: Bytes,
+    pub tld: Bytes,
+    pub number_of_years: u64,
+}
+
+impl Event for RegisterReservedNameEvent {
+    const NAME: &'static str = "register_reserved_name";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SetLaunchScheduleEvent {
+    pub registrar: Address,
+    pub tld: Bytes,
+    pub schedule: LaunchSchedule,
+}
+
+impl Event for SetLaunchScheduleEvent {
+    const NAME: &'static str = "set_launch_schedule";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SetReferralShareEvent {
+    pub admin: Address,
+    pub share: u32,
+}
+
+impl Event for SetReferralShareEvent {
+    const NAME: &'static str = "set_referral_share";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReferralRewardEvent {
+    pub referrer: Address,
+    pub payer: Address,
+    pub tld: Bytes,
+    pub amount: i128,
+}
+
+impl Event for ReferralRewardEvent {
+    const NAME: &'static str = "referral_reward";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClaimReferralRewardsEvent {
+    pub referrer: Address,
+    pub amount: i128,
+}
+
+impl Event for ClaimReferralRewardsEvent {
+    const NAME: &'static str = "claim_referral_rewards";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SetExpiryPremiumEvent {
+    pub admin: Address,
+    pub premium: ExpiryPremium,
+}
+
+impl Event for SetExpiryPremiumEvent {
+    const NAME: &'static str = "set_expiry_premium";
+}
@@ -0,0 +1,242 @@
+#![no_std]
+mod admin;
+mod errors;
+mod events;
+mod multisig;
+mod phases;
+mod premium;
+mod referrals;
+mod tlds;
+mod types;
+mod utils;
+use crate::errors::*;
+use crate::events::*;
+use crate::utils::*;
+use access_control::{Role, ScheduledUpgrade};
+use soroban_sdk::{
+    contract, contractimpl, contractimport, panic_with_error, symbol_short, token, Address, Bytes,
+    BytesN, Env, Symbol,
+};
+use types::*;
+
+mod registry {
+    super::contractimport!(file = "../../target/wasm32-unknown-unknown/release/registry.wasm");
+}
+
+const REGISTRY: Symbol = symbol_short!("registry");
+const ASSET: Symbol = symbol_short!("asset");
+const PRICE: Symbol = symbol_short!("price");
+const REFERRAL_SHARE: Symbol = symbol_short!("ref_shr");
+const UNCLAIMED_REWARDS: Symbol = symbol_short!("unclaimed");
+const EXPIRY_PREMIUM: Symbol = symbol_short!("premium");
+const MAX_BASIS_POINTS: u32 = 10_000;
+const ONE_DAY_IN_SECONDS: u64 = 24 * 60 * 60;
+const ONE_YEAR_IN_SECONDS: u64 = 365 * ONE_DAY_IN_SECONDS;
+
+// Sells names on behalf of the registry: it takes payment in `asset` and then
+// registers or renews through the registry's controller entrypoints. The
+// registry must list this contract with `add_controller`. Fees stay here until
+// the treasurer withdraws them.
+#[contract]
+pub struct RegistrarController;
+
+#[contractimpl]
+impl RegistrarController {
+    pub fn __constructor(
+        env: Env,
+        admin: Address,
+        registry: Address,
+        asset: Address,
+        price_per_year: i128,
+    ) {
+        env.extend_me();
+        access_control::init_admin(&env, &admin);
+        env.storage().instance().set(&REGISTRY, &registry);
+        env.storage().instance().set(&ASSET, &asset);
+        env.storage().instance().set(&PRICE, &price_per_year);
+    }
+
+    pub fn register_name(
+        env: Env,
+        name: Bytes,
+        tld: Bytes,
+        owner: Address,
+        number_of_years: u64,
+        referrer: Option<Address>,
+    ) {
+        env.extend_me();
+        env.require_not_paused();
+        owner.require_auth();
+        match Self::registration_phase(env.clone(), tld.clone()) {
+            RegistrationPhase::Closed => panic_with_error!(&env, Error::RegistrationClosed),
+            RegistrationPhase::Allowlist => panic_with_error!(&env, Error::NotAllowlisted),
+            RegistrationPhase::Open => {}
+        }
+        Self::register(env, name, tld, owner, number_of_years, referrer)
+    }
+
+    pub(crate) fn register(
+        env: Env,
+        name: Bytes,
+        tld: Bytes,
+        owner: Address,
+        number_of_years: u64,
+        referrer: Option<Address>,
+    ) {
+        if Self::is_name_reserved(env.clone(), name.clone(), tld.clone()) {
+            panic_with_error!(&env, Error::NameReserved);
+        }
+        if let Some(policy) = Self::tld_policy(env.clone(), tld.clone()) {
+            if name.len() < policy.min_name_length {
+                panic_with_error!(&env, Error::NameInvalid);
+            }
+        }
+        let duration = Self::duration(&env, number_of_years);
+        let price = Self::quote_price(env.clone(), name.clone(), tld.clone(), number_of_years);
+        Self::charge(&env, &owner, &tld, price, referrer.as_ref());
+        Self::registry(&env).controller_register(
+            &env.current_contract_address(),
+            &name,
+            &tld,
+            &owner,
+            &duration,
+        );
+
+        RegisterNameEvent {
+            owner,
+            name,
+            tld,
+            number_of_years,
+            price,
+        }
+        .publish(&env);
+    }
+
+    pub fn renew_name(env: Env, name: Bytes, tld: Bytes, payer: Address, number_of_years: u64) {
+        env.extend_me();
+        env.require_not_paused();
+        payer.require_auth();
+        let duration = Self::duration(&env, number_of_years);
+        let price = Self::base_price(&env, &tld, number_of_years);
+        Self::charge(&env, &payer, &tld, price, None);
+        Self::registry(&env).controller_renew(
+            &env.current_contract_address(),
+            &name,
+            &tld,
+            &duration,
+        );
+
+        RenewNameEvent {
+            payer,
+            name,
+            tld,
+            number_of_years,
+            price,
+        }
+        .publish(&env);
+    }
+
+    pub fn price_per_year(env: Env) -> i128 {
+        env.extend_me();
+        env.storage().instance().get(&PRICE).unwrap()
+    }
+
+    pub fn set_price_per_year(env: Env, caller: Address, price_per_year: i128) {
+        env.extend_me();
+        let admin = Self::require_role(&env, caller, Role::PricingManager);
+        if price_per_year < 0 {
+            panic_with_error!(&env, Error::PriceInvalid);
+        }
+        env.storage().instance().set(&PRICE, &price_per_year);
+
+        SetPriceEvent {
+            admin,
+            price_per_year,
+        }
+        .publish(&env);
+    }
+
+    pub fn withdraw(env: Env, caller: Address, to: Address, amount: i128) {
+        env.extend_me();
+        env.require_not_paused();
+        let admin = Self::require_role(&env, caller, Role::Treasurer);
+        // Referral rewards held here are not the treasury's to take.
+        let available = Self::asset(&env).balance(&env.current_contract_address())
+            - Self::unclaimed_referral_rewards(env.clone());
+        if amount > available {
+            panic_with_error!(&env, Error::WithdrawalTooLarge);
+        }
+        Self::asset(&env).transfer(&env.current_contract_address(), &to, &amount);
+
+        WithdrawEvent { admin, to, amount }.publish(&env);
+    }
+
+    pub(crate) fn base_price(env: &Env, tld: &Bytes, number_of_years: u64) -> i128 {
+        Self::check_period(env, tld, number_of_years);
+        let price_per_year = Self::tld_policy(env.clone(), tld.clone()).map_or_else(
+            || Self::price_per_year(env.clone()),
+            |policy| policy.price_per_year,
+        );
+        price_per_year
+            .checked_mul(i128::from(number_of_years))
+            .unwrap_or_else(|| panic_with_error!(env, Error::RegistrationPeriodInvalid))
+    }
+
+    pub(crate) fn duration(env: &Env, number_of_years: u64) -> u64 {
+        number_of_years
+            .checked_mul(ONE_YEAR_IN_SECONDS)
+            .unwrap_or_else(|| panic_with_error!(env, Error::RegistrationPeriodInvalid))
+    }
+
+    // Fees for a TLD with a registrar go to the registrar, the rest stay here.
+    // A referrer's share is held here in both cases.
+    fn charge(env: &Env, payer: &Address, tld: &Bytes, price: i128, referrer: Option<&Address>) {
+        let recipient = env
+            .storage()
+            .instance()
+            .get(&DataKey::Registrar(tld.clone()))
+            .unwrap_or_else(|| env.current_contract_address());
+        let reward = referrer.map_or(0, |referrer| {
+            Self::reward_referrer(env, referrer, payer, tld, price)
+        });
+        Self::asset(env).transfer(payer, &recipient, &(price - reward));
+        if reward > 0 {
+            Self::asset(env).transfer(payer, &env.current_contract_address(), &reward);
+        }
+    }
+
+    // Upgrades are announced first and can only be executed once `eta` has
+    // passed, so holders can review a code change before it takes effect.
+    pub fn schedule_upgrade(e: Env, caller: Address, new_wasm_hash: BytesN<32>, eta: u64) {
+        e.extend_me();
+        let admin = Self::require_role(&e, caller, Role::Upgrader);
+        access_control::schedule_upgrade::<Self>(&e, admin, new_wasm_hash, eta)
+    }
+
+    pub fn cancel_upgrade(e: Env, caller: Address) {
+        e.extend_me();
+        let admin = Self::require_role(&e, caller, Role::Upgrader);
+        access_control::cancel_upgrade::<Self>(&e, admin)
+    }
+
+    pub fn execute_upgrade(e: Env, caller: Address) {
+        e.extend_me();
+        let admin = Self::require_role(&e, caller, Role::Upgrader);
+        access_control::execute_upgrade::<Self>(&e, admin)
+    }
+
+    pub fn pending_upgrade(e: Env) -> Option<ScheduledUpgrade> {
+        e.extend_me();
+        access_control::pending_upgrade(&e)
+    }
+
+    pub(crate) fn registry(env: &Env) -> registry::Client<'_> {
+        registry::Client::new(env, &env.storage().instance().get(&REGISTRY).unwrap())
+    }
+
+    pub(crate) fn asset(env: &Env) -> token::Client<'_> {
+        token::Client::new(env, &env.storage().instance().get(&ASSET).unwrap())
+    }
+}
+
+mod test;
@@ -0,0 +1,88 @@
+use crate::errors::*;
+use crate::events::*;
+use crate::types::*;
+use crate::utils::*;
+use crate::{RegistrarController, RegistrarControllerArgs, RegistrarControllerClient};
+use soroban_sdk::{contractimpl, panic_with_error, xdr::ToXdr, Address, Bytes, BytesN, Env, Vec};
+
+// A TLD can launch in phases so the best names do not all go in the first
+// block: closed until `allowlist_at`, then open to allowlisted owners until
+// `open_at`, then open to everyone. The allowlist is a merkle tree built with
+// sorted pairs, i.e. each parent is sha256(min(a, b) || max(a, b)); only its
+// root is stored. TLDs without a schedule are open.
+#[contractimpl]
+impl RegistrarController {
+    pub fn launch_schedule(env: Env, tld: Bytes) -> Option<LaunchSchedule> {
+        env.extend_me();
+        env.storage().instance().get(&DataKey::Launch(tld))
+    }
+
+    pub fn set_launch_schedule(env: Env, tld: Bytes, schedule: LaunchSchedule) {
+        env.extend_me();
+        let registrar = Self::require_tld_registrar(&env, &tld);
+        if schedule.allowlist_at > schedule.open_at {
+            panic_with_error!(&env, Error::LaunchScheduleInvalid);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::Launch(tld.clone()), &schedule);
+
+        SetLaunchScheduleEvent {
+            registrar,
+            tld,
+            schedule,
+        }
+        .publish(&env);
+    }
+
+    pub fn registration_phase(env: Env, tld: Bytes) -> RegistrationPhase {
+        env.extend_me();
+        let Some(schedule) = Self::launch_schedule(env.clone(), tld) else {
+            return RegistrationPhase::Open;
+        };
+        let now = env.ledger().timestamp();
+        if now >= schedule.open_at {
+            RegistrationPhase::Open
+        } else if now >= schedule.allowlist_at {
+            RegistrationPhase::Allowlist
+        } else {
+            RegistrationPhase::Closed
+        }
+    }
+
+    // `proof` holds the sibling hashes from the owner's leaf up to the root.
+    pub fn register_allowlisted_name(
+        env: Env,
+        name: Bytes,
+        tld: Bytes,
+        owner: Address,
+        number_of_years: u64,
+        referrer: Option<Address>,
+        proof: Vec<BytesN<32>>,
+    ) {
+        env.extend_me();
+        env.require_not_paused();
+        owner.require_auth();
+        if Self::registration_phase(env.clone(), tld.clone()) == RegistrationPhase::Closed {
+            panic_with_error!(&env, Error::RegistrationClosed);
+        }
+        // A TLD without a schedule has no allowlist to be on.
+        let schedule = Self::launch_schedule(env.clone(), tld.clone())
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotAllowlisted));
+        let mut hash: BytesN<32> = env.crypto().sha256(&owner.clone().to_xdr(&env)).into();
+        for sibling in proof.iter() {
+            let (first, second) = if hash.to_array() <= sibling.to_array() {
+                (hash, sibling)
+            } else {
+                (sibling, hash)
+            };
+            let mut pair = Bytes::from(first);
+            pair.append(&Bytes::from(second));
+            hash = env.crypto().sha256(&pair).into();
+        }
+        if hash != schedule.allowlist_root {
+            panic_with_error!(&env, Error::NotAllowlisted);
+        }
+        Self::register(env, name, tld, owner, number_of_years, referrer)
+    }
+}
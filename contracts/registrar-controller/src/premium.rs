@@ -0,0 +1,67 @@
+use crate::errors::*;
+use crate::events::*;
+use crate::types::*;
+use crate::utils::*;
+use crate::{
+    RegistrarController, RegistrarControllerArgs, RegistrarControllerClient, EXPIRY_PREMIUM,
+    ONE_DAY_IN_SECONDS,
+};
+use access_control::Role;
+use soroban_sdk::{contractimpl, panic_with_error, Address, Bytes, Env};
+
+// A Dutch auction for expired names: once a name's grace period is over, it is
+// sold at the base price plus a premium that falls to zero over a few days.
+// Whoever values the name most can take it early by paying more, instead of
+// it going to the fastest bot at the base price.
+#[contractimpl]
+impl RegistrarController {
+    pub fn expiry_premium(env: Env) -> Option<ExpiryPremium> {
+        env.extend_me();
+        env.storage().instance().get(&EXPIRY_PREMIUM)
+    }
+
+    pub fn set_expiry_premium(env: Env, caller: Address, premium: ExpiryPremium) {
+        env.extend_me();
+        let admin = Self::require_role(&env, caller, Role::PricingManager);
+        // The premium is scaled by the seconds left in the decay, which must
+        // not overflow.
+        let decay = u64::from(premium.decay_days) * ONE_DAY_IN_SECONDS;
+        if premium.start_premium < 0
+            || premium.decay_days == 0
+            || premium
+                .start_premium
+                .checked_mul(i128::from(decay))
+                .is_none()
+        {
+            panic_with_error!(&env, Error::PriceInvalid);
+        }
+        env.storage().instance().set(&EXPIRY_PREMIUM, &premium);
+
+        SetExpiryPremiumEvent { admin, premium }.publish(&env);
+    }
+
+    // What `register_name` charges for `name` right now, premium included.
+    pub fn quote_price(env: Env, name: Bytes, tld: Bytes, number_of_years: u64) -> i128 {
+        env.extend_me();
+        Self::base_price(&env, &tld, number_of_years)
+            .checked_add(Self::premium(&env, &name, &tld))
+            .unwrap_or_else(|| panic_with_error!(&env, Error::PriceInvalid))
+    }
+
+    fn premium(env: &Env, name: &Bytes, tld: &Bytes) -> i128 {
+        let Some(premium) = Self::expiry_premium(env.clone()) else {
+            return 0;
+        };
+        // Names that were never registered are sold at the base price.
+        let Some(release_time) = Self::registry(env).lookup_release_time(name, tld) else {
+            return 0;
+        };
+        let now = env.ledger().timestamp();
+        let decay = u64::from(premium.decay_days) * ONE_DAY_IN_SECONDS;
+        if now < release_time || now - release_time >= decay {
+            return 0;
+        }
+        let remaining = decay - (now - release_time);
+        premium.start_premium * i128::from(remaining) / i128::from(decay)
+    }
+}
@@ -0,0 +1,110 @@
+use crate::errors::*;
+use crate::events::*;
+use crate::types::*;
+use crate::utils::*;
+use crate::{
+    RegistrarController, RegistrarControllerArgs, RegistrarControllerClient, MAX_BASIS_POINTS,
+    REFERRAL_SHARE, UNCLAIMED_REWARDS,
+};
+use access_control::Role;
+use soroban_sdk::{contractimpl, panic_with_error, Address, Bytes, Env};
+
+// Wallets and other partners that bring in a registration can pass themselves
+// as `referrer` and earn a share of the fee, in basis points. The share is
+// taken before a TLD registrar is paid and held here until the referrer claims
+// it.
+#[contractimpl]
+impl RegistrarController {
+    pub fn referral_share(env: Env) -> u32 {
+        env.extend_me();
+        env.storage().instance().get(&REFERRAL_SHARE).unwrap_or(0)
+    }
+
+    pub fn set_referral_share(env: Env, caller: Address, share: u32) {
+        env.extend_me();
+        let admin = Self::require_role(&env, caller, Role::PricingManager);
+        if share > MAX_BASIS_POINTS {
+            panic_with_error!(&env, Error::ReferralShareInvalid);
+        }
+        env.storage().instance().set(&REFERRAL_SHARE, &share);
+
+        SetReferralShareEvent { admin, share }.publish(&env);
+    }
+
+    pub fn referral_rewards(env: Env, referrer: Address) -> ReferralRewards {
+        env.extend_me();
+        env.storage()
+            .instance()
+            .get(&DataKey::Referral(referrer))
+            .unwrap_or(ReferralRewards {
+                unclaimed: 0,
+                total: 0,
+            })
+    }
+
+    // Rewards earned by all referrers and not claimed yet, which `withdraw`
+    // leaves in place.
+    pub fn unclaimed_referral_rewards(env: Env) -> i128 {
+        env.extend_me();
+        env.storage()
+            .instance()
+            .get(&UNCLAIMED_REWARDS)
+            .unwrap_or(0)
+    }
+
+    pub fn claim_referral_rewards(env: Env, referrer: Address) -> i128 {
+        env.extend_me();
+        env.require_not_paused();
+        referrer.require_auth();
+        let mut rewards = Self::referral_rewards(env.clone(), referrer.clone());
+        let amount = rewards.unclaimed;
+        if amount == 0 {
+            panic_with_error!(&env, Error::NoReferralRewards);
+        }
+        rewards.unclaimed = 0;
+        env.storage()
+            .instance()
+            .set(&DataKey::Referral(referrer.clone()), &rewards);
+        Self::add_unclaimed_rewards(&env, -amount);
+        Self::asset(&env).transfer(&env.current_contract_address(), &referrer, &amount);
+
+        ClaimReferralRewardsEvent { referrer, amount }.publish(&env);
+        amount
+    }
+
+    // Credits `referrer` with its share of `price` and returns that share.
+    pub(crate) fn reward_referrer(
+        env: &Env,
+        referrer: &Address,
+        payer: &Address,
+        tld: &Bytes,
+        price: i128,
+    ) -> i128 {
+        if referrer == payer {
+            panic_with_error!(env, Error::ReferrerInvalid);
+        }
+        let amount =
+            price * i128::from(Self::referral_share(env.clone())) / i128::from(MAX_BASIS_POINTS);
+        let mut rewards = Self::referral_rewards(env.clone(), referrer.clone());
+        rewards.unclaimed += amount;
+        rewards.total += amount;
+        env.storage()
+            .instance()
+            .set(&DataKey::Referral(referrer.clone()), &rewards);
+        Self::add_unclaimed_rewards(env, amount);
+
+        ReferralRewardEvent {
+            referrer: referrer.clone(),
+            payer: payer.clone(),
+            tld: tld.clone(),
+            amount,
+        }
+        .publish(env);
+        amount
+    }
+
+    fn add_unclaimed_rewards(env: &Env, amount: i128) {
+        let unclaimed = Self::unclaimed_referral_rewards(env.clone()) + amount;
+        env.storage().instance().set(&UNCLAIMED_REWARDS, &unclaimed);
+    }
+}
@@ -0,0 +1,1085 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Events, Ledger},
+    token, vec,
+    xdr::ToXdr,
+    Address, BytesN, Env, IntoVal, Val, Vec,
+};
+
+const MAX_ASSET: i128 = 100000;
+const PRICE_PER_YEAR: i128 = 20;
+
+fn create_token_contract<'a>(
+    e: &Env,
+    admin: &Address,
+) -> (token::Client<'a>, token::StellarAssetClient<'a>) {
+    let sac = e.register_stellar_asset_contract_v2(admin.clone());
+    (
+        token::Client::new(e, &sac.address()),
+        token::StellarAssetClient::new(e, &sac.address()),
+    )
+}
+
+fn get_events_by_contract_id(e: &Env, contract_id: &Address) -> Vec<(Address, Vec<Val>, Val)> {
+    let all_events: Vec<(Address, Vec<Val>, Val)> = e.events().all();
+    let mut contract_events: Vec<(Address, Vec<Val>, Val)> = vec![&e];
+    for event in all_events.iter() {
+        if event.0 == contract_id.clone() {
+            contract_events.push_back(event.clone());
+        }
+    }
+    contract_events
+}
+
+fn allowlist_leaf(e: &Env, owner: &Address) -> BytesN<32> {
+    e.crypto().sha256(&owner.clone().to_xdr(e)).into()
+}
+
+fn allowlist_parent(e: &Env, a: &BytesN<32>, b: &BytesN<32>) -> BytesN<32> {
+    let (first, second) = if a.to_array() <= b.to_array() {
+        (a, b)
+    } else {
+        (b, a)
+    };
+    let mut pair = Bytes::from(first.clone());
+    pair.append(&Bytes::from(second.clone()));
+    e.crypto().sha256(&pair).into()
+}
+
+#[test]
+fn test_register_and_renew_name() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    let admin = Address::generate(&env);
+    let (token, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let registry_id = env.register(
+        registry::WASM,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let controller_id = env.register(
+        RegistrarController,
+        (&admin, &registry_id, &token_admin.address, PRICE_PER_YEAR),
+    );
+
+    let registry_client = registry::Client::new(&env, &registry_id);
+    let client = RegistrarControllerClient::new(&env, &controller_id);
+
+    let name = Bytes::from_slice(&env, "ttt".as_bytes());
+    let owner = Address::generate(&env);
+    let payer = Address::generate(&env);
+    let resolver = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET);
+    token_admin.mint(&payer, &MAX_ASSET);
+    registry_client.set_resolver(&admin, &resolver);
+    registry_client.add_controller(&admin, &controller_id);
+
+    client.register_name(&name, &com_tld, &owner, &1, &None);
+    assert_eq!(
+        get_events_by_contract_id(&env, &controller_id),
+        vec![
+            &env,
+            (
+                controller_id.clone(),
+                (Symbol::new(&env, "register_name"), EVENT_VERSION).into_val(&env),
+                RegisterNameEvent {
+                    owner: owner.clone(),
+                    name: name.clone(),
+                    tld: com_tld.clone(),
+                    number_of_years: 1,
+                    price: PRICE_PER_YEAR,
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+    assert_eq!(registry_client.get_owner(&name, &com_tld), owner);
+    assert_eq!(
+        registry_client.get_name(&name, &com_tld).expiry,
+        100 + ONE_YEAR_IN_SECONDS
+    );
+    assert_eq!(token.balance(&owner), MAX_ASSET - PRICE_PER_YEAR);
+    assert_eq!(token.balance(&controller_id), PRICE_PER_YEAR);
+
+    client.renew_name(&name, &com_tld, &payer, &2);
+    assert_eq!(
+        get_events_by_contract_id(&env, &controller_id),
+        vec![
+            &env,
+            (
+                controller_id.clone(),
+                (Symbol::new(&env, "renew_name"), EVENT_VERSION).into_val(&env),
+                RenewNameEvent {
+                    payer: payer.clone(),
+                    name: name.clone(),
+                    tld: com_tld.clone(),
+                    number_of_years: 2,
+                    price: 2 * PRICE_PER_YEAR,
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+    assert_eq!(
+        registry_client.get_name(&name, &com_tld).expiry,
+        100 + 3 * ONE_YEAR_IN_SECONDS
+    );
+    assert_eq!(token.balance(&payer), MAX_ASSET - 2 * PRICE_PER_YEAR);
+    assert_eq!(token.balance(&controller_id), 3 * PRICE_PER_YEAR);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_register_name_without_controller_role() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let registry_id = env.register(
+        registry::WASM,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let controller_id = env.register(
+        RegistrarController,
+        (&admin, &registry_id, &token_admin.address, PRICE_PER_YEAR),
+    );
+
+    let registry_client = registry::Client::new(&env, &registry_id);
+    let client = RegistrarControllerClient::new(&env, &controller_id);
+
+    let name = Bytes::from_slice(&env, "ttt".as_bytes());
+    let owner = Address::generate(&env);
+    let resolver = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET);
+    registry_client.set_resolver(&admin, &resolver);
+
+    client.register_name(&name, &com_tld, &owner, &1, &None);
+}
+
+#[test]
+fn test_set_price_and_withdraw() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (token, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let registry_id = env.register(
+        registry::WASM,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let controller_id = env.register(
+        RegistrarController,
+        (&admin, &registry_id, &token_admin.address, PRICE_PER_YEAR),
+    );
+
+    let registry_client = registry::Client::new(&env, &registry_id);
+    let client = RegistrarControllerClient::new(&env, &controller_id);
+
+    let name = Bytes::from_slice(&env, "ttt".as_bytes());
+    let owner = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let pricing_manager = Address::generate(&env);
+    let treasurer = Address::generate(&env);
+    let resolver = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET);
+    registry_client.set_resolver(&admin, &resolver);
+    registry_client.add_controller(&admin, &controller_id);
+
+    client.grant_role(&Role::PricingManager, &pricing_manager);
+    client.grant_role(&Role::Treasurer, &treasurer);
+    assert_eq!(
+        client.try_set_price_per_year(&treasurer, &50),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::NotAuthorized as u32
+        )))
+    );
+    assert_eq!(
+        client.try_set_price_per_year(&pricing_manager, &-1),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::PriceInvalid as u32
+        )))
+    );
+    client.set_price_per_year(&pricing_manager, &50);
+    assert_eq!(env.auths()[0].0, pricing_manager);
+    assert_eq!(
+        get_events_by_contract_id(&env, &controller_id),
+        vec![
+            &env,
+            (
+                controller_id.clone(),
+                (Symbol::new(&env, "set_price"), EVENT_VERSION).into_val(&env),
+                SetPriceEvent {
+                    admin: pricing_manager.clone(),
+                    price_per_year: 50,
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+    assert_eq!(client.price_per_year(), 50);
+
+    client.register_name(&name, &com_tld, &owner, &2, &None);
+    assert_eq!(token.balance(&controller_id), 100);
+
+    assert_eq!(
+        client.try_withdraw(&pricing_manager, &treasury, &60),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::NotAuthorized as u32
+        )))
+    );
+    client.withdraw(&treasurer, &treasury, &60);
+    assert_eq!(env.auths()[0].0, treasurer);
+    assert_eq!(
+        get_events_by_contract_id(&env, &controller_id),
+        vec![
+            &env,
+            (
+                controller_id.clone(),
+                (symbol_short!("withdraw"), EVENT_VERSION).into_val(&env),
+                WithdrawEvent {
+                    admin: treasurer.clone(),
+                    to: treasury.clone(),
+                    amount: 60,
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+    assert_eq!(token.balance(&treasury), 60);
+    assert_eq!(token.balance(&controller_id), 40);
+}
+
+#[test]
+fn test_tld_registrar() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (token, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let xlm_tld = Bytes::from_slice(&env, "xlm".as_bytes());
+    let registry_id = env.register(
+        registry::WASM,
+        (
+            &admin,
+            &token_admin.address,
+            vec![&env, com_tld.clone(), xlm_tld.clone()],
+        ),
+    );
+    let controller_id = env.register(
+        RegistrarController,
+        (&admin, &registry_id, &token_admin.address, PRICE_PER_YEAR),
+    );
+
+    let registry_client = registry::Client::new(&env, &registry_id);
+    let client = RegistrarControllerClient::new(&env, &controller_id);
+
+    let owner = Address::generate(&env);
+    let registrar = Address::generate(&env);
+    let resolver = Address::generate(&env);
+    let policy = TldPolicy {
+        price_per_year: 100,
+        min_name_length: 5,
+        max_years: 2,
+    };
+
+    token_admin.mint(&owner, &MAX_ASSET);
+    registry_client.set_resolver(&admin, &resolver);
+    registry_client.add_controller(&admin, &controller_id);
+
+    assert_eq!(client.tld_registrar(&xlm_tld), admin);
+    client.set_tld_registrar(&admin, &xlm_tld, &registrar);
+    assert_eq!(env.auths()[0].0, admin);
+    assert_eq!(
+        get_events_by_contract_id(&env, &controller_id),
+        vec![
+            &env,
+            (
+                controller_id.clone(),
+                (Symbol::new(&env, "set_tld_registrar"), EVENT_VERSION).into_val(&env),
+                SetTldRegistrarEvent {
+                    admin: admin.clone(),
+                    tld: xlm_tld.clone(),
+                    registrar: registrar.clone(),
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+    assert_eq!(client.tld_registrar(&xlm_tld), registrar);
+    assert_eq!(client.tld_registrar(&com_tld), admin);
+
+    client.set_tld_policy(&xlm_tld, &policy);
+    assert_eq!(env.auths()[0].0, registrar);
+    assert_eq!(
+        get_events_by_contract_id(&env, &controller_id),
+        vec![
+            &env,
+            (
+                controller_id.clone(),
+                (Symbol::new(&env, "set_tld_policy"), EVENT_VERSION).into_val(&env),
+                SetTldPolicyEvent {
+                    registrar: registrar.clone(),
+                    tld: xlm_tld.clone(),
+                    policy: policy.clone(),
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+    assert_eq!(client.tld_policy(&xlm_tld), Some(policy));
+    assert_eq!(client.tld_policy(&com_tld), None);
+
+    let short_name = Bytes::from_slice(&env, "ttt".as_bytes());
+    let name = Bytes::from_slice(&env, "ttttt".as_bytes());
+    assert_eq!(
+        client.try_register_name(&short_name, &xlm_tld, &owner, &1, &None),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::NameInvalid as u32
+        )))
+    );
+    assert_eq!(
+        client.try_register_name(&name, &xlm_tld, &owner, &3, &None),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::RegistrationPeriodInvalid as u32
+        )))
+    );
+
+    // Fees for a delegated TLD go to its registrar, the others stay here.
+    client.register_name(&name, &xlm_tld, &owner, &2, &None);
+    client.register_name(&short_name, &com_tld, &owner, &3, &None);
+    assert_eq!(token.balance(&registrar), 200);
+    assert_eq!(token.balance(&controller_id), 3 * PRICE_PER_YEAR);
+
+    client.remove_tld_registrar(&admin, &xlm_tld);
+    assert_eq!(
+        get_events_by_contract_id(&env, &controller_id),
+        vec![
+            &env,
+            (
+                controller_id.clone(),
+                (Symbol::new(&env, "remove_tld_registrar"), EVENT_VERSION).into_val(&env),
+                RemoveTldRegistrarEvent {
+                    admin: admin.clone(),
+                    tld: xlm_tld.clone(),
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+    assert_eq!(client.tld_registrar(&xlm_tld), admin);
+    client.renew_name(&name, &xlm_tld, &owner, &1);
+    assert_eq!(token.balance(&registrar), 200);
+    assert_eq!(token.balance(&controller_id), 3 * PRICE_PER_YEAR + 100);
+}
+
+#[test]
+fn test_reserved_names() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (token, token_admin) = create_token_contract(&env, &admin);
+    let xlm_tld = Bytes::from_slice(&env, "xlm".as_bytes());
+    let registry_id = env.register(
+        registry::WASM,
+        (&admin, &token_admin.address, vec![&env, xlm_tld.clone()]),
+    );
+    let controller_id = env.register(
+        RegistrarController,
+        (&admin, &registry_id, &token_admin.address, PRICE_PER_YEAR),
+    );
+
+    let registry_client = registry::Client::new(&env, &registry_id);
+    let client = RegistrarControllerClient::new(&env, &controller_id);
+
+    let name = Bytes::from_slice(&env, "ttt".as_bytes());
+    let other_name = Bytes::from_slice(&env, "uuu".as_bytes());
+    let owner = Address::generate(&env);
+    let registrar = Address::generate(&env);
+    let resolver = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET);
+    registry_client.set_resolver(&admin, &resolver);
+    registry_client.add_controller(&admin, &controller_id);
+    client.set_tld_registrar(&admin, &xlm_tld, &registrar);
+
+    client.set_reserved_names(&xlm_tld, &vec![&env, name.clone()], &true);
+    assert_eq!(env.auths()[0].0, registrar);
+    assert_eq!(
+        get_events_by_contract_id(&env, &controller_id),
+        vec![
+            &env,
+            (
+                controller_id.clone(),
+                (Symbol::new(&env, "set_reserved_names"), EVENT_VERSION).into_val(&env),
+                SetReservedNamesEvent {
+                    registrar: registrar.clone(),
+                    tld: xlm_tld.clone(),
+                    names: vec![&env, name.clone()],
+                    reserved: true,
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+    assert!(client.is_name_reserved(&name, &xlm_tld));
+    assert_eq!(
+        client.try_register_name(&name, &xlm_tld, &owner, &1, &None),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::NameReserved as u32
+        )))
+    );
+    assert_eq!(
+        client.try_register_reserved_name(&other_name, &xlm_tld, &owner, &1),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::NameNotReserved as u32
+        )))
+    );
+
+    client.register_reserved_name(&name, &xlm_tld, &owner, &1);
+    assert_eq!(env.auths()[0].0, registrar);
+    assert_eq!(
+        get_events_by_contract_id(&env, &controller_id),
+        vec![
+            &env,
+            (
+                controller_id.clone(),
+                (Symbol::new(&env, "register_reserved_name"), EVENT_VERSION).into_val(&env),
+                RegisterReservedNameEvent {
+                    registrar: registrar.clone(),
+                    owner: owner.clone(),
+                    name: name.clone(),
+                    tld: xlm_tld.clone(),
+                    number_of_years: 1,
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+    assert_eq!(registry_client.get_owner(&name, &xlm_tld), owner);
+    assert_eq!(token.balance(&owner), MAX_ASSET);
+
+    client.set_reserved_names(&xlm_tld, &vec![&env, name.clone()], &false);
+    assert!(!client.is_name_reserved(&name, &xlm_tld));
+}
+
+#[test]
+fn test_registration_phases() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let xlm_tld = Bytes::from_slice(&env, "xlm".as_bytes());
+    let registry_id = env.register(
+        registry::WASM,
+        (&admin, &token_admin.address, vec![&env, xlm_tld.clone()]),
+    );
+    let controller_id = env.register(
+        RegistrarController,
+        (&admin, &registry_id, &token_admin.address, PRICE_PER_YEAR),
+    );
+
+    let registry_client = registry::Client::new(&env, &registry_id);
+    let client = RegistrarControllerClient::new(&env, &controller_id);
+
+    let name = Bytes::from_slice(&env, "ttt".as_bytes());
+    let other_name = Bytes::from_slice(&env, "uuu".as_bytes());
+    let supporter = Address::generate(&env);
+    let other_supporter = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    let resolver = Address::generate(&env);
+
+    token_admin.mint(&supporter, &MAX_ASSET);
+    token_admin.mint(&outsider, &MAX_ASSET);
+    registry_client.set_resolver(&admin, &resolver);
+    registry_client.add_controller(&admin, &controller_id);
+
+    let supporter_leaf = allowlist_leaf(&env, &supporter);
+    let other_supporter_leaf = allowlist_leaf(&env, &other_supporter);
+    let schedule = LaunchSchedule {
+        allowlist_root: allowlist_parent(&env, &supporter_leaf, &other_supporter_leaf),
+        allowlist_at: 100,
+        open_at: 200,
+    };
+
+    assert_eq!(client.registration_phase(&xlm_tld), RegistrationPhase::Open);
+    // Without a schedule there is no allowlist to register through.
+    assert_eq!(
+        client.try_register_allowlisted_name(
+            &name,
+            &xlm_tld,
+            &supporter,
+            &1,
+            &None,
+            &vec![&env, other_supporter_leaf.clone()]
+        ),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::NotAllowlisted as u32
+        )))
+    );
+    assert_eq!(
+        client.try_set_launch_schedule(
+            &xlm_tld,
+            &LaunchSchedule {
+                allowlist_at: 300,
+                ..schedule.clone()
+            }
+        ),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::LaunchScheduleInvalid as u32
+        )))
+    );
+    client.set_launch_schedule(&xlm_tld, &schedule);
+    assert_eq!(env.auths()[0].0, admin);
+    assert_eq!(
+        get_events_by_contract_id(&env, &controller_id),
+        vec![
+            &env,
+            (
+                controller_id.clone(),
+                (Symbol::new(&env, "set_launch_schedule"), EVENT_VERSION).into_val(&env),
+                SetLaunchScheduleEvent {
+                    registrar: admin.clone(),
+                    tld: xlm_tld.clone(),
+                    schedule: schedule.clone(),
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+    assert_eq!(client.launch_schedule(&xlm_tld), Some(schedule));
+
+    let proof = vec![&env, other_supporter_leaf.clone()];
+    let registration_closed =
+        soroban_sdk::Error::from_contract_error(Error::RegistrationClosed as u32);
+    let not_allowlisted = soroban_sdk::Error::from_contract_error(Error::NotAllowlisted as u32);
+
+    env.ledger().with_mut(|li| li.timestamp = 50);
+    assert_eq!(
+        client.registration_phase(&xlm_tld),
+        RegistrationPhase::Closed
+    );
+    assert_eq!(
+        client.try_register_name(&name, &xlm_tld, &supporter, &1, &None),
+        Err(Ok(registration_closed))
+    );
+    assert_eq!(
+        client.try_register_allowlisted_name(&name, &xlm_tld, &supporter, &1, &None, &proof),
+        Err(Ok(registration_closed))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    assert_eq!(
+        client.registration_phase(&xlm_tld),
+        RegistrationPhase::Allowlist
+    );
+    assert_eq!(
+        client.try_register_name(&name, &xlm_tld, &supporter, &1, &None),
+        Err(Ok(not_allowlisted))
+    );
+    assert_eq!(
+        client.try_register_allowlisted_name(&other_name, &xlm_tld, &outsider, &1, &None, &proof),
+        Err(Ok(not_allowlisted))
+    );
+    client.register_allowlisted_name(&name, &xlm_tld, &supporter, &1, &None, &proof);
+    assert_eq!(registry_client.get_owner(&name, &xlm_tld), supporter);
+
+    env.ledger().with_mut(|li| li.timestamp = 200);
+    assert_eq!(client.registration_phase(&xlm_tld), RegistrationPhase::Open);
+    client.register_name(&other_name, &xlm_tld, &outsider, &1, &None);
+    assert_eq!(registry_client.get_owner(&other_name, &xlm_tld), outsider);
+}
+
+#[test]
+fn test_referral_rewards() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (token, token_admin) = create_token_contract(&env, &admin);
+    let xlm_tld = Bytes::from_slice(&env, "xlm".as_bytes());
+    let registry_id = env.register(
+        registry::WASM,
+        (&admin, &token_admin.address, vec![&env, xlm_tld.clone()]),
+    );
+    let controller_id = env.register(
+        RegistrarController,
+        (&admin, &registry_id, &token_admin.address, PRICE_PER_YEAR),
+    );
+
+    let registry_client = registry::Client::new(&env, &registry_id);
+    let client = RegistrarControllerClient::new(&env, &controller_id);
+
+    let name = Bytes::from_slice(&env, "ttt".as_bytes());
+    let other_name = Bytes::from_slice(&env, "uuu".as_bytes());
+    let owner = Address::generate(&env);
+    let referrer = Address::generate(&env);
+    let registrar = Address::generate(&env);
+    let resolver = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET);
+    registry_client.set_resolver(&admin, &resolver);
+    registry_client.add_controller(&admin, &controller_id);
+    client.set_tld_registrar(&admin, &xlm_tld, &registrar);
+
+    assert_eq!(client.referral_share(), 0);
+    assert_eq!(
+        client.try_set_referral_share(&admin, &10_001),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::ReferralShareInvalid as u32
+        )))
+    );
+    client.set_referral_share(&admin, &1_000);
+    assert_eq!(env.auths()[0].0, admin);
+    assert_eq!(
+        get_events_by_contract_id(&env, &controller_id),
+        vec![
+            &env,
+            (
+                controller_id.clone(),
+                (Symbol::new(&env, "set_referral_share"), EVENT_VERSION).into_val(&env),
+                SetReferralShareEvent {
+                    admin: admin.clone(),
+                    share: 1_000,
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+    assert_eq!(client.referral_share(), 1_000);
+
+    assert_eq!(
+        client.try_register_name(&name, &xlm_tld, &owner, &1, &Some(owner.clone())),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::ReferrerInvalid as u32
+        )))
+    );
+
+    client.register_name(&name, &xlm_tld, &owner, &2, &Some(referrer.clone()));
+    assert_eq!(
+        get_events_by_contract_id(&env, &controller_id),
+        vec![
+            &env,
+            (
+                controller_id.clone(),
+                (Symbol::new(&env, "referral_reward"), EVENT_VERSION).into_val(&env),
+                ReferralRewardEvent {
+                    referrer: referrer.clone(),
+                    payer: owner.clone(),
+                    tld: xlm_tld.clone(),
+                    amount: 4,
+                }
+                .into_val(&env),
+            ),
+            (
+                controller_id.clone(),
+                (Symbol::new(&env, "register_name"), EVENT_VERSION).into_val(&env),
+                RegisterNameEvent {
+                    owner: owner.clone(),
+                    name: name.clone(),
+                    tld: xlm_tld.clone(),
+                    number_of_years: 2,
+                    price: 2 * PRICE_PER_YEAR,
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+    assert_eq!(token.balance(&owner), MAX_ASSET - 2 * PRICE_PER_YEAR);
+    assert_eq!(token.balance(&registrar), 2 * PRICE_PER_YEAR - 4);
+    assert_eq!(token.balance(&controller_id), 4);
+
+    client.register_name(&other_name, &xlm_tld, &owner, &1, &Some(referrer.clone()));
+    assert_eq!(
+        client.referral_rewards(&referrer),
+        ReferralRewards {
+            unclaimed: 6,
+            total: 6,
+        }
+    );
+    assert_eq!(client.unclaimed_referral_rewards(), 6);
+
+    // Only what is left after the unclaimed rewards can be withdrawn.
+    let treasury = Address::generate(&env);
+    token_admin.mint(&controller_id, &10);
+    assert_eq!(
+        client.try_withdraw(&admin, &treasury, &11),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::WithdrawalTooLarge as u32
+        )))
+    );
+    client.withdraw(&admin, &treasury, &10);
+    assert_eq!(token.balance(&treasury), 10);
+    assert_eq!(token.balance(&controller_id), 6);
+
+    assert_eq!(client.claim_referral_rewards(&referrer), 6);
+    assert_eq!(env.auths()[0].0, referrer);
+    assert_eq!(
+        get_events_by_contract_id(&env, &controller_id),
+        vec![
+            &env,
+            (
+                controller_id.clone(),
+                (Symbol::new(&env, "claim_referral_rewards"), EVENT_VERSION).into_val(&env),
+                ClaimReferralRewardsEvent {
+                    referrer: referrer.clone(),
+                    amount: 6,
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+    assert_eq!(token.balance(&referrer), 6);
+    assert_eq!(token.balance(&controller_id), 0);
+    assert_eq!(client.unclaimed_referral_rewards(), 0);
+    assert_eq!(
+        client.referral_rewards(&referrer),
+        ReferralRewards {
+            unclaimed: 0,
+            total: 6,
+        }
+    );
+    assert_eq!(
+        client.try_claim_referral_rewards(&referrer),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::NoReferralRewards as u32
+        )))
+    );
+}
+
+#[test]
+fn test_expiry_premium() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    let admin = Address::generate(&env);
+    let (token, token_admin) = create_token_contract(&env, &admin);
+    let xlm_tld = Bytes::from_slice(&env, "xlm".as_bytes());
+    let registry_id = env.register(
+        registry::WASM,
+        (&admin, &token_admin.address, vec![&env, xlm_tld.clone()]),
+    );
+    let controller_id = env.register(
+        RegistrarController,
+        (&admin, &registry_id, &token_admin.address, PRICE_PER_YEAR),
+    );
+
+    let registry_client = registry::Client::new(&env, &registry_id);
+    let client = RegistrarControllerClient::new(&env, &controller_id);
+
+    let name = Bytes::from_slice(&env, "ttt".as_bytes());
+    let owner = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+    let resolver = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET);
+    token_admin.mint(&new_owner, &MAX_ASSET);
+    registry_client.set_resolver(&admin, &resolver);
+    registry_client.add_controller(&admin, &controller_id);
+
+    let premium = ExpiryPremium {
+        start_premium: 1_000,
+        decay_days: 10,
+    };
+    assert_eq!(client.expiry_premium(), None);
+    assert_eq!(
+        client.try_set_expiry_premium(
+            &admin,
+            &ExpiryPremium {
+                start_premium: 1_000,
+                decay_days: 0,
+            }
+        ),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::PriceInvalid as u32
+        )))
+    );
+    client.set_expiry_premium(&admin, &premium);
+    assert_eq!(env.auths()[0].0, admin);
+    assert_eq!(
+        get_events_by_contract_id(&env, &controller_id),
+        vec![
+            &env,
+            (
+                controller_id.clone(),
+                (Symbol::new(&env, "set_expiry_premium"), EVENT_VERSION).into_val(&env),
+                SetExpiryPremiumEvent {
+                    admin: admin.clone(),
+                    premium: premium.clone(),
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+    assert_eq!(client.expiry_premium(), Some(premium));
+
+    // A name that was never registered has no premium.
+    assert_eq!(client.quote_price(&name, &xlm_tld, &1), PRICE_PER_YEAR);
+    client.register_name(&name, &xlm_tld, &owner, &1, &None);
+    assert_eq!(client.quote_price(&name, &xlm_tld, &1), PRICE_PER_YEAR);
+
+    // The full premium is due right after the grace period...
+    let release_time = registry_client
+        .lookup_release_time(&name, &xlm_tld)
+        .unwrap();
+    env.ledger().with_mut(|li| li.timestamp = release_time + 1);
+    assert_eq!(
+        client.quote_price(&name, &xlm_tld, &1),
+        PRICE_PER_YEAR + 999
+    );
+
+    // ...even once anyone cleaned the lapsed name up...
+    registry_client.cleanup_expired_name(&name, &xlm_tld);
+    assert!(!registry_client.is_name_registered(&name, &xlm_tld));
+    assert_eq!(
+        client.quote_price(&name, &xlm_tld, &1),
+        PRICE_PER_YEAR + 999
+    );
+
+    // ...and halves after half of the decay period.
+    env.ledger()
+        .with_mut(|li| li.timestamp = release_time + 5 * ONE_DAY_IN_SECONDS);
+    assert_eq!(
+        client.quote_price(&name, &xlm_tld, &2),
+        2 * PRICE_PER_YEAR + 500
+    );
+    client.register_name(&name, &xlm_tld, &new_owner, &2, &None);
+    assert_eq!(registry_client.get_owner(&name, &xlm_tld), new_owner);
+    assert_eq!(
+        token.balance(&new_owner),
+        MAX_ASSET - 2 * PRICE_PER_YEAR - 500
+    );
+
+    // Once the decay period is over the name is back at the base price.
+    let release_time = registry_client
+        .lookup_release_time(&name, &xlm_tld)
+        .unwrap();
+    env.ledger()
+        .with_mut(|li| li.timestamp = release_time + 10 * ONE_DAY_IN_SECONDS);
+    assert_eq!(client.quote_price(&name, &xlm_tld, &1), PRICE_PER_YEAR);
+}
+
+#[test]
+fn test_pause() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let registry_id = env.register(
+        registry::WASM,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let controller_id = env.register(
+        RegistrarController,
+        (&admin, &registry_id, &token_admin.address, PRICE_PER_YEAR),
+    );
+
+    let registry_client = registry::Client::new(&env, &registry_id);
+    let client = RegistrarControllerClient::new(&env, &controller_id);
+
+    let name = Bytes::from_slice(&env, "ttt".as_bytes());
+    let other_name = Bytes::from_slice(&env, "uuu".as_bytes());
+    let owner = Address::generate(&env);
+    let treasurer = Address::generate(&env);
+    let pauser = Address::generate(&env);
+    let resolver = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET);
+    registry_client.set_resolver(&admin, &resolver);
+    registry_client.add_controller(&admin, &controller_id);
+    client.register_name(&name, &com_tld, &owner, &1, &None);
+
+    client.grant_role(&Role::Treasurer, &treasurer);
+    client.grant_role(&Role::Pauser, &pauser);
+    assert_eq!(
+        client.try_pause(&treasurer),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::NotAuthorized as u32
+        )))
+    );
+    client.pause(&pauser);
+    assert_eq!(env.auths()[0].0, pauser);
+    assert!(client.is_paused());
+
+    let paused = soroban_sdk::Error::from_contract_error(Error::Paused as u32);
+    assert_eq!(
+        client.try_register_name(&other_name, &com_tld, &owner, &1, &None),
+        Err(Ok(paused))
+    );
+    assert_eq!(
+        client.try_renew_name(&name, &com_tld, &owner, &1),
+        Err(Ok(paused))
+    );
+    assert_eq!(
+        client.try_withdraw(&treasurer, &treasurer, &1),
+        Err(Ok(paused))
+    );
+    assert_eq!(client.try_claim_referral_rewards(&owner), Err(Ok(paused)));
+
+    client.unpause(&pauser);
+    assert!(!client.is_paused());
+    client.renew_name(&name, &com_tld, &owner, &1);
+    client.withdraw(&treasurer, &treasurer, &1);
+}
+
+#[test]
+fn test_admin_handover_and_upgrade() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let registry_id = Address::generate(&env);
+    let controller_id = env.register(
+        RegistrarController,
+        (&admin, &registry_id, &token_admin.address, PRICE_PER_YEAR),
+    );
+    let client = RegistrarControllerClient::new(&env, &controller_id);
+
+    let new_admin = Address::generate(&env);
+    let upgrader = Address::generate(&env);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let not_authorized = soroban_sdk::Error::from_contract_error(Error::NotAuthorized as u32);
+
+    client.propose_admin(&new_admin);
+    assert_eq!(client.pending_admin(), Some(new_admin.clone()));
+    client.accept_admin();
+    assert_eq!(env.auths()[0].0, new_admin);
+    assert_eq!(client.admin(), new_admin);
+
+    let wasm_hash = env.deployer().upload_contract_wasm(registry::WASM);
+    let eta = env.ledger().timestamp() + access_control::MIN_UPGRADE_DELAY;
+    assert_eq!(
+        client.try_schedule_upgrade(&upgrader, &wasm_hash, &eta),
+        Err(Ok(not_authorized))
+    );
+    client.grant_role(&Role::Upgrader, &upgrader);
+    assert_eq!(
+        client.try_schedule_upgrade(&upgrader, &wasm_hash, &(eta - 1)),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::UpgradeEtaTooEarly as u32
+        )))
+    );
+    client.schedule_upgrade(&upgrader, &wasm_hash, &eta);
+    assert_eq!(
+        client.pending_upgrade(),
+        Some(ScheduledUpgrade {
+            new_wasm_hash: wasm_hash.clone(),
+            eta,
+        })
+    );
+    assert_eq!(
+        client.try_execute_upgrade(&upgrader),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::UpgradeNotReady as u32
+        )))
+    );
+    client.cancel_upgrade(&upgrader);
+    assert_eq!(client.pending_upgrade(), None);
+
+    // Once the signers take over, the upgrader granted by the single key is
+    // gone and admin calls only run as approved actions.
+    client.set_admin_signers(&vec![&env, first.clone(), second.clone()], &2);
+    assert!(!client.has_role(&Role::Upgrader, &upgrader));
+    assert_eq!(
+        client.try_schedule_upgrade(&upgrader, &wasm_hash, &eta),
+        Err(Ok(not_authorized))
+    );
+    assert_eq!(
+        client.try_grant_role(&Role::Pauser, &new_admin),
+        Err(Ok(not_authorized))
+    );
+
+    let id = client.propose_admin_action(&first, &AdminAction::Pause);
+    assert_eq!(
+        client.try_execute_admin_action(&first, &id),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::NotEnoughApprovals as u32
+        )))
+    );
+    client.approve_admin_action(&second, &id);
+    client.execute_admin_action(&second, &id);
+    assert!(client.is_paused());
+
+    let id = client.propose_admin_action(
+        &first,
+        &AdminAction::ScheduleUpgrade(wasm_hash.clone(), eta),
+    );
+    client.approve_admin_action(&second, &id);
+    client.execute_admin_action(&first, &id);
+    assert_eq!(
+        client.pending_upgrade(),
+        Some(ScheduledUpgrade {
+            new_wasm_hash: wasm_hash,
+            eta,
+        })
+    );
+}
+
+#[test]
+fn test_registration_period_overflow() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let registry_id = env.register(
+        registry::WASM,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let controller_id = env.register(
+        RegistrarController,
+        (&admin, &registry_id, &token_admin.address, PRICE_PER_YEAR),
+    );
+
+    let registry_client = registry::Client::new(&env, &registry_id);
+    let client = RegistrarControllerClient::new(&env, &controller_id);
+
+    let name = Bytes::from_slice(&env, "ttt".as_bytes());
+    let other_name = Bytes::from_slice(&env, "uuu".as_bytes());
+    let owner = Address::generate(&env);
+    let resolver = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET);
+    registry_client.set_resolver(&admin, &resolver);
+    registry_client.add_controller(&admin, &controller_id);
+    client.register_name(&name, &com_tld, &owner, &1, &None);
+
+    // Without a TLD policy any year count passes the period check, so the
+    // duration itself must not wrap.
+    let period_invalid = Err(Ok(soroban_sdk::Error::from_contract_error(
+        Error::RegistrationPeriodInvalid as u32,
+    )));
+    assert_eq!(
+        client.try_register_name(&other_name, &com_tld, &owner, &u64::MAX, &None),
+        period_invalid
+    );
+    assert_eq!(
+        client.try_renew_name(&name, &com_tld, &owner, &u64::MAX),
+        period_invalid
+    );
+    assert_eq!(
+        client.try_quote_price(&name, &com_tld, &u64::MAX),
+        Ok(Ok(PRICE_PER_YEAR * i128::from(u64::MAX)))
+    );
+
+    // A premium whose full amount does not fit is refused up front.
+    assert_eq!(
+        client.try_set_expiry_premium(
+            &admin,
+            &ExpiryPremium {
+                start_premium: i128::MAX,
+                decay_days: 2,
+            }
+        ),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::PriceInvalid as u32
+        )))
+    );
+}
@@ -0,0 +1,149 @@
+use crate::errors::*;
+use crate::events::*;
+use crate::types::*;
+use crate::utils::*;
+use crate::{RegistrarController, RegistrarControllerArgs, RegistrarControllerClient};
+use access_control::Role;
+use soroban_sdk::{contractimpl, panic_with_error, Address, Bytes, Env, Vec};
+
+// The TLD manager can hand a TLD over to a registrar, e.g. a partner running
+// its own TLD on the registry. The registrar then sets that TLD's policy and
+// reserved names, and registration and renewal fees for it are paid straight
+// to the registrar. TLDs without a registrar are run by the admin.
+#[contractimpl]
+impl RegistrarController {
+    pub fn tld_registrar(env: Env, tld: Bytes) -> Address {
+        env.extend_me();
+        env.storage()
+            .instance()
+            .get(&DataKey::Registrar(tld))
+            .unwrap_or_else(|| access_control::admin(&env))
+    }
+
+    pub fn set_tld_registrar(env: Env, caller: Address, tld: Bytes, registrar: Address) {
+        env.extend_me();
+        let admin = Self::require_role(&env, caller, Role::TldManager);
+        env.storage()
+            .instance()
+            .set(&DataKey::Registrar(tld.clone()), &registrar);
+
+        SetTldRegistrarEvent {
+            admin,
+            tld,
+            registrar,
+        }
+        .publish(&env);
+    }
+
+    // Takes the TLD back. Its policy and reserved names are kept for the admin.
+    pub fn remove_tld_registrar(env: Env, caller: Address, tld: Bytes) {
+        env.extend_me();
+        let admin = Self::require_role(&env, caller, Role::TldManager);
+        env.storage()
+            .instance()
+            .remove(&DataKey::Registrar(tld.clone()));
+
+        RemoveTldRegistrarEvent { admin, tld }.publish(&env);
+    }
+
+    // Without a policy a TLD is sold at `price_per_year` with no further limits.
+    pub fn tld_policy(env: Env, tld: Bytes) -> Option<TldPolicy> {
+        env.extend_me();
+        env.storage().instance().get(&DataKey::Policy(tld))
+    }
+
+    pub fn set_tld_policy(env: Env, tld: Bytes, policy: TldPolicy) {
+        env.extend_me();
+        let registrar = Self::require_tld_registrar(&env, &tld);
+        if policy.price_per_year < 0 {
+            panic_with_error!(&env, Error::PriceInvalid);
+        }
+        if policy.max_years == 0 {
+            panic_with_error!(&env, Error::RegistrationPeriodInvalid);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::Policy(tld.clone()), &policy);
+
+        SetTldPolicyEvent {
+            registrar,
+            tld,
+            policy,
+        }
+        .publish(&env);
+    }
+
+    pub fn is_name_reserved(env: Env, name: Bytes, tld: Bytes) -> bool {
+        env.extend_me();
+        env.storage().instance().has(&DataKey::Reserved(name, tld))
+    }
+
+    pub fn set_reserved_names(env: Env, tld: Bytes, names: Vec<Bytes>, reserved: bool) {
+        env.extend_me();
+        let registrar = Self::require_tld_registrar(&env, &tld);
+        for name in names.iter() {
+            let key = DataKey::Reserved(name, tld.clone());
+            if reserved {
+                env.storage().instance().set(&key, &true);
+            } else {
+                env.storage().instance().remove(&key);
+            }
+        }
+
+        SetReservedNamesEvent {
+            registrar,
+            tld,
+            names,
+            reserved,
+        }
+        .publish(&env);
+    }
+
+    // Reserved names can only be given out by the TLD's registrar, free of
+    // charge.
+    pub fn register_reserved_name(
+        env: Env,
+        name: Bytes,
+        tld: Bytes,
+        owner: Address,
+        number_of_years: u64,
+    ) {
+        env.extend_me();
+        env.require_not_paused();
+        let registrar = Self::require_tld_registrar(&env, &tld);
+        if !Self::is_name_reserved(env.clone(), name.clone(), tld.clone()) {
+            panic_with_error!(&env, Error::NameNotReserved);
+        }
+        Self::check_period(&env, &tld, number_of_years);
+        Self::registry(&env).controller_register(
+            &env.current_contract_address(),
+            &name,
+            &tld,
+            &owner,
+            &Self::duration(&env, number_of_years),
+        );
+
+        RegisterReservedNameEvent {
+            registrar,
+            owner,
+            name,
+            tld,
+            number_of_years,
+        }
+        .publish(&env);
+    }
+
+    pub(crate) fn require_tld_registrar(env: &Env, tld: &Bytes) -> Address {
+        let registrar = Self::tld_registrar(env.clone(), tld.clone());
+        registrar.require_auth();
+        registrar
+    }
+
+    pub(crate) fn check_period(env: &Env, tld: &Bytes, number_of_years: u64) {
+        let max_years =
+            Self::tld_policy(env.clone(), tld.clone()).map_or(u64::MAX, |policy| policy.max_years);
+        if number_of_years == 0 || number_of_years > max_years {
+            panic_with_error!(env, Error::RegistrationPeriodInvalid);
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use access_control::Role;
+use soroban_sdk::{contracttype, Address, Bytes, BytesN, Vec};
+
+#[contracttype]
+pub enum DataKey {
+    // tld -> registrar
+    Registrar(Bytes),
+    // tld -> policy
+    Policy(Bytes),
+    // domain.tld
+    Reserved(Bytes, Bytes),
+    // tld -> launch schedule
+    Launch(Bytes),
+    // referrer -> rewards
+    Referral(Address),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TldPolicy {
+    pub price_per_year: i128,
+    pub min_name_length: u32,
+    // Longest period a single registration or renewal may cover.
+    pub max_years: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RegistrationPhase {
+    Closed,
+    Allowlist,
+    Open,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LaunchSchedule {
+    // Merkle root over sha256(owner.to_xdr()) of every allowlisted owner.
+    pub allowlist_root: BytesN<32>,
+    pub allowlist_at: u64,
+    pub open_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReferralRewards {
+    pub unclaimed: i128,
+    // Everything earned so far, claimed or not.
+    pub total: i128,
+}
+
+// Extra price on a name leaving the registry's grace period, so it does not
+// simply go to whoever calls first. It starts at `start_premium` and decays
+// linearly to zero over `decay_days`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExpiryPremium {
+    pub start_premium: i128,
+    pub decay_days: u32,
+}
+
+// Admin entrypoints that the signers can run together once the admin is a
+// multisig. Pricing, TLD and treasury calls are left to the holders of the
+// matching role, which the signers grant.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AdminAction {
+    GrantRole(Role, Address),
+    RevokeRole(Role, Address),
+    Pause,
+    Unpause,
+    // signers, threshold
+    SetAdminSigners(Vec<Address>, u32),
+    ProposeAdmin(Address),
+    CancelAdminProposal,
+    // new wasm hash, eta
+    ScheduleUpgrade(BytesN<32>, u64),
+    CancelUpgrade,
+    ExecuteUpgrade,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminProposal {
+    pub action: AdminAction,
+    pub approvals: Vec<Address>,
+}
@@ -0,0 +1,19 @@
+use crate::errors::Error;
+use soroban_sdk::{panic_with_error, Env};
+
+pub trait Base {
+    fn extend_me(&self);
+    fn require_not_paused(&self);
+}
+
+impl Base for Env {
+    fn extend_me(&self) {
+        self.storage().instance().extend_ttl(17280, 17280 * 30);
+    }
+
+    fn require_not_paused(&self) {
+        if access_control::is_paused(self) {
+            panic_with_error!(self, Error::Paused);
+        }
+    }
+}
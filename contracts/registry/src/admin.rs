@@ -0,0 +1,134 @@
+use crate::events::EVENT_VERSION;
+use crate::types::*;
+use crate::utils::*;
+use crate::{Registry, RegistryArgs, RegistryClient};
+use access_control::Role;
+use soroban_sdk::{contractimpl, Address, Env};
+
+// Admin rotation, roles and pausing, shared with the resolver through the
+// `access-control` crate.
+#[contractimpl]
+impl Registry {
+    pub fn admin(env: Env) -> Address {
+        env.extend_me();
+        access_control::admin(&env)
+    }
+
+    pub fn pending_admin(env: Env) -> Option<Address> {
+        env.extend_me();
+        access_control::pending_admin(&env)
+    }
+
+    pub fn propose_admin(env: Env, new_admin: Address) {
+        env.extend_me();
+        let admin = Self::require_admin(&env);
+        access_control::propose_admin::<Self>(&env, admin, new_admin)
+    }
+
+    pub fn accept_admin(env: Env) {
+        env.extend_me();
+        access_control::accept_admin::<Self>(&env)
+    }
+
+    pub fn cancel_admin_proposal(env: Env) {
+        env.extend_me();
+        let admin = Self::require_admin(&env);
+        access_control::cancel_admin_proposal::<Self>(&env, admin)
+    }
+
+    pub fn has_role(env: Env, role: Role, account: Address) -> bool {
+        env.extend_me();
+        access_control::has_role(&env, role, &account)
+    }
+
+    pub fn grant_role(env: Env, role: Role, account: Address) {
+        env.extend_me();
+        let admin = Self::require_admin(&env);
+        access_control::grant_role::<Self>(&env, admin, role, account)
+    }
+
+    pub fn revoke_role(env: Env, role: Role, account: Address) {
+        env.extend_me();
+        let admin = Self::require_admin(&env);
+        access_control::revoke_role::<Self>(&env, admin, role, account)
+    }
+
+    pub fn renounce_role(env: Env, account: Address, role: Role) {
+        env.extend_me();
+        access_control::renounce_role::<Self>(&env, account, role)
+    }
+
+    pub fn is_paused(env: Env) -> bool {
+        env.extend_me();
+        access_control::is_paused(&env)
+    }
+
+    pub fn pause(env: Env, caller: Address) {
+        env.extend_me();
+        let pauser = Self::require_role(&env, caller, Role::Pauser);
+        access_control::pause::<Self>(&env, pauser)
+    }
+
+    pub fn unpause(env: Env, caller: Address) {
+        env.extend_me();
+        let pauser = Self::require_role(&env, caller, Role::Pauser);
+        access_control::unpause::<Self>(&env, pauser)
+    }
+
+    pub(crate) fn require_admin(env: &Env) -> Address {
+        access_control::require_admin(env)
+    }
+
+    pub(crate) fn require_role(env: &Env, caller: Address, role: Role) -> Address {
+        access_control::require_role(env, &caller, role);
+        caller
+    }
+}
+
+impl access_control::AdminContract for Registry {
+    const EVENT_VERSION: u32 = EVENT_VERSION;
+    type Action = AdminAction;
+
+    fn execute(env: &Env, admin: Address, action: AdminAction) {
+        match action {
+            AdminAction::SetResolver(resolver) => {
+                Self::apply_set_resolver(env.clone(), admin, resolver)
+            }
+            AdminAction::SetBaseUri(base_uri) => {
+                Self::apply_set_base_uri(env.clone(), admin, base_uri)
+            }
+            AdminAction::GrantRole(role, account) => {
+                access_control::grant_role::<Self>(env, admin, role, account)
+            }
+            AdminAction::RevokeRole(role, account) => {
+                access_control::revoke_role::<Self>(env, admin, role, account)
+            }
+            AdminAction::Pause => access_control::pause::<Self>(env, admin),
+            AdminAction::Unpause => access_control::unpause::<Self>(env, admin),
+            AdminAction::AddController(controller) => {
+                Self::apply_add_controller(env.clone(), admin, controller)
+            }
+            AdminAction::RemoveController(controller) => {
+                Self::apply_remove_controller(env.clone(), admin, controller)
+            }
+            AdminAction::SetAdminSigners(signers, threshold) => {
+                access_control::set_admin_signers::<Self>(env, admin, signers, threshold)
+            }
+            AdminAction::ProposeAdmin(new_admin) => {
+                access_control::propose_admin::<Self>(env, admin, new_admin)
+            }
+            AdminAction::CancelAdminProposal => {
+                access_control::cancel_admin_proposal::<Self>(env, admin)
+            }
+            AdminAction::ScheduleUpgrade(new_wasm_hash, eta) => {
+                access_control::schedule_upgrade::<Self>(env, admin, new_wasm_hash, eta)
+            }
+            AdminAction::CancelUpgrade => access_control::cancel_upgrade::<Self>(env, admin),
+            AdminAction::ExecuteUpgrade => access_control::execute_upgrade::<Self>(env, admin),
+            AdminAction::IndexNames(names) => Self::apply_index_names(env.clone(), names),
+            AdminAction::Migrate(batch_size) => {
+                Self::apply_migrate(env.clone(), admin, batch_size);
+            }
+        }
+    }
+}
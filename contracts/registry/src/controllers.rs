@@ -0,0 +1,57 @@
+use crate::errors::*;
+use crate::events::*;
+use crate::types::*;
+use crate::utils::*;
+use crate::{Registry, RegistryArgs, RegistryClient};
+use access_control::Role;
+use soroban_sdk::{contractimpl, panic_with_error, Address, Env};
+
+// Contracts allowed to register and renew names, see `controller_register`.
+#[contractimpl]
+impl Registry {
+    pub fn is_controller(env: Env, controller: Address) -> bool {
+        env.extend_me();
+        env.storage()
+            .instance()
+            .has(&DataKey::Controller(controller))
+    }
+
+    pub fn add_controller(env: Env, caller: Address, controller: Address) {
+        env.extend_me();
+        let admin = Self::require_role(&env, caller, Role::SuperAdmin);
+        Self::apply_add_controller(env, admin, controller)
+    }
+
+    pub fn remove_controller(env: Env, caller: Address, controller: Address) {
+        env.extend_me();
+        let admin = Self::require_role(&env, caller, Role::SuperAdmin);
+        Self::apply_remove_controller(env, admin, controller)
+    }
+
+    pub(crate) fn apply_add_controller(env: Env, admin: Address, controller: Address) {
+        env.storage()
+            .instance()
+            .set(&DataKey::Controller(controller.clone()), &true);
+
+        AddControllerEvent { admin, controller }.publish(&env);
+    }
+
+    pub(crate) fn apply_remove_controller(env: Env, admin: Address, controller: Address) {
+        env.storage()
+            .instance()
+            .remove(&DataKey::Controller(controller.clone()));
+
+        RemoveControllerEvent { admin, controller }.publish(&env);
+    }
+
+    pub(crate) fn require_controller(env: &Env, controller: &Address) {
+        if !env
+            .storage()
+            .instance()
+            .has(&DataKey::Controller(controller.clone()))
+        {
+            panic_with_error!(env, Error::NotAuthorized);
+        }
+        controller.require_auth();
+    }
+}
@@ -1,5 +1,7 @@
 use soroban_sdk::contracterror;
 
+// A code means the same error in every contract, and `sns-client` lists all of
+// them. Only the ones the registry can raise are declared here.
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
@@ -13,4 +15,22 @@ pub enum Error {
     TLDNotSupported = 7,
     NoOffer = 8,
     RecordTypeInvalid = 9,
+    NotAuthorized = 10,
+    IncorrectOwner = 11,
+    InvalidLiveUntilLedger = 12,
+    NameNotExpired = 13,
+    NoPendingAdmin = 26,
+    UpgradeEtaTooEarly = 27,
+    UpgradeNotReady = 28,
+    NoPendingUpgrade = 29,
+    Paused = 30,
+    NoMigrationPending = 31,
+    AdminActionNotFound = 32,
+    NotEnoughApprovals = 33,
+    AlreadyApproved = 34,
+    AdminSignersInvalid = 35,
+    RegistrationPeriodInvalid = 39,
+    TokenUriTooLong = 46,
+    UpgradePending = 49,
+    RoleNotGranted = 50,
 }
@@ -0,0 +1,200 @@
+use soroban_sdk::{contracttype, Address, Bytes, BytesN, Env, IntoVal, String, Symbol, Val};
+
+// Bumped whenever the fields of an existing event change. It is published as the
+// second topic so indexers can keep decoding old events after an upgrade.
+pub const EVENT_VERSION: u32 = 1;
+
+pub trait Event: IntoVal<Env, Val> + Sized {
+    const NAME: &'static str;
+
+    fn publish(self, env: &Env) {
+        env.events()
+            .publish((Symbol::new(env, Self::NAME), EVENT_VERSION), self);
+    }
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RegisterNameEvent {
+    pub controller: Address,
+    pub owner: Address,
+    pub name: Bytes,
+    pub tld: Bytes,
+    pub expiry: u64,
+}
+
+impl Event for RegisterNameEvent {
+    const NAME: &'static str = "register_name";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RenewNameEvent {
+    pub controller: Address,
+    pub name: Bytes,
+    pub tld: Bytes,
+    pub expiry: u64,
+}
+
+impl Event for RenewNameEvent {
+    const NAME: &'static str = "renew_name";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransferEvent {
+    pub from: Address,
+    pub to: Address,
+    pub name: Bytes,
+    pub tld: Bytes,
+    pub token_id: BytesN<32>,
+}
+
+impl Event for TransferEvent {
+    const NAME: &'static str = "transfer";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NameResolverChangedEvent {
+    pub caller: Address,
+    pub name: Bytes,
+    pub tld: Bytes,
+    pub resolver: Address,
+}
+
+impl Event for NameResolverChangedEvent {
+    const NAME: &'static str = "name_resolver_changed";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CleanupExpiredNameEvent {
+    pub owner: Address,
+    pub name: Bytes,
+    pub tld: Bytes,
+}
+
+impl Event for CleanupExpiredNameEvent {
+    const NAME: &'static str = "cleanup_expired_name";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ApproveEvent {
+    pub approver: Address,
+    pub approved: Address,
+    pub token_id: BytesN<32>,
+    pub live_until_ledger: u32,
+}
+
+impl Event for ApproveEvent {
+    const NAME: &'static str = "approve";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ApproveForAllEvent {
+    pub owner: Address,
+    pub operator: Address,
+    pub live_until_ledger: u32,
+}
+
+impl Event for ApproveForAllEvent {
+    const NAME: &'static str = "approve_for_all";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MakeSellOfferEvent {
+    pub seller: Address,
+    pub name: Bytes,
+    pub tld: Bytes,
+    pub price: u64,
+}
+
+impl Event for MakeSellOfferEvent {
+    const NAME: &'static str = "make_sell_offer";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CancelSellOfferEvent {
+    pub seller: Address,
+    pub name: Bytes,
+    pub tld: Bytes,
+}
+
+impl Event for CancelSellOfferEvent {
+    const NAME: &'static str = "cancel_sell_offer";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BuyNameEvent {
+    pub buyer: Address,
+    pub seller: Address,
+    pub name: Bytes,
+    pub tld: Bytes,
+    pub price: u64,
+}
+
+impl Event for BuyNameEvent {
+    const NAME: &'static str = "buy_name";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SetResolverEvent {
+    pub admin: Address,
+    pub resolver: Address,
+}
+
+impl Event for SetResolverEvent {
+    const NAME: &'static str = "set_resolver";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SetBaseUriEvent {
+    pub admin: Address,
+    pub base_uri: String,
+}
+
+impl Event for SetBaseUriEvent {
+    const NAME: &'static str = "set_base_uri";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AddControllerEvent {
+    pub admin: Address,
+    pub controller: Address,
+}
+
+impl Event for AddControllerEvent {
+    const NAME: &'static str = "add_controller";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RemoveControllerEvent {
+    pub admin: Address,
+    pub controller: Address,
+}
+
+impl Event for RemoveControllerEvent {
+    const NAME: &'static str = "remove_controller";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MigrateEvent {
+    pub admin: Address,
+    pub from_version: u32,
+    pub to_version: u32,
+}
+
+impl Event for MigrateEvent {
+    const NAME: &'static str = "migrate";
+}
@@ -1,21 +1,37 @@
 #![no_std]
+mod admin;
+mod controllers;
 mod errors;
+mod events;
+mod migration;
+mod multisig;
+mod nft;
 mod types;
 mod utils;
 use crate::errors::*;
+use crate::events::*;
 use crate::utils::*;
+use access_control::{Role, ScheduledUpgrade};
 use soroban_sdk::BytesN;
 use soroban_sdk::{
     contract, contractimpl, panic_with_error, symbol_short, token, Address, Bytes, Env, Symbol, Vec,
 };
 use types::*;
 
-const ONE_YEAR_IN_SECONDS: u64 = 365 * 24 * 60 * 60;
-const ADMIN: Symbol = symbol_short!("admin");
+const SCHEMA_VERSION: Symbol = symbol_short!("schema");
+const NAME_COUNT: Symbol = symbol_short!("name_cnt");
+const MIGRATION_CURSOR: Symbol = symbol_short!("migr_cur");
+// Layout of the entries written by this code. Bump it together with a new step
+// in `migration.rs` whenever a stored type changes.
+const CURRENT_SCHEMA_VERSION: u32 = 1;
+// Expired names stay with their owner for this long: they no longer resolve,
+// but the owner can still renew them and nobody else can register them.
+const GRACE_PERIOD: u64 = 90 * 24 * 60 * 60;
 const RESOLVER: Symbol = symbol_short!("resolver");
 const ASSET: Symbol = symbol_short!("asset");
 const TLDS: Symbol = symbol_short!("tlds");
-const ASSET_AMOUNT_PER_YEAR: u64 = 20;
+const BASE_URI: Symbol = symbol_short!("base_uri");
+const MAX_PAGE_SIZE: u32 = 50;
 
 #[contract]
 pub struct Registry;
@@ -24,39 +40,99 @@ pub struct Registry;
 impl Registry {
     pub fn __constructor(env: Env, admin: Address, asset: Address, tlds: Vec<Bytes>) {
         env.extend_me();
-        env.storage().instance().set(&ADMIN, &admin);
+        access_control::init_admin(&env, &admin);
         env.storage().instance().set(&ASSET, &asset);
         env.storage().instance().set(&TLDS, &tlds);
+        env.storage()
+            .instance()
+            .set(&SCHEMA_VERSION, &CURRENT_SCHEMA_VERSION);
     }
 
-    pub fn register_name(env: Env, name: Bytes, tld: Bytes, owner: Address, number_of_years: u64) {
+    // Registration and renewal come in through controller contracts, which own
+    // pricing, payment and registration rules, so those can change without
+    // touching the contract holding everyone's names. The registry only checks
+    // the name and records owner and expiry.
+    pub fn controller_register(
+        env: Env,
+        controller: Address,
+        name: Bytes,
+        tld: Bytes,
+        owner: Address,
+        duration: u64,
+    ) {
         env.extend_me();
-        owner.require_auth();
+        env.require_not_paused();
+        Self::require_controller(&env, &controller);
         name.validate_name(&env, false);
         tld.validate_tld(&env);
-        if Self::is_name_registered(env.clone(), name.clone(), tld.clone()) {
-            if !Self::is_name_expired(env.clone(), name.clone(), tld.clone()) {
+        if let Some(domain) = env.load_domain(&name, &tld) {
+            // The owner keeps the name until the grace period is over.
+            if domain.release_time() >= env.ledger().timestamp() {
                 panic_with_error!(&env, Error::NameAlreadyRegistered);
             }
+            env.delete_name(&name, &tld);
         }
-        token::Client::new(&env, &env.storage().instance().get(&ASSET).unwrap()).transfer(
-            &owner,
-            &env.current_contract_address(),
-            &(number_of_years * ASSET_AMOUNT_PER_YEAR).into(),
-        );
+        let expiry = env
+            .ledger()
+            .timestamp()
+            .checked_add(duration)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::RegistrationPeriodInvalid));
         let domain: Domain = Domain {
             owner: owner.clone(),
             resolver: env.storage().instance().get(&RESOLVER).unwrap(),
-            expiry: env.ledger().timestamp() + (number_of_years * ONE_YEAR_IN_SECONDS),
+            expiry,
         };
         env.storage()
             .instance()
             .set(&DataKey::Name(name.clone(), tld.clone()), &domain);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::ReleaseTime(name.clone(), tld.clone()));
+        let token_key = DataKey::Token(env.token_id(&name, &tld));
+        if !env.storage().instance().has(&token_key) {
+            env.index_name(&name, &tld);
+        }
+        env.storage()
+            .instance()
+            .set(&token_key, &(name.clone(), tld.clone()));
+        env.add_owned_name(&owner, &name, &tld);
 
-        env.events().publish(
-            (Symbol::new(&env, "register_name"),),
-            (owner, name, tld, number_of_years),
-        );
+        RegisterNameEvent {
+            controller,
+            owner,
+            name,
+            tld,
+            expiry,
+        }
+        .publish(&env);
+    }
+
+    pub fn controller_renew(env: Env, controller: Address, name: Bytes, tld: Bytes, duration: u64) {
+        env.extend_me();
+        env.require_not_paused();
+        Self::require_controller(&env, &controller);
+        let name: Bytes = name.get_root_name(&env);
+        let mut domain: Domain = env
+            .load_domain(&name, &tld)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NameNotRegistered));
+        if domain.release_time() < env.ledger().timestamp() {
+            panic_with_error!(&env, Error::NameExpired);
+        }
+        domain.expiry = domain
+            .expiry
+            .checked_add(duration)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::RegistrationPeriodInvalid));
+        env.storage()
+            .instance()
+            .set(&DataKey::Name(name.clone(), tld.clone()), &domain);
+
+        RenewNameEvent {
+            controller,
+            name,
+            tld,
+            expiry: domain.expiry,
+        }
+        .publish(&env);
     }
 
     pub fn is_name_expired(env: Env, name: Bytes, tld: Bytes) -> bool {
@@ -65,15 +141,8 @@ impl Registry {
         if !Self::is_name_registered(env.clone(), name.clone(), tld.clone()) {
             panic_with_error!(&env, Error::NameNotRegistered);
         }
-        let domain: Domain = env
-            .storage()
-            .instance()
-            .get(&DataKey::Name(name.clone(), tld.clone()))
-            .unwrap();
-        if domain.expiry < env.ledger().timestamp() {
-            return true;
-        }
-        return false;
+        let domain: Domain = env.load_domain(&name, &tld).unwrap();
+        domain.expiry < env.ledger().timestamp()
     }
 
     pub fn is_name_registered(env: Env, name: Bytes, tld: Bytes) -> bool {
@@ -93,10 +162,89 @@ impl Registry {
         if Self::is_name_expired(env.clone(), name.clone(), tld.clone()) {
             panic_with_error!(&env, Error::NameExpired);
         }
-        env.storage()
-            .instance()
-            .get(&DataKey::Name(name.clone(), tld.clone()))
-            .unwrap()
+        env.load_domain(&name, &tld).unwrap()
+    }
+
+    // Non-panicking counterparts of the getters below, for contracts that want
+    // to handle a missing or expired name instead of aborting. They are not
+    // named `try_*` because the generated clients already use that prefix.
+    pub fn lookup_name(env: Env, name: Bytes, tld: Bytes) -> Option<Domain> {
+        env.extend_me();
+        let name: Bytes = name.get_root_name(&env);
+        let domain: Domain = env.load_domain(&name, &tld)?;
+        if domain.expiry < env.ledger().timestamp() {
+            return None;
+        }
+        Some(domain)
+    }
+
+    pub fn lookup_owner(env: Env, name: Bytes, tld: Bytes) -> Option<Address> {
+        Self::lookup_name(env, name, tld).map(|domain| domain.owner)
+    }
+
+    // See `Domain::release_time`. Cleaned up names keep theirs until they are
+    // registered again, names never registered have none.
+    pub fn lookup_release_time(env: Env, name: Bytes, tld: Bytes) -> Option<u64> {
+        env.extend_me();
+        let name: Bytes = name.get_root_name(&env);
+        match env.load_domain(&name, &tld) {
+            Some(domain) => Some(domain.release_time()),
+            None => env
+                .storage()
+                .persistent()
+                .get(&DataKey::ReleaseTime(name, tld)),
+        }
+    }
+
+    pub fn lookup_sell_offer(env: Env, name: Bytes, tld: Bytes) -> Option<Offer> {
+        env.extend_me();
+        let name: Bytes = name.get_root_name(&env);
+        env.storage().instance().get(&DataKey::Offer(name, tld))
+    }
+
+    // Pages through the owner's names, `balance` of them in total. Expired
+    // names in the page are skipped, so a page can hold fewer than `limit`.
+    pub fn names_of(env: Env, owner: Address, cursor: u32, limit: u32) -> Vec<(Bytes, Bytes)> {
+        env.extend_me();
+        let count = env.owned_name_count(&owner);
+        let start = cursor.min(count);
+        let end = start.saturating_add(limit.min(MAX_PAGE_SIZE)).min(count);
+        let now = env.ledger().timestamp();
+        let mut names = Vec::new(&env);
+        for (name, tld) in env.owned_names(&owner, start, end).iter() {
+            if let Some(domain) = env.load_domain(&name, &tld) {
+                if domain.expiry >= now {
+                    names.push_back((name, tld));
+                }
+            }
+        }
+        names
+    }
+
+    pub fn cleanup_expired_name(env: Env, name: Bytes, tld: Bytes) {
+        env.extend_me();
+        env.require_not_paused();
+        let name: Bytes = name.get_root_name(&env);
+        if !Self::is_name_expired(env.clone(), name.clone(), tld.clone()) {
+            panic_with_error!(&env, Error::NameNotExpired);
+        }
+        let domain: Domain = env.load_domain(&name, &tld).unwrap();
+        // Still renewable by its owner.
+        if domain.release_time() >= env.ledger().timestamp() {
+            panic_with_error!(&env, Error::NameNotExpired);
+        }
+        env.delete_name(&name, &tld);
+        // Kept so the controller still prices the release of the name.
+        let key = DataKey::ReleaseTime(name.clone(), tld.clone());
+        env.storage().persistent().set(&key, &domain.release_time());
+        env.extend_persistent(&key);
+
+        CleanupExpiredNameEvent {
+            owner: domain.owner,
+            name,
+            tld,
+        }
+        .publish(&env);
     }
 
     pub fn get_current_contract_resolver(env: Env) -> Address {
@@ -107,37 +255,72 @@ impl Registry {
     pub fn get_name_resolver(env: Env, name: Bytes, tld: Bytes) -> Address {
         env.extend_me();
         let domain: Domain = Self::get_name(env.clone(), name.clone(), tld.clone());
-        return domain.resolver;
+        domain.resolver
     }
 
-    pub fn set_resolver(env: Env, resolver: Address) {
+    pub fn set_resolver(env: Env, caller: Address, resolver: Address) {
         env.extend_me();
-        let admin: Address = env.storage().instance().get(&ADMIN).unwrap();
-        admin.require_auth();
+        let admin = Self::require_role(&env, caller, Role::SuperAdmin);
+        Self::apply_set_resolver(env, admin, resolver)
+    }
+
+    pub(crate) fn apply_set_resolver(env: Env, admin: Address, resolver: Address) {
         env.storage().instance().set(&RESOLVER, &resolver);
+
+        SetResolverEvent { admin, resolver }.publish(&env);
+    }
+
+    pub fn set_name_resolver(
+        env: Env,
+        name: Bytes,
+        tld: Bytes,
+        caller: Address,
+        resolver: Address,
+    ) {
+        env.extend_me();
+        env.require_not_paused();
+        let name: Bytes = name.get_root_name(&env);
+        let mut domain: Domain = Self::get_name(env.clone(), name.clone(), tld.clone());
+        Self::require_owner_or_operator(&env, &domain, &caller);
+        domain.resolver = resolver.clone();
+        env.storage()
+            .instance()
+            .set(&DataKey::Name(name.clone(), tld.clone()), &domain);
+
+        NameResolverChangedEvent {
+            caller,
+            name,
+            tld,
+            resolver,
+        }
+        .publish(&env);
+    }
+
+    pub fn migrate_to_current_resolver(env: Env, name: Bytes, tld: Bytes, caller: Address) {
+        let resolver = Self::get_current_contract_resolver(env.clone());
+        Self::set_name_resolver(env, name, tld, caller, resolver);
     }
 
     pub fn get_owner(env: Env, name: Bytes, tld: Bytes) -> Address {
         env.extend_me();
         let name: Bytes = name.get_root_name(&env);
         let domain: Domain = Self::get_name(env.clone(), name.clone(), tld.clone());
-        return domain.owner;
+        domain.owner
     }
 
     pub fn transfer(env: Env, name: Bytes, tld: Bytes, new_owner: Address) {
         env.extend_me();
+        env.require_not_paused();
         let name: Bytes = name.get_root_name(&env);
         let mut domain: Domain = Self::get_name(env.clone(), name.clone(), tld.clone());
 
         domain.owner.require_auth();
-        domain.owner = new_owner;
-        env.storage()
-            .instance()
-            .set(&DataKey::Name(name.clone(), tld.clone()), &domain);
+        env.move_name(&name, &tld, &mut domain, &new_owner);
     }
 
     pub fn make_sell_offer(env: Env, name: Bytes, tld: Bytes, price: u64) {
         env.extend_me();
+        env.require_not_paused();
         let name: Bytes = name.get_root_name(&env);
         let domain: Domain = Self::get_name(env.clone(), name.clone(), tld.clone());
         domain.owner.require_auth();
@@ -151,12 +334,17 @@ impl Registry {
             .instance()
             .set(&DataKey::Offer(name.clone(), tld.clone()), &offer);
 
-        env.events().publish(
-            (Symbol::new(&env, "make_sell_offer"),),
-            (domain.owner, name, tld, price),
-        );
+        MakeSellOfferEvent {
+            seller: domain.owner,
+            name,
+            tld,
+            price,
+        }
+        .publish(&env);
     }
 
+    // Left open while paused so sellers can still pull their offers during an
+    // incident.
     pub fn cancel_sell_offer(env: Env, name: Bytes, tld: Bytes) {
         env.extend_me();
         let name: Bytes = name.get_root_name(&env);
@@ -166,10 +354,12 @@ impl Registry {
             .instance()
             .remove(&DataKey::Offer(name.clone(), tld.clone()));
 
-        env.events().publish(
-            (Symbol::new(&env, "cancel_sell_offer"),),
-            (offer.seller, name, tld),
-        );
+        CancelSellOfferEvent {
+            seller: offer.seller,
+            name,
+            tld,
+        }
+        .publish(&env);
     }
 
     pub fn get_sell_offer(env: Env, name: Bytes, tld: Bytes) -> Offer {
@@ -185,6 +375,7 @@ impl Registry {
 
     pub fn buy_name(env: Env, name: Bytes, tld: Bytes, buyer: Address) {
         env.extend_me();
+        env.require_not_paused();
         buyer.require_auth();
         let name: Bytes = name.get_root_name(&env);
         let offer: Offer = Self::get_sell_offer(env.clone(), name.clone(), tld.clone());
@@ -196,26 +387,54 @@ impl Registry {
 
         // Transfer the domain to the buyer
         let mut domain: Domain = Self::get_name(env.clone(), name.clone(), tld.clone());
-        domain.owner = buyer.clone();
-        env.storage()
-            .instance()
-            .set(&DataKey::Name(name.clone(), tld.clone()), &domain);
+        env.move_name(&name, &tld, &mut domain, &buyer);
 
         env.storage()
             .instance()
             .remove(&DataKey::Offer(name.clone(), tld.clone()));
 
-        env.events().publish(
-            (Symbol::new(&env, "buy_name"),),
-            (buyer, name, tld, offer.price),
-        );
+        BuyNameEvent {
+            buyer,
+            seller: offer.seller,
+            name,
+            tld,
+            price: offer.price,
+        }
+        .publish(&env);
+    }
+
+    fn require_owner_or_operator(env: &Env, domain: &Domain, caller: &Address) {
+        caller.require_auth();
+        if *caller != domain.owner
+            && !Self::is_approved_for_all(env.clone(), domain.owner.clone(), caller.clone())
+        {
+            panic_with_error!(env, Error::NotAuthorized);
+        }
     }
 
-    pub fn upgrade(e: Env, new_wasm_hash: BytesN<32>) {
-        let admin: Address = e.storage().instance().get(&ADMIN).unwrap();
-        admin.require_auth();
+    // Upgrades are announced first and can only be executed once `eta` has
+    // passed, so holders can review a code change before it takes effect.
+    pub fn schedule_upgrade(e: Env, caller: Address, new_wasm_hash: BytesN<32>, eta: u64) {
+        e.extend_me();
+        let admin = Self::require_role(&e, caller, Role::Upgrader);
+        access_control::schedule_upgrade::<Self>(&e, admin, new_wasm_hash, eta)
+    }
+
+    pub fn cancel_upgrade(e: Env, caller: Address) {
+        e.extend_me();
+        let admin = Self::require_role(&e, caller, Role::Upgrader);
+        access_control::cancel_upgrade::<Self>(&e, admin)
+    }
+
+    pub fn execute_upgrade(e: Env, caller: Address) {
+        e.extend_me();
+        let admin = Self::require_role(&e, caller, Role::Upgrader);
+        access_control::execute_upgrade::<Self>(&e, admin)
+    }
 
-        e.deployer().update_current_contract_wasm(new_wasm_hash);
+    pub fn pending_upgrade(e: Env) -> Option<ScheduledUpgrade> {
+        e.extend_me();
+        access_control::pending_upgrade(&e)
     }
 }
 mod test;
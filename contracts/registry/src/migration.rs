@@ -0,0 +1,108 @@
+use crate::errors::*;
+use crate::events::*;
+use crate::types::*;
+use crate::utils::*;
+use crate::{
+    Registry, RegistryArgs, RegistryClient, CURRENT_SCHEMA_VERSION, MIGRATION_CURSOR, NAME_COUNT,
+    SCHEMA_VERSION,
+};
+use access_control::Role;
+use soroban_sdk::{contractimpl, panic_with_error, Address, Bytes, Env, Vec};
+
+// After an upgrade that changes a stored type, `migrate` rewrites every name
+// in the new layout, a batch at a time so no call runs out of budget. Until it
+// finishes, reads fall back to the old layout.
+#[contractimpl]
+impl Registry {
+    // Deployments from before the schema version was stored report 0.
+    pub fn schema_version(env: Env) -> u32 {
+        env.extend_me();
+        env.storage().instance().get(&SCHEMA_VERSION).unwrap_or(0)
+    }
+
+    // Names registered before schema version 1 are not in the name index that
+    // `migrate` walks. The admin adds them here, e.g. from past
+    // `register_name` events, before migrating.
+    pub fn index_names(env: Env, caller: Address, names: Vec<(Bytes, Bytes)>) {
+        env.extend_me();
+        Self::require_role(&env, caller, Role::Upgrader);
+        Self::apply_index_names(env, names)
+    }
+
+    pub(crate) fn apply_index_names(env: Env, names: Vec<(Bytes, Bytes)>) {
+        if Self::schema_version(env.clone()) >= 1 {
+            panic_with_error!(&env, Error::NoMigrationPending);
+        }
+        for (name, tld) in names.iter() {
+            if !env
+                .storage()
+                .instance()
+                .has(&DataKey::Name(name.clone(), tld.clone()))
+            {
+                panic_with_error!(&env, Error::NameNotRegistered);
+            }
+            env.index_name(&name, &tld);
+        }
+    }
+
+    // Migrates up to `batch_size` indexed names and returns whether the whole
+    // migration is done.
+    pub fn migrate(env: Env, caller: Address, batch_size: u32) -> bool {
+        env.extend_me();
+        let admin = Self::require_role(&env, caller, Role::Upgrader);
+        Self::apply_migrate(env, admin, batch_size)
+    }
+
+    pub(crate) fn apply_migrate(env: Env, admin: Address, batch_size: u32) -> bool {
+        let from_version = Self::schema_version(env.clone());
+        if from_version >= CURRENT_SCHEMA_VERSION {
+            panic_with_error!(&env, Error::NoMigrationPending);
+        }
+
+        let count: u32 = env.storage().instance().get(&NAME_COUNT).unwrap_or(0);
+        let cursor: u32 = env.storage().instance().get(&MIGRATION_CURSOR).unwrap_or(0);
+        let end = cursor.saturating_add(batch_size).min(count);
+        for index in cursor..end {
+            let (name, tld): (Bytes, Bytes) = env
+                .storage()
+                .persistent()
+                .get(&DataKey::NameAt(index))
+                .unwrap();
+            Self::migrate_name(&env, from_version, &name, &tld);
+        }
+        if end < count {
+            env.storage().instance().set(&MIGRATION_CURSOR, &end);
+            return false;
+        }
+
+        env.storage().instance().remove(&MIGRATION_CURSOR);
+        env.storage()
+            .instance()
+            .set(&SCHEMA_VERSION, &CURRENT_SCHEMA_VERSION);
+
+        MigrateEvent {
+            admin,
+            from_version,
+            to_version: CURRENT_SCHEMA_VERSION,
+        }
+        .publish(&env);
+        true
+    }
+
+    fn migrate_name(env: &Env, from_version: u32, name: &Bytes, tld: &Bytes) {
+        // 0 -> 1: names get a token and are listed under their owner. Names
+        // registered again since the upgrade already have both.
+        if from_version < 1 {
+            if let Some(domain) = env.load_domain(name, tld) {
+                env.storage().instance().set(
+                    &DataKey::Token(env.token_id(name, tld)),
+                    &(name.clone(), tld.clone()),
+                );
+                let position_key = DataKey::OwnedNamePosition(name.clone(), tld.clone());
+                if !env.storage().persistent().has(&position_key) {
+                    env.add_owned_name(&domain.owner, name, tld);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use crate::types::*;
+use crate::utils::*;
+use crate::{Registry, RegistryArgs, RegistryClient};
+use access_control::AdminSigners;
+use soroban_sdk::{contractimpl, Address, Env, Vec};
+
+// The multisig that can replace the admin key, shared with the resolver
+// through the `access-control` crate. Approved actions run through
+// `AdminContract::execute` in `admin.rs`.
+#[contractimpl]
+impl Registry {
+    pub fn admin_signers(env: Env) -> Option<AdminSigners> {
+        env.extend_me();
+        access_control::admin_signers(&env)
+    }
+
+    pub fn set_admin_signers(env: Env, signers: Vec<Address>, threshold: u32) {
+        env.extend_me();
+        let admin = Self::require_admin(&env);
+        access_control::set_admin_signers::<Self>(&env, admin, signers, threshold)
+    }
+
+    pub fn admin_action(env: Env, id: u32) -> AdminProposal {
+        env.extend_me();
+        let proposal = access_control::admin_action::<Self>(&env, id);
+        AdminProposal {
+            action: proposal.action,
+            approvals: proposal.approvals,
+        }
+    }
+
+    pub fn propose_admin_action(env: Env, signer: Address, action: AdminAction) -> u32 {
+        env.extend_me();
+        access_control::propose_admin_action::<Self>(&env, signer, action)
+    }
+
+    pub fn approve_admin_action(env: Env, signer: Address, id: u32) {
+        env.extend_me();
+        access_control::approve_admin_action::<Self>(&env, signer, id)
+    }
+
+    pub fn execute_admin_action(env: Env, signer: Address, id: u32) {
+        env.extend_me();
+        access_control::execute_admin_action::<Self>(&env, signer, id)
+    }
+}
@@ -0,0 +1,189 @@
+use crate::errors::*;
+use crate::events::*;
+use crate::types::*;
+use crate::utils::*;
+use crate::{Registry, RegistryArgs, RegistryClient, BASE_URI};
+use access_control::Role;
+use soroban_sdk::{contractimpl, panic_with_error, Address, Bytes, BytesN, Env, String};
+
+const NFT_NAME: &str = "Stellar Name Service";
+const NFT_SYMBOL: &str = "SNS";
+const MAX_TOKEN_URI_LENGTH: usize = 256;
+
+// Token-style view over registered names, so wallets and marketplaces that
+// speak the Soroban NFT interface can show them as collectibles. The token id
+// of `name.tld` is `sha256("name.tld")`.
+#[contractimpl]
+impl Registry {
+    pub fn name(env: Env) -> String {
+        String::from_str(&env, NFT_NAME)
+    }
+
+    pub fn symbol(env: Env) -> String {
+        String::from_str(&env, NFT_SYMBOL)
+    }
+
+    pub fn token_id(env: Env, name: Bytes, tld: Bytes) -> BytesN<32> {
+        env.token_id(&name.get_root_name(&env), &tld)
+    }
+
+    // Counts expired names too, until they are cleaned up or registered again.
+    pub fn balance(env: Env, owner: Address) -> u32 {
+        env.extend_me();
+        env.owned_name_count(&owner)
+    }
+
+    pub fn owner_of(env: Env, token_id: BytesN<32>) -> Address {
+        env.extend_me();
+        let (name, tld) = Self::get_token_name(&env, &token_id);
+        Self::get_owner(env, name, tld)
+    }
+
+    pub fn token_uri(env: Env, token_id: BytesN<32>) -> String {
+        env.extend_me();
+        let (name, tld) = Self::get_token_name(&env, &token_id);
+        let base_uri: String = env
+            .storage()
+            .instance()
+            .get(&BASE_URI)
+            .unwrap_or(String::from_str(&env, ""));
+
+        let base_len = base_uri.len() as usize;
+        let name_len = name.len() as usize;
+        let tld_len = tld.len() as usize;
+        let uri_len = base_len + name_len + 1 + tld_len;
+        if uri_len > MAX_TOKEN_URI_LENGTH {
+            panic_with_error!(&env, Error::TokenUriTooLong);
+        }
+        let mut uri = [0u8; MAX_TOKEN_URI_LENGTH];
+        base_uri.copy_into_slice(&mut uri[..base_len]);
+        name.copy_into_slice(&mut uri[base_len..base_len + name_len]);
+        uri[base_len + name_len] = b'.';
+        tld.copy_into_slice(&mut uri[base_len + name_len + 1..uri_len]);
+        String::from_bytes(&env, &uri[..uri_len])
+    }
+
+    pub fn set_base_uri(env: Env, caller: Address, base_uri: String) {
+        env.extend_me();
+        let admin = Self::require_role(&env, caller, Role::SuperAdmin);
+        Self::apply_set_base_uri(env, admin, base_uri)
+    }
+
+    pub(crate) fn apply_set_base_uri(env: Env, admin: Address, base_uri: String) {
+        env.storage().instance().set(&BASE_URI, &base_uri);
+
+        SetBaseUriEvent { admin, base_uri }.publish(&env);
+    }
+
+    pub fn approve(
+        env: Env,
+        approver: Address,
+        approved: Address,
+        token_id: BytesN<32>,
+        live_until_ledger: u32,
+    ) {
+        env.extend_me();
+        env.require_not_paused();
+        approver.require_auth();
+        let owner = Self::owner_of(env.clone(), token_id.clone());
+        if approver != owner && !Self::is_approved_for_all(env.clone(), owner, approver.clone()) {
+            panic_with_error!(&env, Error::NotAuthorized);
+        }
+        if live_until_ledger < env.ledger().sequence() {
+            panic_with_error!(&env, Error::InvalidLiveUntilLedger);
+        }
+        let approval = Approval {
+            approved: approved.clone(),
+            live_until_ledger,
+        };
+        env.storage()
+            .instance()
+            .set(&DataKey::Approval(token_id.clone()), &approval);
+
+        ApproveEvent {
+            approver,
+            approved,
+            token_id,
+            live_until_ledger,
+        }
+        .publish(&env);
+    }
+
+    pub fn approve_for_all(env: Env, owner: Address, operator: Address, live_until_ledger: u32) {
+        env.extend_me();
+        env.require_not_paused();
+        owner.require_auth();
+        let key = DataKey::Operator(owner.clone(), operator.clone());
+        if live_until_ledger == 0 {
+            env.storage().instance().remove(&key);
+        } else {
+            if live_until_ledger < env.ledger().sequence() {
+                panic_with_error!(&env, Error::InvalidLiveUntilLedger);
+            }
+            env.storage().instance().set(&key, &live_until_ledger);
+        }
+
+        ApproveForAllEvent {
+            owner,
+            operator,
+            live_until_ledger,
+        }
+        .publish(&env);
+    }
+
+    pub fn get_approved(env: Env, token_id: BytesN<32>) -> Option<Address> {
+        env.extend_me();
+        let approval: Option<Approval> = env.storage().instance().get(&DataKey::Approval(token_id));
+        match approval {
+            Some(approval) if approval.live_until_ledger >= env.ledger().sequence() => {
+                Some(approval.approved)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn is_approved_for_all(env: Env, owner: Address, operator: Address) -> bool {
+        env.extend_me();
+        let live_until_ledger: Option<u32> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Operator(owner, operator));
+        match live_until_ledger {
+            Some(live_until_ledger) => live_until_ledger >= env.ledger().sequence(),
+            None => false,
+        }
+    }
+
+    pub fn transfer_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        token_id: BytesN<32>,
+    ) {
+        env.extend_me();
+        env.require_not_paused();
+        spender.require_auth();
+        let (name, tld) = Self::get_token_name(&env, &token_id);
+        let mut domain: Domain = Self::get_name(env.clone(), name.clone(), tld.clone());
+        if domain.owner != from {
+            panic_with_error!(&env, Error::IncorrectOwner);
+        }
+        if spender != from
+            && Self::get_approved(env.clone(), token_id.clone()) != Some(spender.clone())
+            && !Self::is_approved_for_all(env.clone(), from.clone(), spender)
+        {
+            panic_with_error!(&env, Error::NotAuthorized);
+        }
+        env.move_name(&name, &tld, &mut domain, &to);
+    }
+
+    fn get_token_name(env: &Env, token_id: &BytesN<32>) -> (Bytes, Bytes) {
+        env.storage()
+            .instance()
+            .get(&DataKey::Token(token_id.clone()))
+            .unwrap_or_else(|| {
+                panic_with_error!(env, Error::NameNotRegistered);
+            })
+    }
+}
@@ -1,11 +1,29 @@
 #![cfg(test)]
+#![allow(clippy::bool_assert_comparison, clippy::needless_lifetimes)]
 
 use super::*;
+use access_control::{
+    AcceptAdminEvent, AdminSigners, CancelAdminProposalEvent, CancelUpgradeEvent,
+    ExecuteAdminActionEvent, GrantRoleEvent, PauseEvent, ProposeAdminEvent, RenounceRoleEvent,
+    RevokeRoleEvent, Role, ScheduleUpgradeEvent, UpgradeEvent, MIN_UPGRADE_DELAY,
+};
 use soroban_sdk::{
     testutils::{Address as _, Events, Ledger},
-    token, vec, Address, Bytes, Env, IntoVal, Val,
+    token, vec, Address, Bytes, Env, IntoVal, String, Val,
 };
 const MAX_ASSET_AMOUNT: i128 = 100000;
+const ONE_YEAR_IN_SECONDS: u64 = 365 * 24 * 60 * 60;
+
+// The registry as first released, upgraded with `upgrade(hash)`.
+mod registry_v0 {
+    soroban_sdk::contractimport!(file = "testdata/registry_v0.wasm");
+}
+
+mod registry_current {
+    soroban_sdk::contractimport!(
+        file = "../../target/wasm32-unknown-unknown/release/registry.wasm"
+    );
+}
 
 fn create_token_contract<'a>(
     e: &Env,
@@ -37,25 +55,27 @@ fn test_basic_funtional() {
     let env = Env::default();
     env.mock_all_auths();
     let admin = Address::generate(&env);
-    let (token, token_admin) = create_token_contract(&env, &admin);
+    let (_, token_admin) = create_token_contract(&env, &admin);
     let com_tld = Bytes::from_slice(&env, "com".as_bytes());
     let contract_id = env.register(
         Registry,
         (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
     );
     let client = RegistryClient::new(&env, &contract_id);
+    let controller = Address::generate(&env);
+    client.add_controller(&admin, &controller);
 
     let name = Bytes::from_slice(&env, "ttt".as_bytes());
     let owner = Address::generate(&env);
     let resolver = Address::generate(&env);
 
     token_admin.mint(&owner, &MAX_ASSET_AMOUNT);
-    client.set_resolver(&resolver);
+    client.set_resolver(&admin, &resolver);
 
     let is_registered = client.is_name_registered(&name, &com_tld);
     assert_eq!(is_registered, false);
 
-    client.register_name(&name, &com_tld, &owner, &1);
+    client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
 
     assert_eq!(
         get_events_by_contract_id(&env, &contract_id),
@@ -63,8 +83,15 @@ fn test_basic_funtional() {
             &env,
             (
                 contract_id.clone(),
-                (Symbol::new(&env, "register_name"),).into_val(&env),
-                (owner.clone(), name.clone(), com_tld.clone(), 1u64).into_val(&env)
+                (Symbol::new(&env, "register_name"), EVENT_VERSION).into_val(&env),
+                RegisterNameEvent {
+                    controller: controller.clone(),
+                    owner: owner.clone(),
+                    name: name.clone(),
+                    tld: com_tld.clone(),
+                    expiry: ONE_YEAR_IN_SECONDS,
+                }
+                .into_val(&env)
             )
         ]
     );
@@ -75,14 +102,6 @@ fn test_basic_funtional() {
     assert_eq!(client.get_owner(&name, &com_tld), owner);
     assert_eq!(client.get_name_resolver(&name, &com_tld), resolver);
     assert_eq!(client.is_name_expired(&name, &com_tld), false);
-    assert_eq!(
-        token.balance(&owner),
-        i128::from(MAX_ASSET_AMOUNT - i128::from(ASSET_AMOUNT_PER_YEAR * 1))
-    );
-    assert_eq!(
-        token.balance(&contract_id),
-        i128::from(ASSET_AMOUNT_PER_YEAR * 1)
-    );
 }
 
 #[test]
@@ -97,15 +116,17 @@ fn test_make_sell_offer() {
         (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
     );
     let client = RegistryClient::new(&env, &contract_id);
+    let controller = Address::generate(&env);
+    client.add_controller(&admin, &controller);
 
     let name = Bytes::from_slice(&env, "ttt".as_bytes());
     let owner = Address::generate(&env);
     let resolver = Address::generate(&env);
 
     token_admin.mint(&owner, &MAX_ASSET_AMOUNT);
-    client.set_resolver(&resolver);
+    client.set_resolver(&admin, &resolver);
 
-    client.register_name(&name, &com_tld, &owner, &1);
+    client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
 
     client.make_sell_offer(&name, &com_tld, &10);
 
@@ -115,8 +136,14 @@ fn test_make_sell_offer() {
             &env,
             (
                 contract_id.clone(),
-                (Symbol::new(&env, "make_sell_offer"),).into_val(&env),
-                (owner.clone(), name.clone(), com_tld.clone(), 10u64).into_val(&env),
+                (Symbol::new(&env, "make_sell_offer"), EVENT_VERSION).into_val(&env),
+                MakeSellOfferEvent {
+                    seller: owner.clone(),
+                    name: name.clone(),
+                    tld: com_tld.clone(),
+                    price: 10,
+                }
+                .into_val(&env),
             )
         ]
     );
@@ -135,14 +162,33 @@ fn test_make_sell_offer() {
             &env,
             (
                 contract_id.clone(),
-                (Symbol::new(&env, "buy_name"),).into_val(&env),
-                (buyer.clone(), name.clone(), com_tld.clone(), 10u64).into_val(&env),
+                (symbol_short!("transfer"), EVENT_VERSION).into_val(&env),
+                TransferEvent {
+                    from: owner.clone(),
+                    to: buyer.clone(),
+                    name: name.clone(),
+                    tld: com_tld.clone(),
+                    token_id: client.token_id(&name, &com_tld),
+                }
+                .into_val(&env),
+            ),
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "buy_name"), EVENT_VERSION).into_val(&env),
+                BuyNameEvent {
+                    buyer: buyer.clone(),
+                    seller: owner.clone(),
+                    name: name.clone(),
+                    tld: com_tld.clone(),
+                    price: 10,
+                }
+                .into_val(&env),
             )
         ]
     );
 
     assert_eq!(client.get_owner(&name, &com_tld), buyer);
-    assert_eq!(token.balance(&owner), i128::from(MAX_ASSET_AMOUNT - 10));
+    assert_eq!(token.balance(&owner), MAX_ASSET_AMOUNT + 10);
 }
 
 #[test]
@@ -158,15 +204,17 @@ fn test_cancel_sell_offer() {
         (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
     );
     let client = RegistryClient::new(&env, &contract_id);
+    let controller = Address::generate(&env);
+    client.add_controller(&admin, &controller);
 
     let name = Bytes::from_slice(&env, "ttt".as_bytes());
     let owner = Address::generate(&env);
     let resolver = Address::generate(&env);
 
     token_admin.mint(&owner, &MAX_ASSET_AMOUNT);
-    client.set_resolver(&resolver);
+    client.set_resolver(&admin, &resolver);
 
-    client.register_name(&name, &com_tld, &owner, &1);
+    client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
 
     client.make_sell_offer(&name, &com_tld, &10);
 
@@ -178,8 +226,13 @@ fn test_cancel_sell_offer() {
             &env,
             (
                 contract_id.clone(),
-                (Symbol::new(&env, "cancel_sell_offer"),).into_val(&env),
-                (owner.clone(), name.clone(), com_tld.clone()).into_val(&env),
+                (Symbol::new(&env, "cancel_sell_offer"), EVENT_VERSION).into_val(&env),
+                CancelSellOfferEvent {
+                    seller: owner.clone(),
+                    name: name.clone(),
+                    tld: com_tld.clone(),
+                }
+                .into_val(&env),
             )
         ]
     );
@@ -199,18 +252,20 @@ fn test_check_sub_domain() {
         (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
     );
     let client = RegistryClient::new(&env, &contract_id);
+    let controller = Address::generate(&env);
+    client.add_controller(&admin, &controller);
 
     let name = Bytes::from_slice(&env, "ttt".as_bytes());
     let owner = Address::generate(&env);
     let resolver = Address::generate(&env);
 
     token_admin.mint(&owner, &MAX_ASSET_AMOUNT);
-    client.set_resolver(&resolver);
+    client.set_resolver(&admin, &resolver);
 
     let is_registered = client.is_name_registered(&name, &com_tld);
     assert_eq!(is_registered, false);
 
-    client.register_name(&name, &com_tld, &owner, &1);
+    client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
 
     let sub_name = Bytes::from_slice(&env, "me.ttt".as_bytes());
 
@@ -232,14 +287,16 @@ fn test_name_invalid_because_uppercase() {
         (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
     );
     let client = RegistryClient::new(&env, &contract_id);
+    let controller = Address::generate(&env);
+    client.add_controller(&admin, &controller);
 
     let name = Bytes::from_slice(&env, "DOMAIN".as_bytes());
     let owner = Address::generate(&env);
     let resolver = Address::generate(&env);
 
     token_admin.mint(&owner, &MAX_ASSET_AMOUNT);
-    client.set_resolver(&resolver);
-    client.register_name(&name, &com_tld, &owner, &1);
+    client.set_resolver(&admin, &resolver);
+    client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
 }
 
 #[test]
@@ -255,14 +312,16 @@ fn test_name_invalid_because_not_ascii_alphabetic() {
         (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
     );
     let client = RegistryClient::new(&env, &contract_id);
+    let controller = Address::generate(&env);
+    client.add_controller(&admin, &controller);
 
     let name = Bytes::from_slice(&env, "domain-".as_bytes());
     let owner = Address::generate(&env);
     let resolver = Address::generate(&env);
 
     token_admin.mint(&owner, &MAX_ASSET_AMOUNT);
-    client.set_resolver(&resolver);
-    client.register_name(&name, &com_tld, &owner, &1);
+    client.set_resolver(&admin, &resolver);
+    client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
 }
 
 #[test]
@@ -278,14 +337,16 @@ fn test_name_invalid_because_too_short() {
         (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
     );
     let client = RegistryClient::new(&env, &contract_id);
+    let controller = Address::generate(&env);
+    client.add_controller(&admin, &controller);
 
     let name = Bytes::from_slice(&env, "d0".as_bytes());
     let owner = Address::generate(&env);
     let resolver = Address::generate(&env);
 
     token_admin.mint(&owner, &MAX_ASSET_AMOUNT);
-    client.set_resolver(&resolver);
-    client.register_name(&name, &com_tld, &owner, &1);
+    client.set_resolver(&admin, &resolver);
+    client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
 }
 
 #[test]
@@ -301,14 +362,16 @@ fn test_name_invalid_because_too_long() {
         (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
     );
     let client = RegistryClient::new(&env, &contract_id);
+    let controller = Address::generate(&env);
+    client.add_controller(&admin, &controller);
 
     let name = Bytes::from_slice(&env, "thisdomainisverylongsoitshouldbeinvalid".as_bytes());
     let owner = Address::generate(&env);
     let resolver = Address::generate(&env);
 
     token_admin.mint(&owner, &MAX_ASSET_AMOUNT);
-    client.set_resolver(&resolver);
-    client.register_name(&name, &com_tld, &owner, &1);
+    client.set_resolver(&admin, &resolver);
+    client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
 }
 
 #[test]
@@ -323,15 +386,17 @@ fn test_transfer_owner_ship() {
         (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
     );
     let client = RegistryClient::new(&env, &contract_id);
+    let controller = Address::generate(&env);
+    client.add_controller(&admin, &controller);
 
     let name = Bytes::from_slice(&env, "test".as_bytes());
     let owner = Address::generate(&env);
     let resolver = Address::generate(&env);
 
     token_admin.mint(&owner, &MAX_ASSET_AMOUNT);
-    client.set_resolver(&resolver);
+    client.set_resolver(&admin, &resolver);
 
-    client.register_name(&name, &com_tld, &owner, &1);
+    client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
 
     let new_owner = Address::generate(&env);
 
@@ -354,11 +419,13 @@ fn test_register_unsupported_tld() {
         (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
     );
     let client = RegistryClient::new(&env, &contract_id);
+    let controller = Address::generate(&env);
+    client.add_controller(&admin, &controller);
 
     let name = Bytes::from_slice(&env, "test".as_bytes());
     let owner = Address::generate(&env);
 
-    client.register_name(&name, &net_tld, &owner, &1)
+    client.controller_register(&controller, &name, &net_tld, &owner, &ONE_YEAR_IN_SECONDS)
 }
 
 #[test]
@@ -392,14 +459,16 @@ fn test_name_should_be_expired() {
         (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
     );
     let client = RegistryClient::new(&env, &contract_id);
+    let controller = Address::generate(&env);
+    client.add_controller(&admin, &controller);
 
     let name = Bytes::from_slice(&env, "test".as_bytes());
     let owner = Address::generate(&env);
     let resolver = Address::generate(&env);
-    client.set_resolver(&resolver);
+    client.set_resolver(&admin, &resolver);
 
     token_admin.mint(&owner, &MAX_ASSET_AMOUNT);
-    client.register_name(&name, &com_tld, &owner, &1);
+    client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
 
     env.ledger().set_timestamp(1000000000000000);
 
@@ -419,16 +488,1657 @@ fn test_register_name_that_expired() {
         (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
     );
     let client = RegistryClient::new(&env, &contract_id);
+    let controller = Address::generate(&env);
+    client.add_controller(&admin, &controller);
+
+    let name = Bytes::from_slice(&env, "test".as_bytes());
+    let owner = Address::generate(&env);
+    let resolver = Address::generate(&env);
+    client.set_resolver(&admin, &resolver);
+
+    token_admin.mint(&owner, &MAX_ASSET_AMOUNT);
+    client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+
+    env.ledger().set_timestamp(1000000000000000);
+
+    client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+}
+
+#[test]
+fn test_grace_period() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let contract_id = env.register(
+        Registry,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let client = RegistryClient::new(&env, &contract_id);
+    let controller = Address::generate(&env);
+    client.add_controller(&admin, &controller);
+
+    let name = Bytes::from_slice(&env, "test".as_bytes());
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+    client.set_resolver(&admin, &Address::generate(&env));
+
+    assert_eq!(client.lookup_release_time(&name, &com_tld), None);
+    client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+    let release_time = ONE_YEAR_IN_SECONDS + GRACE_PERIOD;
+    assert_eq!(
+        client.lookup_release_time(&name, &com_tld),
+        Some(release_time)
+    );
+
+    // Expired names stop resolving but stay with their owner.
+    env.ledger().set_timestamp(ONE_YEAR_IN_SECONDS + 1);
+    assert_eq!(client.lookup_name(&name, &com_tld), None);
+    assert_eq!(
+        client.try_controller_register(&controller, &name, &com_tld, &other, &ONE_YEAR_IN_SECONDS),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::NameAlreadyRegistered as u32
+        )))
+    );
+    assert_eq!(
+        client.try_cleanup_expired_name(&name, &com_tld),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::NameNotExpired as u32
+        )))
+    );
+    assert_eq!(
+        client.try_controller_renew(&controller, &name, &com_tld, &u64::MAX),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::RegistrationPeriodInvalid as u32
+        )))
+    );
+    client.controller_renew(&controller, &name, &com_tld, &ONE_YEAR_IN_SECONDS);
+    assert_eq!(client.get_owner(&name, &com_tld), owner);
+    assert_eq!(
+        client.lookup_release_time(&name, &com_tld),
+        Some(release_time + ONE_YEAR_IN_SECONDS)
+    );
+
+    // After the grace period the name can neither be renewed nor kept.
+    env.ledger()
+        .set_timestamp(release_time + ONE_YEAR_IN_SECONDS + 1);
+    assert_eq!(
+        client.try_controller_renew(&controller, &name, &com_tld, &ONE_YEAR_IN_SECONDS),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::NameExpired as u32
+        )))
+    );
+    client.controller_register(&controller, &name, &com_tld, &other, &ONE_YEAR_IN_SECONDS);
+    assert_eq!(client.get_owner(&name, &com_tld), other);
+}
+
+#[test]
+fn test_nft_owner_of_consistent_with_get_owner() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let contract_id = env.register(
+        Registry,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let client = RegistryClient::new(&env, &contract_id);
+    let controller = Address::generate(&env);
+    client.add_controller(&admin, &controller);
+
+    let name = Bytes::from_slice(&env, "test".as_bytes());
+    let owner = Address::generate(&env);
+    let resolver = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET_AMOUNT);
+    client.set_resolver(&admin, &resolver);
+    client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+
+    let token_id = client.token_id(&name, &com_tld);
+    assert_eq!(
+        token_id,
+        client.token_id(&Bytes::from_slice(&env, "me.test".as_bytes()), &com_tld)
+    );
+    assert_eq!(
+        client.owner_of(&token_id),
+        client.get_owner(&name, &com_tld)
+    );
+    assert_eq!(client.balance(&owner), 1);
+    assert_eq!(
+        client.name(),
+        String::from_str(&env, "Stellar Name Service")
+    );
+    assert_eq!(client.symbol(), String::from_str(&env, "SNS"));
+
+    client.set_base_uri(&admin, &String::from_str(&env, "https://sns.xlm/"));
+    assert_eq!(
+        client.token_uri(&token_id),
+        String::from_str(&env, "https://sns.xlm/test.com")
+    );
+
+    let long_uri = [b'a'; 250];
+    client.set_base_uri(&admin, &String::from_bytes(&env, &long_uri));
+    assert_eq!(
+        client.try_token_uri(&token_id),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::TokenUriTooLong as u32
+        )))
+    );
+    client.set_base_uri(&admin, &String::from_str(&env, "https://sns.xlm/"));
+
+    let new_owner = Address::generate(&env);
+    client.transfer(&name, &com_tld, &new_owner);
+
+    assert_eq!(client.owner_of(&token_id), new_owner);
+    assert_eq!(
+        client.owner_of(&token_id),
+        client.get_owner(&name, &com_tld)
+    );
+    assert_eq!(client.balance(&owner), 0);
+    assert_eq!(client.balance(&new_owner), 1);
+}
+
+#[test]
+fn test_nft_transfer_from_with_approval() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let contract_id = env.register(
+        Registry,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let client = RegistryClient::new(&env, &contract_id);
+    let controller = Address::generate(&env);
+    client.add_controller(&admin, &controller);
+
+    let name = Bytes::from_slice(&env, "test".as_bytes());
+    let owner = Address::generate(&env);
+    let resolver = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET_AMOUNT);
+    client.set_resolver(&admin, &resolver);
+    client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+
+    let token_id = client.token_id(&name, &com_tld);
+    let spender = Address::generate(&env);
+    let receiver = Address::generate(&env);
+
+    client.approve(&owner, &spender, &token_id, &1000);
+    assert_eq!(client.get_approved(&token_id), Some(spender.clone()));
+
+    client.transfer_from(&spender, &owner, &receiver, &token_id);
+
+    assert_eq!(
+        get_events_by_contract_id(&env, &contract_id),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("transfer"), EVENT_VERSION).into_val(&env),
+                TransferEvent {
+                    from: owner.clone(),
+                    to: receiver.clone(),
+                    name: name.clone(),
+                    tld: com_tld.clone(),
+                    token_id: token_id.clone(),
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+    assert_eq!(client.get_owner(&name, &com_tld), receiver);
+    assert_eq!(client.owner_of(&token_id), receiver);
+    assert_eq!(client.get_approved(&token_id), None);
+    assert_eq!(client.balance(&owner), 0);
+    assert_eq!(client.balance(&receiver), 1);
+}
+
+#[test]
+fn test_nft_transfer_from_by_operator() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let contract_id = env.register(
+        Registry,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let client = RegistryClient::new(&env, &contract_id);
+    let controller = Address::generate(&env);
+    client.add_controller(&admin, &controller);
+
+    let name = Bytes::from_slice(&env, "test".as_bytes());
+    let owner = Address::generate(&env);
+    let resolver = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET_AMOUNT);
+    client.set_resolver(&admin, &resolver);
+    client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+
+    let token_id = client.token_id(&name, &com_tld);
+    let operator = Address::generate(&env);
+    let receiver = Address::generate(&env);
+
+    client.approve_for_all(&owner, &operator, &1000);
+    assert!(client.is_approved_for_all(&owner, &operator));
+
+    client.transfer_from(&operator, &owner, &receiver, &token_id);
+    assert_eq!(client.get_owner(&name, &com_tld), receiver);
+
+    client.approve_for_all(&owner, &operator, &0);
+    assert!(!client.is_approved_for_all(&owner, &operator));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_nft_transfer_from_without_approval() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let contract_id = env.register(
+        Registry,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let client = RegistryClient::new(&env, &contract_id);
+    let controller = Address::generate(&env);
+    client.add_controller(&admin, &controller);
+
+    let name = Bytes::from_slice(&env, "test".as_bytes());
+    let owner = Address::generate(&env);
+    let resolver = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET_AMOUNT);
+    client.set_resolver(&admin, &resolver);
+    client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+
+    let token_id = client.token_id(&name, &com_tld);
+    let spender = Address::generate(&env);
+
+    client.transfer_from(&spender, &owner, &spender, &token_id);
+}
+
+#[test]
+fn test_nft_balance_after_expired_name_registered_again() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let contract_id = env.register(
+        Registry,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let client = RegistryClient::new(&env, &contract_id);
+    let controller = Address::generate(&env);
+    client.add_controller(&admin, &controller);
+
+    let name = Bytes::from_slice(&env, "test".as_bytes());
+    let owner = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+    let resolver = Address::generate(&env);
+    client.set_resolver(&admin, &resolver);
+
+    token_admin.mint(&owner, &MAX_ASSET_AMOUNT);
+    token_admin.mint(&new_owner, &MAX_ASSET_AMOUNT);
+    client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+    client.make_sell_offer(&name, &com_tld, &10);
+
+    env.ledger().set_timestamp(1000000000000000);
+
+    client.controller_register(
+        &controller,
+        &name,
+        &com_tld,
+        &new_owner,
+        &ONE_YEAR_IN_SECONDS,
+    );
+
+    let token_id = client.token_id(&name, &com_tld);
+    assert_eq!(client.owner_of(&token_id), new_owner);
+    assert_eq!(client.balance(&owner), 0);
+    assert_eq!(client.balance(&new_owner), 1);
+    assert!(client.try_get_sell_offer(&name, &com_tld).is_err());
+}
+
+#[test]
+fn test_names_of_follows_register_transfer_and_sale() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let contract_id = env.register(
+        Registry,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let client = RegistryClient::new(&env, &contract_id);
+    let controller = Address::generate(&env);
+    client.add_controller(&admin, &controller);
+
+    let first = Bytes::from_slice(&env, "first".as_bytes());
+    let second = Bytes::from_slice(&env, "second".as_bytes());
+    let third = Bytes::from_slice(&env, "third".as_bytes());
+    let owner = Address::generate(&env);
+    let resolver = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET_AMOUNT);
+    client.set_resolver(&admin, &resolver);
+    client.controller_register(&controller, &first, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+    client.controller_register(&controller, &second, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+    client.controller_register(&controller, &third, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+
+    assert_eq!(
+        client.names_of(&owner, &0, &2),
+        vec![
+            &env,
+            (first.clone(), com_tld.clone()),
+            (second.clone(), com_tld.clone())
+        ]
+    );
+    assert_eq!(
+        client.names_of(&owner, &2, &2),
+        vec![&env, (third.clone(), com_tld.clone())]
+    );
+    assert_eq!(client.names_of(&owner, &3, &2), vec![&env]);
+
+    let new_owner = Address::generate(&env);
+    client.transfer(&first, &com_tld, &new_owner);
+
+    let buyer = Address::generate(&env);
+    token_admin.mint(&buyer, &MAX_ASSET_AMOUNT);
+    client.make_sell_offer(&second, &com_tld, &10);
+    client.buy_name(&second, &com_tld, &buyer);
+
+    assert_eq!(
+        client.names_of(&owner, &0, &10),
+        vec![&env, (third.clone(), com_tld.clone())]
+    );
+    assert_eq!(
+        client.names_of(&new_owner, &0, &10),
+        vec![&env, (first.clone(), com_tld.clone())]
+    );
+    assert_eq!(
+        client.names_of(&buyer, &0, &10),
+        vec![&env, (second.clone(), com_tld.clone())]
+    );
+
+    // Expired names drop out of the list before they are cleaned up, but
+    // still count towards the balance.
+    let fourth = Bytes::from_slice(&env, "fourth".as_bytes());
+    client.controller_register(
+        &controller,
+        &fourth,
+        &com_tld,
+        &owner,
+        &(ONE_YEAR_IN_SECONDS * 2),
+    );
+    env.ledger().set_timestamp(ONE_YEAR_IN_SECONDS + 1);
+    assert_eq!(
+        client.names_of(&owner, &0, &10),
+        vec![&env, (fourth.clone(), com_tld.clone())]
+    );
+    assert_eq!(client.balance(&owner), 2);
+    env.as_contract(&contract_id, || {
+        let key = DataKey::OwnedNameCount(owner.clone());
+        assert_eq!(env.storage().persistent().get(&key), Some(2u32));
+        assert!(env
+            .storage()
+            .persistent()
+            .has(&DataKey::OwnedName(owner.clone(), 1)));
+        assert!(!env
+            .storage()
+            .persistent()
+            .has(&DataKey::OwnedName(owner.clone(), 2)));
+    });
+}
+
+#[test]
+fn test_cleanup_expired_name() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let contract_id = env.register(
+        Registry,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let client = RegistryClient::new(&env, &contract_id);
+    let controller = Address::generate(&env);
+    client.add_controller(&admin, &controller);
 
     let name = Bytes::from_slice(&env, "test".as_bytes());
     let owner = Address::generate(&env);
     let resolver = Address::generate(&env);
-    client.set_resolver(&resolver);
+    client.set_resolver(&admin, &resolver);
 
     token_admin.mint(&owner, &MAX_ASSET_AMOUNT);
-    client.register_name(&name, &com_tld, &owner, &1);
+    client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+    let release_time = client.lookup_release_time(&name, &com_tld).unwrap();
 
     env.ledger().set_timestamp(1000000000000000);
 
-    client.register_name(&name, &com_tld, &owner, &1);
+    client.cleanup_expired_name(&name, &com_tld);
+
+    assert!(!client.is_name_registered(&name, &com_tld));
+    assert_eq!(client.names_of(&owner, &0, &10), vec![&env]);
+    assert_eq!(client.balance(&owner), 0);
+    // The release time outlives the cleanup until the name is taken again.
+    assert_eq!(
+        client.lookup_release_time(&name, &com_tld),
+        Some(release_time)
+    );
+    client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+    assert_eq!(
+        client.lookup_release_time(&name, &com_tld),
+        Some(1000000000000000 + ONE_YEAR_IN_SECONDS + GRACE_PERIOD)
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #13)")]
+fn test_cleanup_name_not_expired() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let contract_id = env.register(
+        Registry,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let client = RegistryClient::new(&env, &contract_id);
+    let controller = Address::generate(&env);
+    client.add_controller(&admin, &controller);
+
+    let name = Bytes::from_slice(&env, "test".as_bytes());
+    let owner = Address::generate(&env);
+    let resolver = Address::generate(&env);
+    client.set_resolver(&admin, &resolver);
+
+    token_admin.mint(&owner, &MAX_ASSET_AMOUNT);
+    client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+
+    client.cleanup_expired_name(&name, &com_tld);
+}
+
+#[test]
+fn test_renew_name_event() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let contract_id = env.register(
+        Registry,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let client = RegistryClient::new(&env, &contract_id);
+    let controller = Address::generate(&env);
+    client.add_controller(&admin, &controller);
+
+    let name = Bytes::from_slice(&env, "test".as_bytes());
+    let owner = Address::generate(&env);
+    let payer = Address::generate(&env);
+    let resolver = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET_AMOUNT);
+    token_admin.mint(&payer, &MAX_ASSET_AMOUNT);
+    client.set_resolver(&admin, &resolver);
+    client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+
+    client.controller_renew(&controller, &name, &com_tld, &(2 * ONE_YEAR_IN_SECONDS));
+
+    assert_eq!(
+        get_events_by_contract_id(&env, &contract_id),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "renew_name"), EVENT_VERSION).into_val(&env),
+                RenewNameEvent {
+                    controller: controller.clone(),
+                    name: name.clone(),
+                    tld: com_tld.clone(),
+                    expiry: 3 * ONE_YEAR_IN_SECONDS,
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+    assert_eq!(
+        client.get_name(&name, &com_tld).expiry,
+        3 * ONE_YEAR_IN_SECONDS
+    );
+}
+
+#[test]
+fn test_transfer_event() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let contract_id = env.register(
+        Registry,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let client = RegistryClient::new(&env, &contract_id);
+    let controller = Address::generate(&env);
+    client.add_controller(&admin, &controller);
+
+    let name = Bytes::from_slice(&env, "test".as_bytes());
+    let owner = Address::generate(&env);
+    let resolver = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET_AMOUNT);
+    client.set_resolver(&admin, &resolver);
+    client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+
+    let new_owner = Address::generate(&env);
+    client.transfer(&name, &com_tld, &new_owner);
+
+    assert_eq!(
+        get_events_by_contract_id(&env, &contract_id),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("transfer"), EVENT_VERSION).into_val(&env),
+                TransferEvent {
+                    from: owner.clone(),
+                    to: new_owner.clone(),
+                    name: name.clone(),
+                    tld: com_tld.clone(),
+                    token_id: client.token_id(&name, &com_tld),
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+}
+
+#[test]
+fn test_cleanup_expired_name_event() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let contract_id = env.register(
+        Registry,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let client = RegistryClient::new(&env, &contract_id);
+    let controller = Address::generate(&env);
+    client.add_controller(&admin, &controller);
+
+    let name = Bytes::from_slice(&env, "test".as_bytes());
+    let owner = Address::generate(&env);
+    let resolver = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET_AMOUNT);
+    client.set_resolver(&admin, &resolver);
+    client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+
+    env.ledger().set_timestamp(1000000000000000);
+    client.cleanup_expired_name(&name, &com_tld);
+
+    assert_eq!(
+        get_events_by_contract_id(&env, &contract_id),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "cleanup_expired_name"), EVENT_VERSION).into_val(&env),
+                CleanupExpiredNameEvent {
+                    owner: owner.clone(),
+                    name: name.clone(),
+                    tld: com_tld.clone(),
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+}
+
+#[test]
+fn test_approve_events() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let contract_id = env.register(
+        Registry,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let client = RegistryClient::new(&env, &contract_id);
+    let controller = Address::generate(&env);
+    client.add_controller(&admin, &controller);
+
+    let name = Bytes::from_slice(&env, "test".as_bytes());
+    let owner = Address::generate(&env);
+    let resolver = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET_AMOUNT);
+    client.set_resolver(&admin, &resolver);
+    client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+
+    let token_id = client.token_id(&name, &com_tld);
+    let spender = Address::generate(&env);
+
+    client.approve(&owner, &spender, &token_id, &1000);
+
+    assert_eq!(
+        get_events_by_contract_id(&env, &contract_id),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("approve"), EVENT_VERSION).into_val(&env),
+                ApproveEvent {
+                    approver: owner.clone(),
+                    approved: spender.clone(),
+                    token_id: token_id.clone(),
+                    live_until_ledger: 1000,
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+
+    client.approve_for_all(&owner, &spender, &1000);
+
+    assert_eq!(
+        get_events_by_contract_id(&env, &contract_id),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "approve_for_all"), EVENT_VERSION).into_val(&env),
+                ApproveForAllEvent {
+                    owner: owner.clone(),
+                    operator: spender.clone(),
+                    live_until_ledger: 1000,
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+}
+
+#[test]
+fn test_admin_events() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let contract_id = env.register(
+        Registry,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let client = RegistryClient::new(&env, &contract_id);
+
+    let resolver = Address::generate(&env);
+    client.set_resolver(&admin, &resolver);
+
+    assert_eq!(
+        get_events_by_contract_id(&env, &contract_id),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "set_resolver"), EVENT_VERSION).into_val(&env),
+                SetResolverEvent {
+                    admin: admin.clone(),
+                    resolver: resolver.clone(),
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+
+    let base_uri = String::from_str(&env, "https://sns.xlm/");
+    client.set_base_uri(&admin, &base_uri);
+
+    assert_eq!(
+        get_events_by_contract_id(&env, &contract_id),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "set_base_uri"), EVENT_VERSION).into_val(&env),
+                SetBaseUriEvent {
+                    admin: admin.clone(),
+                    base_uri,
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+}
+
+#[test]
+fn test_set_name_resolver() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let contract_id = env.register(
+        Registry,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let client = RegistryClient::new(&env, &contract_id);
+    let controller = Address::generate(&env);
+    client.add_controller(&admin, &controller);
+
+    let name = Bytes::from_slice(&env, "test".as_bytes());
+    let owner = Address::generate(&env);
+    let resolver = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET_AMOUNT);
+    client.set_resolver(&admin, &resolver);
+    client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+
+    let custom_resolver = Address::generate(&env);
+    client.set_name_resolver(&name, &com_tld, &owner, &custom_resolver);
+
+    assert_eq!(
+        get_events_by_contract_id(&env, &contract_id),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "name_resolver_changed"), EVENT_VERSION).into_val(&env),
+                NameResolverChangedEvent {
+                    caller: owner.clone(),
+                    name: name.clone(),
+                    tld: com_tld.clone(),
+                    resolver: custom_resolver.clone(),
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+    assert_eq!(client.get_name_resolver(&name, &com_tld), custom_resolver);
+    assert_eq!(client.get_current_contract_resolver(), resolver);
+
+    let operator = Address::generate(&env);
+    let new_resolver = Address::generate(&env);
+    client.approve_for_all(&owner, &operator, &1000);
+    client.set_resolver(&admin, &new_resolver);
+    client.migrate_to_current_resolver(&name, &com_tld, &operator);
+
+    assert_eq!(client.get_name_resolver(&name, &com_tld), new_resolver);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_set_name_resolver_not_owner() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let contract_id = env.register(
+        Registry,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let client = RegistryClient::new(&env, &contract_id);
+    let controller = Address::generate(&env);
+    client.add_controller(&admin, &controller);
+
+    let name = Bytes::from_slice(&env, "test".as_bytes());
+    let owner = Address::generate(&env);
+    let resolver = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET_AMOUNT);
+    client.set_resolver(&admin, &resolver);
+    client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+
+    let stranger = Address::generate(&env);
+    client.set_name_resolver(&name, &com_tld, &stranger, &stranger);
+}
+
+#[test]
+fn test_lookup_functions() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let contract_id = env.register(
+        Registry,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let client = RegistryClient::new(&env, &contract_id);
+    let controller = Address::generate(&env);
+    client.add_controller(&admin, &controller);
+
+    let name = Bytes::from_slice(&env, "ttt".as_bytes());
+    let owner = Address::generate(&env);
+    let resolver = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET_AMOUNT);
+    client.set_resolver(&admin, &resolver);
+
+    assert_eq!(client.lookup_name(&name, &com_tld), None);
+    assert_eq!(client.lookup_owner(&name, &com_tld), None);
+    assert_eq!(client.lookup_sell_offer(&name, &com_tld), None);
+
+    client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+    client.make_sell_offer(&name, &com_tld, &10);
+
+    assert_eq!(
+        client.lookup_name(&name, &com_tld),
+        Some(client.get_name(&name, &com_tld))
+    );
+    assert_eq!(
+        client.lookup_owner(&Bytes::from_slice(&env, "sub.ttt".as_bytes()), &com_tld),
+        Some(owner.clone())
+    );
+    assert_eq!(
+        client.lookup_sell_offer(&name, &com_tld),
+        Some(client.get_sell_offer(&name, &com_tld))
+    );
+
+    env.ledger().set_timestamp(1000000000000000);
+    assert_eq!(client.lookup_name(&name, &com_tld), None);
+    assert_eq!(client.lookup_owner(&name, &com_tld), None);
+}
+
+#[test]
+fn test_upgrade_timelock() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let contract_id = env.register(
+        Registry,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let client = RegistryClient::new(&env, &contract_id);
+
+    let wasm_hash = env.deployer().upload_contract_wasm(registry_current::WASM);
+    let eta = env.ledger().timestamp() + MIN_UPGRADE_DELAY;
+
+    assert_eq!(
+        client.try_execute_upgrade(&admin),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::NoPendingUpgrade as u32
+        )))
+    );
+    assert_eq!(
+        client.try_cancel_upgrade(&admin),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::NoPendingUpgrade as u32
+        )))
+    );
+    assert_eq!(
+        client.try_schedule_upgrade(&admin, &wasm_hash, &(eta - 1)),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::UpgradeEtaTooEarly as u32
+        )))
+    );
+
+    client.schedule_upgrade(&admin, &wasm_hash, &eta);
+    assert_eq!(
+        get_events_by_contract_id(&env, &contract_id),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "schedule_upgrade"), EVENT_VERSION).into_val(&env),
+                ScheduleUpgradeEvent {
+                    admin: admin.clone(),
+                    new_wasm_hash: wasm_hash.clone(),
+                    eta,
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+    assert_eq!(
+        client.pending_upgrade(),
+        Some(ScheduledUpgrade {
+            new_wasm_hash: wasm_hash.clone(),
+            eta,
+        })
+    );
+
+    // A second schedule cannot replace the pending one.
+    assert_eq!(
+        client.try_schedule_upgrade(&admin, &wasm_hash, &(eta + 1)),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::UpgradePending as u32
+        )))
+    );
+
+    client.cancel_upgrade(&admin);
+    assert_eq!(
+        get_events_by_contract_id(&env, &contract_id),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "cancel_upgrade"), EVENT_VERSION).into_val(&env),
+                CancelUpgradeEvent {
+                    admin: admin.clone(),
+                    new_wasm_hash: wasm_hash.clone(),
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+    assert_eq!(client.pending_upgrade(), None);
+
+    let eta = eta + 1;
+    client.schedule_upgrade(&admin, &wasm_hash, &eta);
+    env.ledger().set_timestamp(eta - 1);
+    assert_eq!(
+        client.try_execute_upgrade(&admin),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::UpgradeNotReady as u32
+        )))
+    );
+
+    env.ledger().set_timestamp(eta);
+    client.execute_upgrade(&admin);
+    assert_eq!(
+        get_events_by_contract_id(&env, &contract_id),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("upgrade"), EVENT_VERSION).into_val(&env),
+                UpgradeEvent {
+                    admin: admin.clone(),
+                    new_wasm_hash: wasm_hash.clone(),
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+    assert_eq!(client.pending_upgrade(), None);
+    assert_eq!(
+        client.try_execute_upgrade(&admin),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::NoPendingUpgrade as u32
+        )))
+    );
+}
+
+#[test]
+fn test_admin_handover() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let contract_id = env.register(
+        Registry,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let client = RegistryClient::new(&env, &contract_id);
+
+    let new_admin = Address::generate(&env);
+    assert_eq!(
+        client.try_accept_admin(),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::NoPendingAdmin as u32
+        )))
+    );
+
+    client.propose_admin(&new_admin);
+    assert_eq!(
+        get_events_by_contract_id(&env, &contract_id),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "propose_admin"), EVENT_VERSION).into_val(&env),
+                ProposeAdminEvent {
+                    admin: admin.clone(),
+                    pending_admin: new_admin.clone(),
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+    assert_eq!(client.pending_admin(), Some(new_admin.clone()));
+
+    client.cancel_admin_proposal();
+    assert_eq!(
+        get_events_by_contract_id(&env, &contract_id),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "cancel_admin_proposal"), EVENT_VERSION).into_val(&env),
+                CancelAdminProposalEvent {
+                    admin: admin.clone(),
+                    pending_admin: new_admin.clone(),
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+    assert_eq!(client.pending_admin(), None);
+    assert_eq!(client.admin(), admin);
+
+    client.propose_admin(&new_admin);
+    client.accept_admin();
+    assert_eq!(env.auths()[0].0, new_admin);
+    assert_eq!(
+        get_events_by_contract_id(&env, &contract_id),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "accept_admin"), EVENT_VERSION).into_val(&env),
+                AcceptAdminEvent {
+                    previous_admin: admin.clone(),
+                    admin: new_admin.clone(),
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+    assert_eq!(client.admin(), new_admin);
+    assert_eq!(client.pending_admin(), None);
+
+    let resolver = Address::generate(&env);
+    client.set_resolver(&new_admin, &resolver);
+    assert_eq!(env.auths()[0].0, new_admin);
+}
+
+#[test]
+fn test_pause() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let contract_id = env.register(
+        Registry,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let client = RegistryClient::new(&env, &contract_id);
+    let controller = Address::generate(&env);
+    client.add_controller(&admin, &controller);
+
+    let name = Bytes::from_slice(&env, "ttt".as_bytes());
+    let other_name = Bytes::from_slice(&env, "uuu".as_bytes());
+    let owner = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let resolver = Address::generate(&env);
+    let pauser = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET_AMOUNT);
+    token_admin.mint(&buyer, &MAX_ASSET_AMOUNT);
+    client.set_resolver(&admin, &resolver);
+    client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+    client.make_sell_offer(&name, &com_tld, &10);
+    let token_id = client.token_id(&name, &com_tld);
+
+    client.grant_role(&Role::Pauser, &pauser);
+    client.pause(&pauser);
+    assert_eq!(env.auths()[0].0, pauser);
+    assert_eq!(
+        get_events_by_contract_id(&env, &contract_id),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("pause"), EVENT_VERSION).into_val(&env),
+                PauseEvent {
+                    pauser: pauser.clone(),
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+    assert!(client.is_paused());
+
+    let paused = Err(Ok(soroban_sdk::Error::from_contract_error(
+        Error::Paused as u32,
+    )));
+    assert_eq!(
+        client.try_controller_register(
+            &controller,
+            &other_name,
+            &com_tld,
+            &owner,
+            &ONE_YEAR_IN_SECONDS
+        ),
+        paused
+    );
+    assert_eq!(
+        client.try_controller_renew(&controller, &name, &com_tld, &ONE_YEAR_IN_SECONDS),
+        paused
+    );
+    assert_eq!(client.try_cleanup_expired_name(&name, &com_tld), paused);
+    assert_eq!(
+        client.try_set_name_resolver(&name, &com_tld, &owner, &resolver),
+        paused
+    );
+    assert_eq!(
+        client.try_migrate_to_current_resolver(&name, &com_tld, &owner),
+        paused
+    );
+    assert_eq!(client.try_transfer(&name, &com_tld, &buyer), paused);
+    assert_eq!(client.try_make_sell_offer(&name, &com_tld, &20), paused);
+    assert_eq!(client.try_buy_name(&name, &com_tld, &buyer), paused);
+    assert_eq!(client.try_approve(&owner, &buyer, &token_id, &1000), paused);
+    assert_eq!(client.try_approve_for_all(&owner, &buyer, &1000), paused);
+    assert_eq!(
+        client.try_transfer_from(&owner, &owner, &buyer, &token_id),
+        paused
+    );
+
+    assert_eq!(client.get_owner(&name, &com_tld), owner);
+    assert_eq!(client.get_name(&name, &com_tld).owner, owner);
+    assert_eq!(client.get_sell_offer(&name, &com_tld).price, 10);
+    client.cancel_sell_offer(&name, &com_tld);
+
+    client.unpause(&pauser);
+    assert!(!client.is_paused());
+    client.transfer(&name, &com_tld, &buyer);
+    assert_eq!(client.get_owner(&name, &com_tld), buyer);
+}
+
+#[test]
+fn test_roles() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let contract_id = env.register(
+        Registry,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let client = RegistryClient::new(&env, &contract_id);
+    let ops = Address::generate(&env);
+    let lead = Address::generate(&env);
+    let resolver = Address::generate(&env);
+    let not_authorized = Err(Ok(soroban_sdk::Error::from_contract_error(
+        Error::NotAuthorized as u32,
+    )));
+    let role_not_granted = Err(Ok(soroban_sdk::Error::from_contract_error(
+        Error::RoleNotGranted as u32,
+    )));
+
+    // The admin holds every role without a grant.
+    assert!(client.has_role(&Role::Upgrader, &admin));
+    assert!(!client.has_role(&Role::Upgrader, &ops));
+    assert_eq!(client.try_pause(&ops), not_authorized);
+    assert_eq!(client.try_set_resolver(&ops, &resolver), not_authorized);
+
+    client.grant_role(&Role::Pauser, &ops);
+    assert_eq!(env.auths()[0].0, admin);
+    assert_eq!(
+        get_events_by_contract_id(&env, &contract_id),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "grant_role"), EVENT_VERSION).into_val(&env),
+                GrantRoleEvent {
+                    admin: admin.clone(),
+                    role: Role::Pauser,
+                    account: ops.clone(),
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+    assert!(client.has_role(&Role::Pauser, &ops));
+    client.pause(&ops);
+    client.unpause(&ops);
+    // A role covers only its own entrypoints.
+    assert_eq!(client.try_cancel_upgrade(&ops), not_authorized);
+
+    // A super admin holds every role, but only the admin grants them.
+    client.grant_role(&Role::SuperAdmin, &lead);
+    assert!(client.has_role(&Role::Upgrader, &lead));
+    client.set_resolver(&lead, &resolver);
+    assert_eq!(env.auths()[0].0, lead);
+    assert_eq!(client.get_current_contract_resolver(), resolver);
+    client.revoke_role(&Role::Pauser, &ops);
+    assert_eq!(env.auths()[0].0, admin);
+    assert_eq!(
+        get_events_by_contract_id(&env, &contract_id),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "revoke_role"), EVENT_VERSION).into_val(&env),
+                RevokeRoleEvent {
+                    admin: admin.clone(),
+                    role: Role::Pauser,
+                    account: ops.clone(),
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+    assert_eq!(client.try_pause(&ops), not_authorized);
+    assert_eq!(
+        client.try_revoke_role(&Role::Pauser, &ops),
+        role_not_granted
+    );
+    // Only explicit grants can be revoked, not the admin's implicit roles.
+    assert_eq!(
+        client.try_revoke_role(&Role::Pauser, &admin),
+        role_not_granted
+    );
+
+    client.renounce_role(&lead, &Role::SuperAdmin);
+    assert_eq!(env.auths()[0].0, lead);
+    assert_eq!(
+        get_events_by_contract_id(&env, &contract_id),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "renounce_role"), EVENT_VERSION).into_val(&env),
+                RenounceRoleEvent {
+                    account: lead.clone(),
+                    role: Role::SuperAdmin,
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+    assert!(!client.has_role(&Role::Upgrader, &lead));
+    assert_eq!(
+        client.try_renounce_role(&lead, &Role::SuperAdmin),
+        role_not_granted
+    );
+
+    // Grants made by the single key do not outlive the switch to a multisig,
+    // and can no longer be revoked or renounced.
+    let signer = Address::generate(&env);
+    client.grant_role(&Role::SuperAdmin, &lead);
+    client.grant_role(&Role::Pauser, &ops);
+    client.set_admin_signers(&vec![&env, signer.clone()], &1);
+    assert!(!client.has_role(&Role::SuperAdmin, &lead));
+    assert!(!client.has_role(&Role::Pauser, &ops));
+    assert_eq!(client.try_set_resolver(&lead, &resolver), not_authorized);
+    assert_eq!(client.try_pause(&ops), not_authorized);
+    assert_eq!(
+        client.try_renounce_role(&ops, &Role::Pauser),
+        role_not_granted
+    );
+    assert_eq!(client.try_grant_role(&Role::Pauser, &ops), not_authorized);
+
+    // From then on roles are granted by the signers.
+    let grant =
+        client.propose_admin_action(&signer, &AdminAction::GrantRole(Role::Pauser, ops.clone()));
+    client.execute_admin_action(&signer, &grant);
+    client.pause(&ops);
+}
+
+#[test]
+fn test_migrate_from_v0() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let contract_id = env.register(
+        registry_v0::WASM,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let old_client = registry_v0::Client::new(&env, &contract_id);
+
+    let resolver = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let alice = Bytes::from_slice(&env, "alice".as_bytes());
+    let bob = Bytes::from_slice(&env, "bob".as_bytes());
+    let carol = Bytes::from_slice(&env, "carol".as_bytes());
+
+    token_admin.mint(&owner, &MAX_ASSET_AMOUNT);
+    old_client.set_resolver(&resolver);
+    old_client.register_name(&alice, &com_tld, &owner, &2);
+    old_client.register_name(&bob, &com_tld, &owner, &2);
+    let alice_expiry = old_client.get_name(&alice, &com_tld).expiry;
+
+    let wasm_hash = env.deployer().upload_contract_wasm(registry_current::WASM);
+    old_client.upgrade(&wasm_hash);
+
+    let client = registry_current::Client::new(&env, &contract_id);
+    let controller = Address::generate(&env);
+    client.add_controller(&admin, &controller);
+    assert_eq!(client.schema_version(), 0);
+    let alice_domain = client.get_name(&alice, &com_tld);
+    assert_eq!(alice_domain.owner, owner);
+    assert_eq!(alice_domain.expiry, alice_expiry);
+    // Names from the first release have no token until they are migrated.
+    let alice_token = client.token_id(&alice, &com_tld);
+    assert_eq!(
+        client.try_owner_of(&alice_token),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::NameNotRegistered as u32
+        )))
+    );
+
+    client.controller_register(&controller, &carol, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+    client.index_names(
+        &admin,
+        &vec![
+            &env,
+            (alice.clone(), com_tld.clone()),
+            (bob.clone(), com_tld.clone()),
+        ],
+    );
+
+    assert!(!client.migrate(&admin, &2));
+    assert_eq!(client.schema_version(), 0);
+    assert!(client.migrate(&admin, &2));
+    assert_eq!(
+        get_events_by_contract_id(&env, &contract_id),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("migrate"), EVENT_VERSION).into_val(&env),
+                MigrateEvent {
+                    admin: admin.clone(),
+                    from_version: 0,
+                    to_version: 1,
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+    assert_eq!(client.schema_version(), 1);
+
+    assert_eq!(client.get_name(&bob, &com_tld).owner, owner);
+    assert_eq!(client.names_of(&owner, &0, &10).len(), 3);
+    assert_eq!(client.balance(&owner), 3);
+    assert_eq!(client.owner_of(&alice_token), owner);
+    client.token_uri(&alice_token);
+    let new_owner = Address::generate(&env);
+    client.transfer_from(&owner, &owner, &new_owner, &alice_token);
+    assert_eq!(client.get_name(&alice, &com_tld).owner, new_owner);
+    assert_eq!(
+        client.names_of(&new_owner, &0, &10),
+        vec![&env, (alice.clone(), com_tld.clone())]
+    );
+    env.as_contract(&contract_id, || {
+        let key = DataKey::OwnedNameCount(owner.clone());
+        assert_eq!(env.storage().persistent().get(&key), Some(2u32));
+        for index in 0..3 {
+            assert!(env.storage().persistent().has(&DataKey::NameAt(index)));
+            assert!(!env.storage().instance().has(&DataKey::NameAt(index)));
+        }
+    });
+
+    assert_eq!(
+        client.try_migrate(&admin, &2),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::NoMigrationPending as u32
+        )))
+    );
+}
+
+#[test]
+fn test_multisig_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let contract_id = env.register(
+        Registry,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let client = RegistryClient::new(&env, &contract_id);
+
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let third = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    let resolver = Address::generate(&env);
+    let not_authorized = soroban_sdk::Error::from_contract_error(Error::NotAuthorized as u32);
+
+    assert_eq!(
+        client.try_set_admin_signers(&vec![&env, first.clone(), first.clone()], &1),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::AdminSignersInvalid as u32
+        )))
+    );
+    // An upgrader and the old admin, made a super admin, prepare an upgrade
+    // before the switch.
+    let upgrader = Address::generate(&env);
+    client.grant_role(&Role::Upgrader, &upgrader);
+    client.grant_role(&Role::SuperAdmin, &admin);
+    let wasm_hash = env.deployer().upload_contract_wasm(registry_current::WASM);
+    let eta = env.ledger().timestamp() + 2 * 24 * 60 * 60;
+    client.schedule_upgrade(&upgrader, &wasm_hash, &eta);
+
+    let signers = vec![&env, first.clone(), second.clone(), third.clone()];
+    client.set_admin_signers(&signers, &2);
+    assert_eq!(
+        client.admin_signers(),
+        Some(AdminSigners {
+            signers: signers.clone(),
+            threshold: 2,
+        })
+    );
+    assert_eq!(client.admin(), contract_id);
+    assert_eq!(
+        client.try_set_resolver(&admin, &resolver),
+        Err(Ok(not_authorized))
+    );
+    // Neither of them can upgrade alone any more.
+    env.ledger().set_timestamp(eta);
+    for holder in [&upgrader, &admin] {
+        assert_eq!(client.try_execute_upgrade(holder), Err(Ok(not_authorized)));
+        assert_eq!(
+            client.try_schedule_upgrade(holder, &wasm_hash, &(eta + 2 * 24 * 60 * 60)),
+            Err(Ok(not_authorized))
+        );
+        assert_eq!(client.try_cancel_upgrade(holder), Err(Ok(not_authorized)));
+    }
+    assert_eq!(
+        client.try_grant_role(&Role::Upgrader, &upgrader),
+        Err(Ok(not_authorized))
+    );
+    assert_eq!(
+        client.try_propose_admin_action(&outsider, &AdminAction::SetResolver(resolver.clone())),
+        Err(Ok(not_authorized))
+    );
+
+    let id = client.propose_admin_action(&first, &AdminAction::SetResolver(resolver.clone()));
+    assert_eq!(
+        client.try_execute_admin_action(&first, &id),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::NotEnoughApprovals as u32
+        )))
+    );
+    assert_eq!(
+        client.try_approve_admin_action(&first, &id),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::AlreadyApproved as u32
+        )))
+    );
+    client.approve_admin_action(&second, &id);
+    client.execute_admin_action(&third, &id);
+    assert_eq!(env.auths()[0].0, third);
+    assert_eq!(
+        get_events_by_contract_id(&env, &contract_id),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "set_resolver"), EVENT_VERSION).into_val(&env),
+                SetResolverEvent {
+                    admin: contract_id.clone(),
+                    resolver: resolver.clone(),
+                }
+                .into_val(&env),
+            ),
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "execute_admin_action"), EVENT_VERSION).into_val(&env),
+                ExecuteAdminActionEvent {
+                    signer: third.clone(),
+                    id,
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+    assert_eq!(client.get_current_contract_resolver(), resolver);
+    assert_eq!(
+        client.try_admin_action(&id),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::AdminActionNotFound as u32
+        )))
+    );
+
+    // The multisig holds every role, and pauses through admin actions.
+    assert!(client.has_role(&Role::Pauser, &contract_id));
+    let pause = client.propose_admin_action(&first, &AdminAction::Pause);
+    client.approve_admin_action(&second, &pause);
+    client.execute_admin_action(&first, &pause);
+    assert!(client.is_paused());
+    assert_eq!(
+        client.try_set_name_resolver(
+            &Bytes::from_slice(&env, b"alice"),
+            &com_tld,
+            &outsider,
+            &resolver
+        ),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::Paused as u32
+        )))
+    );
+    let unpause = client.propose_admin_action(&second, &AdminAction::Unpause);
+    client.approve_admin_action(&third, &unpause);
+    client.execute_admin_action(&second, &unpause);
+    assert!(!client.is_paused());
+
+    // Approvals from removed signers stop counting.
+    let stale = client.propose_admin_action(
+        &third,
+        &AdminAction::GrantRole(Role::Pauser, outsider.clone()),
+    );
+    let remove_third = client.propose_admin_action(
+        &first,
+        &AdminAction::SetAdminSigners(vec![&env, first.clone(), second.clone()], 2),
+    );
+    client.approve_admin_action(&second, &remove_third);
+    client.execute_admin_action(&first, &remove_third);
+    client.approve_admin_action(&first, &stale);
+    assert_eq!(
+        client.try_execute_admin_action(&first, &stale),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::NotEnoughApprovals as u32
+        )))
+    );
+
+    // Handing over to a single key ends multisig mode.
+    let new_admin = Address::generate(&env);
+    let handover =
+        client.propose_admin_action(&first, &AdminAction::ProposeAdmin(new_admin.clone()));
+    client.approve_admin_action(&second, &handover);
+    client.execute_admin_action(&second, &handover);
+    client.accept_admin();
+    assert_eq!(client.admin(), new_admin);
+    assert_eq!(client.admin_signers(), None);
+    client.set_resolver(&new_admin, &resolver);
+}
+
+#[test]
+fn test_controllers() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let contract_id = env.register(
+        Registry,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let client = RegistryClient::new(&env, &contract_id);
+
+    let name = Bytes::from_slice(&env, "ttt".as_bytes());
+    let owner = Address::generate(&env);
+    let controller = Address::generate(&env);
+    let resolver = Address::generate(&env);
+    let not_authorized = soroban_sdk::Error::from_contract_error(Error::NotAuthorized as u32);
+
+    client.set_resolver(&admin, &resolver);
+    assert!(!client.is_controller(&controller));
+    assert_eq!(
+        client.try_controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS),
+        Err(Ok(not_authorized))
+    );
+
+    client.add_controller(&admin, &controller);
+    assert_eq!(
+        get_events_by_contract_id(&env, &contract_id),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "add_controller"), EVENT_VERSION).into_val(&env),
+                AddControllerEvent {
+                    admin: admin.clone(),
+                    controller: controller.clone(),
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+    assert!(client.is_controller(&controller));
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+    assert_eq!(
+        client.get_name(&name, &com_tld).expiry,
+        100 + ONE_YEAR_IN_SECONDS
+    );
+    client.controller_renew(&controller, &name, &com_tld, &ONE_YEAR_IN_SECONDS);
+    assert_eq!(
+        client.get_name(&name, &com_tld).expiry,
+        100 + 2 * ONE_YEAR_IN_SECONDS
+    );
+
+    client.remove_controller(&admin, &controller);
+    assert_eq!(
+        get_events_by_contract_id(&env, &contract_id),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "remove_controller"), EVENT_VERSION).into_val(&env),
+                RemoveControllerEvent {
+                    admin: admin.clone(),
+                    controller: controller.clone(),
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+    assert!(!client.is_controller(&controller));
+    assert_eq!(
+        client.try_controller_renew(&controller, &name, &com_tld, &ONE_YEAR_IN_SECONDS),
+        Err(Ok(not_authorized))
+    );
 }
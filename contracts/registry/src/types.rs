@@ -1,10 +1,30 @@
-use soroban_sdk::{contracttype, Address, Bytes};
+use crate::GRACE_PERIOD;
+use access_control::Role;
+use soroban_sdk::{contracttype, Address, Bytes, BytesN, String, Vec};
 
 #[contracttype]
 pub enum DataKey {
     // domain.tld
     Name(Bytes, Bytes),
     Offer(Bytes, Bytes),
+    // token id -> (domain, tld)
+    Token(BytesN<32>),
+    // owner -> number of names held, in persistent storage
+    OwnedNameCount(Address),
+    // owner, position -> (domain, tld), in persistent storage
+    OwnedName(Address, u32),
+    // domain, tld -> position in the owner's list, in persistent storage
+    OwnedNamePosition(Bytes, Bytes),
+    Approval(BytesN<32>),
+    // owner, operator
+    Operator(Address, Address),
+    // position -> (domain, tld), every name ever registered, in persistent
+    // storage
+    NameAt(u32),
+    AdminAction(u32),
+    Controller(Address),
+    // domain, tld -> release time of a cleaned up name, in persistent storage
+    ReleaseTime(Bytes, Bytes),
 }
 
 #[contracttype]
@@ -15,6 +35,14 @@ pub struct Domain {
     pub expiry: u64,
 }
 
+impl Domain {
+    // When anyone can register the name again, i.e. the end of the grace
+    // period after its expiry.
+    pub fn release_time(&self) -> u64 {
+        self.expiry.saturating_add(GRACE_PERIOD)
+    }
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Offer {
@@ -23,3 +51,43 @@ pub struct Offer {
     pub tld: Bytes,
     pub price: u64,
 }
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Approval {
+    pub approved: Address,
+    pub live_until_ledger: u32,
+}
+
+// Admin entrypoints that the signers can run together once the admin is a
+// multisig.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AdminAction {
+    SetResolver(Address),
+    SetBaseUri(String),
+    GrantRole(Role, Address),
+    RevokeRole(Role, Address),
+    Pause,
+    Unpause,
+    AddController(Address),
+    RemoveController(Address),
+    // signers, threshold
+    SetAdminSigners(Vec<Address>, u32),
+    ProposeAdmin(Address),
+    CancelAdminProposal,
+    // new wasm hash, eta
+    ScheduleUpgrade(BytesN<32>, u64),
+    CancelUpgrade,
+    ExecuteUpgrade,
+    IndexNames(Vec<(Bytes, Bytes)>),
+    // batch size
+    Migrate(u32),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminProposal {
+    pub action: AdminAction,
+    pub approvals: Vec<Address>,
+}
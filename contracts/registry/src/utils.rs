@@ -1,5 +1,10 @@
-use crate::{errors::Error, types::DataKey, TLDS};
-use soroban_sdk::{panic_with_error, Bytes, Env, Vec};
+use crate::{
+    errors::Error,
+    events::{Event, TransferEvent},
+    types::{DataKey, Domain},
+    NAME_COUNT, TLDS,
+};
+use soroban_sdk::{panic_with_error, Address, Bytes, BytesN, Env, Vec};
 
 const DOT_IN_BYTE: u8 = 46;
 
@@ -93,7 +98,17 @@ impl BytesValidator for Bytes {
 
 pub trait Base {
     fn extend_me(&self);
+    fn require_not_paused(&self);
+    fn load_domain(&self, name: &Bytes, tld: &Bytes) -> Option<Domain>;
+    fn index_name(&self, name: &Bytes, tld: &Bytes);
     fn delete_name(&self, name: &Bytes, tld: &Bytes);
+    fn token_id(&self, name: &Bytes, tld: &Bytes) -> BytesN<32>;
+    fn extend_persistent(&self, key: &DataKey);
+    fn owned_name_count(&self, owner: &Address) -> u32;
+    fn owned_names(&self, owner: &Address, start: u32, end: u32) -> Vec<(Bytes, Bytes)>;
+    fn add_owned_name(&self, owner: &Address, name: &Bytes, tld: &Bytes);
+    fn remove_owned_name(&self, owner: &Address, name: &Bytes, tld: &Bytes);
+    fn move_name(&self, name: &Bytes, tld: &Bytes, domain: &mut Domain, new_owner: &Address);
 }
 
 impl Base for Env {
@@ -101,9 +116,150 @@ impl Base for Env {
         self.storage().instance().extend_ttl(17280, 17280 * 30);
     }
 
+    fn require_not_paused(&self) {
+        if access_control::is_paused(self) {
+            panic_with_error!(self, Error::Paused);
+        }
+    }
+
+    fn load_domain(&self, name: &Bytes, tld: &Bytes) -> Option<Domain> {
+        self.storage()
+            .instance()
+            .get(&DataKey::Name(name.clone(), tld.clone()))
+    }
+
+    // The index grows with every name ever registered, so its entries live in
+    // persistent storage and only the count is kept in the instance.
+    fn index_name(&self, name: &Bytes, tld: &Bytes) {
+        let count: u32 = self.storage().instance().get(&NAME_COUNT).unwrap_or(0);
+        let key = DataKey::NameAt(count);
+        self.storage()
+            .persistent()
+            .set(&key, &(name.clone(), tld.clone()));
+        self.extend_persistent(&key);
+        self.storage().instance().set(&NAME_COUNT, &(count + 1));
+    }
+
+    // Drops an expired name together with everything hanging off it, so a stale
+    // offer or approval from the previous owner cannot be used on the new one.
     fn delete_name(&self, name: &Bytes, tld: &Bytes) {
+        let key = DataKey::Name(name.clone(), tld.clone());
+        if let Some(domain) = self.load_domain(name, tld) {
+            self.remove_owned_name(&domain.owner, name, tld);
+        }
+        self.storage().instance().remove(&key);
         self.storage()
             .instance()
-            .remove(&DataKey::Name(name.clone(), tld.clone()));
+            .remove(&DataKey::Offer(name.clone(), tld.clone()));
+        self.storage()
+            .instance()
+            .remove(&DataKey::Approval(self.token_id(name, tld)));
+    }
+
+    fn token_id(&self, name: &Bytes, tld: &Bytes) -> BytesN<32> {
+        let mut full_name: Bytes = name.clone();
+        full_name.push_back(DOT_IN_BYTE);
+        full_name.append(tld);
+        self.crypto().sha256(&full_name).into()
+    }
+
+    fn extend_persistent(&self, key: &DataKey) {
+        self.storage()
+            .persistent()
+            .extend_ttl(key, 17280, 17280 * 30);
+    }
+
+    fn owned_name_count(&self, owner: &Address) -> u32 {
+        let key = DataKey::OwnedNameCount(owner.clone());
+        let Some(count) = self.storage().persistent().get(&key) else {
+            return 0;
+        };
+        self.extend_persistent(&key);
+        count
+    }
+
+    // Each of the owner's names is its own persistent entry, so a page only
+    // loads the names on it, however many the owner holds.
+    fn owned_names(&self, owner: &Address, start: u32, end: u32) -> Vec<(Bytes, Bytes)> {
+        let mut names = Vec::new(self);
+        for position in start..end {
+            let key = DataKey::OwnedName(owner.clone(), position);
+            let name: (Bytes, Bytes) = self.storage().persistent().get(&key).unwrap();
+            self.extend_persistent(&key);
+            names.push_back(name);
+        }
+        names
+    }
+
+    fn add_owned_name(&self, owner: &Address, name: &Bytes, tld: &Bytes) {
+        let position = self.owned_name_count(owner);
+        let name_key = DataKey::OwnedName(owner.clone(), position);
+        let position_key = DataKey::OwnedNamePosition(name.clone(), tld.clone());
+        let count_key = DataKey::OwnedNameCount(owner.clone());
+        self.storage()
+            .persistent()
+            .set(&name_key, &(name.clone(), tld.clone()));
+        self.storage().persistent().set(&position_key, &position);
+        self.storage().persistent().set(&count_key, &(position + 1));
+        self.extend_persistent(&name_key);
+        self.extend_persistent(&position_key);
+        self.extend_persistent(&count_key);
+    }
+
+    // Moves the owner's last name into the freed position, so removing a name
+    // touches a fixed number of entries.
+    fn remove_owned_name(&self, owner: &Address, name: &Bytes, tld: &Bytes) {
+        let position_key = DataKey::OwnedNamePosition(name.clone(), tld.clone());
+        let Some(position) = self.storage().persistent().get::<_, u32>(&position_key) else {
+            return;
+        };
+        let last = self.owned_name_count(owner) - 1;
+        let last_key = DataKey::OwnedName(owner.clone(), last);
+        if position != last {
+            let moved: (Bytes, Bytes) = self.storage().persistent().get(&last_key).unwrap();
+            let moved_key = DataKey::OwnedName(owner.clone(), position);
+            let moved_position_key = DataKey::OwnedNamePosition(moved.0.clone(), moved.1.clone());
+            self.storage().persistent().set(&moved_key, &moved);
+            self.storage()
+                .persistent()
+                .set(&moved_position_key, &position);
+            self.extend_persistent(&moved_key);
+            self.extend_persistent(&moved_position_key);
+        }
+        self.storage().persistent().remove(&last_key);
+        self.storage().persistent().remove(&position_key);
+        let count_key = DataKey::OwnedNameCount(owner.clone());
+        if last == 0 {
+            self.storage().persistent().remove(&count_key);
+        } else {
+            self.storage().persistent().set(&count_key, &last);
+            self.extend_persistent(&count_key);
+        }
+    }
+
+    fn move_name(&self, name: &Bytes, tld: &Bytes, domain: &mut Domain, new_owner: &Address) {
+        let from = domain.owner.clone();
+        let token_id = self.token_id(name, tld);
+        self.remove_owned_name(&from, name, tld);
+        self.add_owned_name(new_owner, name, tld);
+        domain.owner = new_owner.clone();
+        self.storage()
+            .instance()
+            .set(&DataKey::Name(name.clone(), tld.clone()), domain);
+        self.storage()
+            .instance()
+            .remove(&DataKey::Offer(name.clone(), tld.clone()));
+        self.storage()
+            .instance()
+            .remove(&DataKey::Approval(token_id.clone()));
+
+        TransferEvent {
+            from,
+            to: new_owner.clone(),
+            name: name.clone(),
+            tld: tld.clone(),
+            token_id,
+        }
+        .publish(self);
     }
 }
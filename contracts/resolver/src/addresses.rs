@@ -0,0 +1,164 @@
+use crate::errors::*;
+use crate::events::*;
+use crate::types::*;
+use crate::utils::*;
+use crate::{Resolver, ResolverArgs, ResolverClient, MAX_ADDRS_PER_NAME};
+use soroban_sdk::{contractimpl, panic_with_error, Address, Bytes, Env, Vec};
+
+// SLIP-44 coin types.
+const COIN_TYPE_BTC: u32 = 0;
+const COIN_TYPE_LTC: u32 = 2;
+const COIN_TYPE_DOGE: u32 = 3;
+const COIN_TYPE_ETH: u32 = 60;
+const COIN_TYPE_ETC: u32 = 61;
+const COIN_TYPE_ATOM: u32 = 118;
+const COIN_TYPE_XLM: u32 = 148;
+const COIN_TYPE_SOL: u32 = 501;
+// ENSIP-11: EVM chains other than Ethereum use `0x80000000 | chain id`.
+const EVM_CHAIN_FLAG: u32 = 0x8000_0000;
+
+const EVM_ADDRESS_LENGTH: u32 = 20;
+const COSMOS_ADDRESS_LENGTH: u32 = 20;
+const ED25519_ADDRESS_LENGTH: u32 = 32;
+// Longest address accepted for chains without a format check.
+const MAX_ADDRESS_LENGTH: u32 = 64;
+
+// Bitcoin script opcodes.
+const OP_0: u8 = 0x00;
+const OP_1: u8 = 0x51;
+const OP_16: u8 = 0x60;
+const OP_DUP: u8 = 0x76;
+const OP_HASH160: u8 = 0xa9;
+const OP_EQUAL: u8 = 0x87;
+const OP_EQUALVERIFY: u8 = 0x88;
+const OP_CHECKSIG: u8 = 0xac;
+const PUSH_20: u8 = 0x14;
+
+// Addresses on other chains, keyed by SLIP-44 coin type as in ENSIP-9, so
+// `alice.xlm` can receive BTC or ETH too. Addresses are stored in binary: the
+// output script for Bitcoin-like chains, the 20 byte account for EVM chains and
+// the 32 byte public key for Stellar and Solana. Wallets encode them for display.
+#[contractimpl]
+impl Resolver {
+    // Setting an empty `addr` removes the address for `coin_type`.
+    pub fn set_addr(env: Env, name: Bytes, tld: Bytes, coin_type: u32, addr: Bytes) {
+        env.extend_me();
+        env.require_not_paused();
+        let owner = Self::require_name_owner(&env, &name, &tld);
+        Self::apply_set_addr(&env, &owner, &name, &tld, coin_type, &addr);
+    }
+
+    pub fn get_addr(env: Env, name: Bytes, tld: Bytes, coin_type: u32) -> Bytes {
+        env.extend_me();
+        let owner = Self::require_live_owner(&env, &name, &tld);
+        env.get_owned(
+            &RecordKeys::Addr(name.clone(), tld.clone(), coin_type),
+            &owner,
+        )
+        .unwrap_or_else(|| {
+            panic_with_error!(&env, Error::NameHasNoRecord);
+        })
+    }
+
+    pub fn addr_coin_types(env: Env, name: Bytes, tld: Bytes) -> Vec<u32> {
+        env.extend_me();
+        Self::lookup_live_owner(&env, &name, &tld)
+            .and_then(|owner| {
+                env.get_owned(
+                    &RecordKeys::AddrCoinTypes(name.clone(), tld.clone()),
+                    &owner,
+                )
+            })
+            .unwrap_or(Vec::new(&env))
+    }
+
+    pub(crate) fn apply_set_addr(
+        env: &Env,
+        owner: &Address,
+        name: &Bytes,
+        tld: &Bytes,
+        coin_type: u32,
+        addr: &Bytes,
+    ) {
+        if !addr.is_empty() && !is_valid_addr(coin_type, addr) {
+            panic_with_error!(env, Error::AddressInvalid);
+        }
+
+        let coin_types_key = RecordKeys::AddrCoinTypes(name.clone(), tld.clone());
+        let mut coin_types: Vec<u32> = env
+            .get_owned(&coin_types_key, owner)
+            .unwrap_or(Vec::new(env));
+        let addr_key = RecordKeys::Addr(name.clone(), tld.clone(), coin_type);
+        if addr.is_empty() {
+            if let Some(index) = coin_types.first_index_of(coin_type) {
+                coin_types.remove(index);
+            }
+            env.storage().persistent().remove(&addr_key);
+        } else {
+            if !coin_types.contains(coin_type) {
+                if coin_types.len() >= MAX_ADDRS_PER_NAME {
+                    panic_with_error!(env, Error::TooManyAddresses);
+                }
+                coin_types.push_back(coin_type);
+            }
+            env.set_owned(&addr_key, owner, addr.clone());
+        }
+        if coin_types.is_empty() {
+            env.storage().persistent().remove(&coin_types_key);
+        } else {
+            env.set_owned(&coin_types_key, owner, coin_types);
+        }
+
+        SetAddrEvent {
+            owner: owner.clone(),
+            name: name.clone(),
+            tld: tld.clone(),
+            coin_type,
+            addr: addr.clone(),
+        }
+        .publish(env);
+    }
+}
+
+fn is_valid_addr(coin_type: u32, addr: &Bytes) -> bool {
+    match coin_type {
+        COIN_TYPE_BTC | COIN_TYPE_LTC | COIN_TYPE_DOGE => is_bitcoin_script(addr),
+        COIN_TYPE_ETH | COIN_TYPE_ETC => addr.len() == EVM_ADDRESS_LENGTH,
+        COIN_TYPE_ATOM => addr.len() == COSMOS_ADDRESS_LENGTH,
+        COIN_TYPE_XLM | COIN_TYPE_SOL => addr.len() == ED25519_ADDRESS_LENGTH,
+        _ if coin_type & EVM_CHAIN_FLAG != 0 => addr.len() == EVM_ADDRESS_LENGTH,
+        _ => addr.len() <= MAX_ADDRESS_LENGTH,
+    }
+}
+
+// Accepts the standard output scripts: P2PKH, P2SH and segwit (BIP-141) outputs.
+fn is_bitcoin_script(script: &Bytes) -> bool {
+    let len = script.len();
+    let byte = |index: u32| script.get_unchecked(index);
+    // OP_DUP OP_HASH160 <20 bytes> OP_EQUALVERIFY OP_CHECKSIG
+    if len == 25 {
+        return byte(0) == OP_DUP
+            && byte(1) == OP_HASH160
+            && byte(2) == PUSH_20
+            && byte(23) == OP_EQUALVERIFY
+            && byte(24) == OP_CHECKSIG;
+    }
+    // OP_HASH160 <20 bytes> OP_EQUAL
+    if len == 23 && byte(0) == OP_HASH160 {
+        return byte(1) == PUSH_20 && byte(22) == OP_EQUAL;
+    }
+    // <version> <2 to 40 byte program>
+    if !(4..=42).contains(&len) {
+        return false;
+    }
+    let version = byte(0);
+    let program_len = byte(1) as u32;
+    if program_len != len - 2 {
+        return false;
+    }
+    match version {
+        OP_0 => program_len == 20 || program_len == 32,
+        OP_1..=OP_16 => true,
+        _ => false,
+    }
+}
@@ -1,5 +1,7 @@
 use soroban_sdk::contracterror;
 
+// The resolver's own errors, numbered like everywhere else (see the full list
+// in `sns-client`). Codes 1 to 9 are kept as released.
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
@@ -13,4 +15,31 @@ pub enum Error {
     TLDNotSupported = 7,
     NoOffer = 8,
     RecordTypeInvalid = 9,
+    NotAuthorized = 10,
+    PaymentRecordInvalid = 14,
+    ContentHashInvalid = 15,
+    TextKeyInvalid = 16,
+    TextValueTooLong = 17,
+    TooManyTextRecords = 18,
+    PublicKeyInvalid = 19,
+    SignatureInvalid = 20,
+    KeyTypeNotForSigning = 21,
+    RecordUpdatesInvalid = 22,
+    RecordVersionUnavailable = 23,
+    OffchainConfigInvalid = 24,
+    OffchainResponseExpired = 25,
+    NoPendingAdmin = 26,
+    UpgradeEtaTooEarly = 27,
+    UpgradeNotReady = 28,
+    NoPendingUpgrade = 29,
+    Paused = 30,
+    NoMigrationPending = 31,
+    AdminActionNotFound = 32,
+    NotEnoughApprovals = 33,
+    AlreadyApproved = 34,
+    AdminSignersInvalid = 35,
+    AddressInvalid = 47,
+    TooManyAddresses = 48,
+    UpgradePending = 49,
+    RoleNotGranted = 50,
 }
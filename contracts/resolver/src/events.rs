@@ -0,0 +1,131 @@
+use crate::types::{OffchainConfig, PublicKey, Record};
+use soroban_sdk::{contracttype, Address, Bytes, Env, IntoVal, Symbol, Val};
+
+// Bumped whenever the fields of an existing event change. It is published as the
+// second topic so indexers can keep decoding old events after an upgrade.
+pub const EVENT_VERSION: u32 = 1;
+
+pub trait Event: IntoVal<Env, Val> + Sized {
+    const NAME: &'static str;
+
+    fn publish(self, env: &Env) {
+        env.events()
+            .publish((Symbol::new(env, Self::NAME), EVENT_VERSION), self);
+    }
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SetRecordEvent {
+    pub owner: Address,
+    pub name: Bytes,
+    pub tld: Bytes,
+    pub record: Record,
+}
+
+impl Event for SetRecordEvent {
+    const NAME: &'static str = "set_record";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SetTextEvent {
+    pub owner: Address,
+    pub name: Bytes,
+    pub tld: Bytes,
+    pub key: Bytes,
+    pub value: Bytes,
+}
+
+impl Event for SetTextEvent {
+    const NAME: &'static str = "set_text";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SetAddrEvent {
+    pub owner: Address,
+    pub name: Bytes,
+    pub tld: Bytes,
+    pub coin_type: u32,
+    pub addr: Bytes,
+}
+
+impl Event for SetAddrEvent {
+    const NAME: &'static str = "set_addr";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SetPublicKeyEvent {
+    pub owner: Address,
+    pub name: Bytes,
+    pub tld: Bytes,
+    pub public_key: PublicKey,
+}
+
+impl Event for SetPublicKeyEvent {
+    const NAME: &'static str = "set_public_key";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RemovePublicKeyEvent {
+    pub owner: Address,
+    pub name: Bytes,
+    pub tld: Bytes,
+    pub purpose: Symbol,
+}
+
+impl Event for RemovePublicKeyEvent {
+    const NAME: &'static str = "remove_public_key";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeleteRecordEvent {
+    pub owner: Address,
+    pub name: Bytes,
+    pub tld: Bytes,
+}
+
+impl Event for DeleteRecordEvent {
+    const NAME: &'static str = "delete_record";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SetOffchainResolverEvent {
+    pub owner: Address,
+    pub name: Bytes,
+    pub tld: Bytes,
+    pub config: OffchainConfig,
+}
+
+impl Event for SetOffchainResolverEvent {
+    const NAME: &'static str = "set_offchain_resolver";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RemoveOffchainResolverEvent {
+    pub owner: Address,
+    pub name: Bytes,
+    pub tld: Bytes,
+}
+
+impl Event for RemoveOffchainResolverEvent {
+    const NAME: &'static str = "remove_offchain_resolver";
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MigrateEvent {
+    pub admin: Address,
+    pub from_version: u32,
+    pub to_version: u32,
+}
+
+impl Event for MigrateEvent {
+    const NAME: &'static str = "migrate";
+}
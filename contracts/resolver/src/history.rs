@@ -0,0 +1,94 @@
+use crate::errors::*;
+use crate::types::*;
+use crate::utils::*;
+use crate::{Resolver, ResolverArgs, ResolverClient, MAX_RECORD_HISTORY};
+use soroban_sdk::{contractimpl, panic_with_error, Bytes, Env};
+
+// Every change to a name record gets a version number and the ledger it happened
+// in, so it can be audited later (e.g. when a payment address was switched).
+// Only the last `MAX_RECORD_HISTORY` versions are kept to bound storage. History
+// stays readable after the name expires. Text records, addresses and public keys
+// are not versioned; their changes are only recorded in events.
+#[contractimpl]
+impl Resolver {
+    pub fn get_record_meta(env: Env, name: Bytes, tld: Bytes) -> RecordMeta {
+        env.extend_me();
+        let key = RecordKeys::RecordMeta(name, tld);
+        let meta = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NameHasNoRecord));
+        env.extend_persistent(&key);
+        meta
+    }
+
+    // Returns the record as it was at `version`. A deleted record reads back as
+    // `Record::NullRecord`.
+    pub fn resolve_name_at_version(env: Env, name: Bytes, tld: Bytes, version: u32) -> Record {
+        Self::get_record_version(env, name, tld, version).record
+    }
+
+    // Like `resolve_name_at_version`, with the ledger and time the version was
+    // written.
+    pub fn get_record_version(env: Env, name: Bytes, tld: Bytes, version: u32) -> RecordVersion {
+        env.extend_me();
+        Self::load_record_version(&env, &name, &tld, version)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::RecordVersionUnavailable))
+    }
+
+    fn load_record_version(
+        env: &Env,
+        name: &Bytes,
+        tld: &Bytes,
+        version: u32,
+    ) -> Option<RecordVersion> {
+        let key = RecordKeys::RecordHistory(name.clone(), tld.clone(), version);
+        let entry = env.storage().persistent().get(&key)?;
+        env.extend_persistent(&key);
+        Some(entry)
+    }
+
+    pub(crate) fn push_record_history(env: &Env, name: &Bytes, tld: &Bytes, record: &Record) {
+        let meta_key = RecordKeys::RecordMeta(name.clone(), tld.clone());
+        let version = env
+            .storage()
+            .persistent()
+            .get::<_, RecordMeta>(&meta_key)
+            .map_or(1, |meta| meta.version + 1);
+        if version == 1 {
+            env.index_name(name, tld);
+        }
+
+        let ledger = env.ledger().sequence();
+        let timestamp = env.ledger().timestamp();
+        let history_key = RecordKeys::RecordHistory(name.clone(), tld.clone(), version);
+        env.storage().persistent().set(
+            &history_key,
+            &RecordVersion {
+                record: record.clone(),
+                ledger,
+                timestamp,
+            },
+        );
+        env.extend_persistent(&history_key);
+        if version > MAX_RECORD_HISTORY {
+            env.storage()
+                .persistent()
+                .remove(&RecordKeys::RecordHistory(
+                    name.clone(),
+                    tld.clone(),
+                    version - MAX_RECORD_HISTORY,
+                ));
+        }
+        env.storage().persistent().set(
+            &meta_key,
+            &RecordMeta {
+                version,
+                ledger,
+                timestamp,
+            },
+        );
+        env.extend_persistent(&meta_key);
+    }
+}
@@ -1,13 +1,24 @@
 #![no_std]
+mod addresses;
+mod admin;
 mod errors;
+mod events;
+mod history;
+mod migration;
+mod multisig;
+mod offchain;
+mod public_keys;
+mod records;
 mod types;
 mod utils;
 use crate::errors::*;
+use crate::events::*;
 use crate::utils::*;
+use access_control::{Role, ScheduledUpgrade};
 use soroban_sdk::BytesN;
 use soroban_sdk::{
     contract, contractimpl, contractimport, panic_with_error, symbol_short, Address, Bytes, Env,
-    Symbol,
+    Symbol, Vec,
 };
 use types::*;
 
@@ -16,7 +27,20 @@ mod registry {
 }
 
 const REGISTRY: Symbol = symbol_short!("registry");
-const ADMIN: Symbol = symbol_short!("admin");
+const SCHEMA_VERSION: Symbol = symbol_short!("schema");
+const NAME_COUNT: Symbol = symbol_short!("name_cnt");
+const MIGRATION_CURSOR: Symbol = symbol_short!("migr_cur");
+// Layout of the entries written by this code. Bump it together with a new step
+// in `migration.rs` whenever a stored type changes.
+const CURRENT_SCHEMA_VERSION: u32 = 1;
+const MAX_TEXT_KEY_LENGTH: u32 = 64;
+const MAX_TEXT_VALUE_LENGTH: u32 = 512;
+const MAX_TEXT_KEYS_PER_NAME: u32 = 32;
+const MAX_ADDRS_PER_NAME: u32 = 32;
+const MAX_RECORD_UPDATES: u32 = 20;
+const MAX_RECORD_HISTORY: u32 = 10;
+const MAX_GATEWAY_URLS: u32 = 5;
+const MAX_GATEWAY_URL_LENGTH: u32 = 256;
 
 #[contract]
 pub struct Resolver;
@@ -25,66 +49,294 @@ pub struct Resolver;
 impl Resolver {
     pub fn __constructor(env: Env, admin: Address, registry: Address) {
         env.extend_me();
-        env.storage().instance().set(&ADMIN, &admin);
+        access_control::init_admin(&env, &admin);
         env.storage().instance().set(&REGISTRY, &registry);
+        env.storage()
+            .instance()
+            .set(&SCHEMA_VERSION, &CURRENT_SCHEMA_VERSION);
     }
 
     pub fn is_name_has_record(env: Env, name: Bytes, tld: Bytes) -> bool {
         env.extend_me();
-        env.storage()
-            .instance()
-            .has(&RecordKeys::Name(name.clone(), tld.clone()))
+        let Some(owner) = Self::lookup_live_owner(&env, &name, &tld) else {
+            return false;
+        };
+        Self::load_record(&env, &name, &tld, &owner).is_some()
     }
 
     pub fn set_record(env: Env, name: Bytes, tld: Bytes, record_type: Bytes, data: Bytes) {
         env.extend_me();
-        name.validate_name(&env, true);
-        let client = registry::Client::new(&env, &env.storage().instance().get(&REGISTRY).unwrap());
-        if client.is_name_expired(&name, &tld) {
-            panic_with_error!(&env, Error::NameExpired);
-        }
-        let owner = client.get_owner(&name, &tld);
-        owner.require_auth();
+        env.require_not_paused();
+        let owner = Self::require_name_owner(&env, &name, &tld);
+        Self::apply_set_record(&env, &owner, &name, &tld, &record_type, &data);
+    }
 
-        let mut record = Record::NullRecord;
-        if record_type == Bytes::from_slice(&env, "name".as_bytes()) {
-            record = Record::Name(Address::from_string_bytes(&data));
-        } else if record_type == Bytes::from_slice(&env, "ipfs".as_bytes()) {
-            record = Record::Ipfs(data.clone());
-        } else if record_type == Bytes::from_slice(&env, "text".as_bytes()) {
-            record = Record::Text(data.clone());
-        } else {
-            panic_with_error!(&env, Error::RecordTypeInvalid);
+    pub fn delete_record(env: Env, name: Bytes, tld: Bytes) {
+        env.extend_me();
+        env.require_not_paused();
+        let owner = Self::require_name_owner(&env, &name, &tld);
+        Self::apply_delete_record(&env, &owner, &name, &tld);
+    }
+
+    // Standard keys, shared with other naming services so profiles render the same
+    // everywhere: `avatar`, `url`, `email`, `description`, `com.twitter` and
+    // `com.github`. Any other key matching [a-z0-9._-] is accepted too. Setting an
+    // empty value removes the key.
+    pub fn set_text(env: Env, name: Bytes, tld: Bytes, key: Bytes, value: Bytes) {
+        env.extend_me();
+        env.require_not_paused();
+        let owner = Self::require_name_owner(&env, &name, &tld);
+        Self::apply_set_text(&env, &owner, &name, &tld, &key, &value);
+    }
+
+    // Applies every update with a single owner authorization. Any failing update
+    // reverts the whole batch.
+    pub fn set_records(env: Env, name: Bytes, tld: Bytes, updates: Vec<RecordUpdate>) {
+        env.extend_me();
+        env.require_not_paused();
+        if updates.is_empty() || updates.len() > MAX_RECORD_UPDATES {
+            panic_with_error!(&env, Error::RecordUpdatesInvalid);
+        }
+        let owner = Self::require_name_owner(&env, &name, &tld);
+        for update in updates.iter() {
+            match update {
+                RecordUpdate::SetRecord(record_type, data) => {
+                    Self::apply_set_record(&env, &owner, &name, &tld, &record_type, &data)
+                }
+                RecordUpdate::DeleteRecord => Self::apply_delete_record(&env, &owner, &name, &tld),
+                RecordUpdate::SetText(key, value) => {
+                    Self::apply_set_text(&env, &owner, &name, &tld, &key, &value)
+                }
+                RecordUpdate::SetPublicKey(purpose, key_type, key) => {
+                    Self::apply_set_public_key(&env, &owner, &name, &tld, purpose, key_type, key)
+                }
+                RecordUpdate::RemovePublicKey(purpose) => {
+                    Self::apply_remove_public_key(&env, &owner, &name, &tld, purpose)
+                }
+                RecordUpdate::SetAddr(coin_type, addr) => {
+                    Self::apply_set_addr(&env, &owner, &name, &tld, coin_type, &addr)
+                }
+            }
         }
-        env.storage()
-            .instance()
-            .set(&RecordKeys::Name(name.clone(), tld.clone()), &record);
     }
 
+    pub fn text(env: Env, name: Bytes, tld: Bytes, key: Bytes) -> Bytes {
+        env.extend_me();
+        let owner = Self::require_live_owner(&env, &name, &tld);
+        env.get_owned(
+            &RecordKeys::Text(name.clone(), tld.clone(), key.clone()),
+            &owner,
+        )
+        .unwrap_or_else(|| {
+            panic_with_error!(&env, Error::NameHasNoRecord);
+        })
+    }
+
+    pub fn text_keys(env: Env, name: Bytes, tld: Bytes) -> Vec<Bytes> {
+        env.extend_me();
+        Self::lookup_live_owner(&env, &name, &tld)
+            .and_then(|owner| {
+                env.get_owned(&RecordKeys::TextKeys(name.clone(), tld.clone()), &owner)
+            })
+            .unwrap_or(Vec::new(&env))
+    }
+
+    // Records set on `sub.name` win; otherwise a `*.name` record, if any, is
+    // returned for any subdomain. The root name never falls back to the wildcard.
     pub fn resolve_name(env: Env, name: Bytes, tld: Bytes) -> Record {
         env.extend_me();
-        if Self::is_name_has_record(env.clone(), name.clone(), tld.clone()) {
-            let client =
-                registry::Client::new(&env, &env.storage().instance().get(&REGISTRY).unwrap());
-            if client.is_name_expired(&name, &tld) {
-                panic_with_error!(&env, Error::NameExpired);
+        let owner = Self::resolving_owner(&env, &name, &tld);
+        Self::find_record(&env, &name, &tld, &owner)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NameHasNoRecord))
+    }
+
+    // Like `resolve_name`, but returns `None` when the name is unregistered,
+    // expired or has no record, so callers such as payment contracts can fall
+    // back gracefully instead of aborting the whole transaction.
+    pub fn lookup_record(env: Env, name: Bytes, tld: Bytes) -> Option<Record> {
+        env.extend_me();
+        let owner = Self::lookup_live_owner(&env, &name, &tld)?;
+        Self::find_record(&env, &name, &tld, &owner)
+    }
+
+    // Gateways configured for the name take precedence over on-chain records.
+    fn find_record(env: &Env, name: &Bytes, tld: &Bytes, owner: &Address) -> Option<Record> {
+        if let Some(lookup) = Self::offchain_lookup(env, name, tld, owner) {
+            return Some(Record::Offchain(lookup));
+        }
+        let record = Self::load_record(env, name, tld, owner);
+        if record.is_some() {
+            return record;
+        }
+        let wildcard = name.wildcard_name(env)?;
+        Self::load_record(env, &wildcard, tld, owner)
+    }
+
+    // Records set before schema version 1 are read from instance storage until
+    // `migrate` binds them to their owner.
+    fn load_record(env: &Env, name: &Bytes, tld: &Bytes, owner: &Address) -> Option<Record> {
+        let key = RecordKeys::Name(name.clone(), tld.clone());
+        let record = env.get_owned(&key, owner);
+        if record.is_some() {
+            return record;
+        }
+        env.storage().instance().get(&key)
+    }
+
+    fn apply_set_record(
+        env: &Env,
+        owner: &Address,
+        name: &Bytes,
+        tld: &Bytes,
+        record_type: &Bytes,
+        data: &Bytes,
+    ) {
+        let record = if *record_type == Bytes::from_slice(env, "name".as_bytes()) {
+            Record::Name(Address::from_string_bytes(data))
+        } else if *record_type == Bytes::from_slice(env, "contenthash".as_bytes()) {
+            Record::ContentHash(records::parse_content_hash(env, data))
+        } else if *record_type == Bytes::from_slice(env, "text".as_bytes()) {
+            Record::Text(data.clone())
+        } else if *record_type == Bytes::from_slice(env, "payment".as_bytes()) {
+            Record::Payment(records::parse_payment(env, data))
+        } else {
+            panic_with_error!(env, Error::RecordTypeInvalid);
+        };
+        let key = RecordKeys::Name(name.clone(), tld.clone());
+        env.storage().instance().remove(&key);
+        env.set_owned(&key, owner, record.clone());
+        Self::push_record_history(env, name, tld, &record);
+
+        SetRecordEvent {
+            owner: owner.clone(),
+            name: name.clone(),
+            tld: tld.clone(),
+            record,
+        }
+        .publish(env);
+    }
+
+    fn apply_delete_record(env: &Env, owner: &Address, name: &Bytes, tld: &Bytes) {
+        if Self::load_record(env, name, tld, owner).is_none() {
+            panic_with_error!(env, Error::NameHasNoRecord);
+        }
+        let key = RecordKeys::Name(name.clone(), tld.clone());
+        env.storage().instance().remove(&key);
+        env.storage().persistent().remove(&key);
+        Self::push_record_history(env, name, tld, &Record::NullRecord);
+
+        DeleteRecordEvent {
+            owner: owner.clone(),
+            name: name.clone(),
+            tld: tld.clone(),
+        }
+        .publish(env);
+    }
+
+    fn apply_set_text(
+        env: &Env,
+        owner: &Address,
+        name: &Bytes,
+        tld: &Bytes,
+        key: &Bytes,
+        value: &Bytes,
+    ) {
+        key.validate_text_key(env, MAX_TEXT_KEY_LENGTH);
+        if value.len() > MAX_TEXT_VALUE_LENGTH {
+            panic_with_error!(env, Error::TextValueTooLong);
+        }
+
+        let keys_key = RecordKeys::TextKeys(name.clone(), tld.clone());
+        let mut keys: Vec<Bytes> = env.get_owned(&keys_key, owner).unwrap_or(Vec::new(env));
+        let text_key = RecordKeys::Text(name.clone(), tld.clone(), key.clone());
+        if value.is_empty() {
+            if let Some(index) = keys.first_index_of(key) {
+                keys.remove(index);
             }
-            return env
-                .storage()
-                .instance()
-                .get(&RecordKeys::Name(name.clone(), tld.clone()))
-                .unwrap();
+            env.storage().persistent().remove(&text_key);
         } else {
-            panic_with_error!(&env, Error::NameHasNoRecord);
+            if !keys.contains(key) {
+                if keys.len() >= MAX_TEXT_KEYS_PER_NAME {
+                    panic_with_error!(env, Error::TooManyTextRecords);
+                }
+                keys.push_back(key.clone());
+            }
+            env.set_owned(&text_key, owner, value.clone());
+        }
+        if keys.is_empty() {
+            env.storage().persistent().remove(&keys_key);
+        } else {
+            env.set_owned(&keys_key, owner, keys);
+        }
+
+        SetTextEvent {
+            owner: owner.clone(),
+            name: name.clone(),
+            tld: tld.clone(),
+            key: key.clone(),
+            value: value.clone(),
+        }
+        .publish(env);
+    }
+
+    // The account whose records are served for the name.
+    fn require_live_owner(env: &Env, name: &Bytes, tld: &Bytes) -> Address {
+        let client = registry::Client::new(env, &env.storage().instance().get(&REGISTRY).unwrap());
+        if client.is_name_expired(name, tld) {
+            panic_with_error!(env, Error::NameExpired);
+        }
+        client.get_owner(name, tld)
+    }
+
+    pub(crate) fn lookup_live_owner(env: &Env, name: &Bytes, tld: &Bytes) -> Option<Address> {
+        let client = registry::Client::new(env, &env.storage().instance().get(&REGISTRY).unwrap());
+        client.lookup_owner(name, tld)
+    }
+
+    // Like `require_live_owner`, but an unregistered name reads as one without
+    // records.
+    fn resolving_owner(env: &Env, name: &Bytes, tld: &Bytes) -> Address {
+        let client = registry::Client::new(env, &env.storage().instance().get(&REGISTRY).unwrap());
+        if let Some(owner) = client.lookup_owner(name, tld) {
+            return owner;
         }
+        if client.is_name_registered(name, tld) {
+            panic_with_error!(env, Error::NameExpired);
+        }
+        panic_with_error!(env, Error::NameHasNoRecord);
     }
 
-    pub fn upgrade(e: Env, new_wasm_hash: BytesN<32>) {
-        let admin: Address = e.storage().instance().get(&ADMIN).unwrap();
-        admin.require_auth();
+    fn require_name_owner(env: &Env, name: &Bytes, tld: &Bytes) -> Address {
+        name.validate_name(env, true);
+        let owner = Self::require_live_owner(env, name, tld);
+        owner.require_auth();
+        owner
+    }
+
+    // Upgrades are announced first and can only be executed once `eta` has
+    // passed, so holders can review a code change before it takes effect.
+    pub fn schedule_upgrade(e: Env, caller: Address, new_wasm_hash: BytesN<32>, eta: u64) {
+        e.extend_me();
+        let admin = Self::require_role(&e, caller, Role::Upgrader);
+        access_control::schedule_upgrade::<Self>(&e, admin, new_wasm_hash, eta)
+    }
+
+    pub fn cancel_upgrade(e: Env, caller: Address) {
+        e.extend_me();
+        let admin = Self::require_role(&e, caller, Role::Upgrader);
+        access_control::cancel_upgrade::<Self>(&e, admin)
+    }
+
+    pub fn execute_upgrade(e: Env, caller: Address) {
+        e.extend_me();
+        let admin = Self::require_role(&e, caller, Role::Upgrader);
+        access_control::execute_upgrade::<Self>(&e, admin)
+    }
 
-        e.deployer().update_current_contract_wasm(new_wasm_hash);
+    pub fn pending_upgrade(e: Env) -> Option<ScheduledUpgrade> {
+        e.extend_me();
+        access_control::pending_upgrade(&e)
     }
 }
 
-mod test;
\ No newline at end of file
+mod test;
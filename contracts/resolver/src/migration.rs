@@ -0,0 +1,107 @@
+use crate::errors::*;
+use crate::events::*;
+use crate::types::*;
+use crate::utils::*;
+use crate::{
+    Resolver, ResolverArgs, ResolverClient, CURRENT_SCHEMA_VERSION, MIGRATION_CURSOR, NAME_COUNT,
+    SCHEMA_VERSION,
+};
+use access_control::Role;
+use soroban_sdk::{contractimpl, panic_with_error, Address, Bytes, Env, Vec};
+
+// After an upgrade that changes a stored type, `migrate` rewrites the entries
+// of every indexed name in the new layout, a batch at a time so no call runs
+// out of budget. Until it finishes, reads fall back to the old layout.
+#[contractimpl]
+impl Resolver {
+    // Deployments from before the schema version was stored report 0.
+    pub fn schema_version(env: Env) -> u32 {
+        env.extend_me();
+        env.storage().instance().get(&SCHEMA_VERSION).unwrap_or(0)
+    }
+
+    // Names given a record before schema version 1 are not in the name index
+    // that `migrate` walks. The admin adds them here, e.g. from past
+    // `set_record` calls, before migrating.
+    pub fn index_names(env: Env, caller: Address, names: Vec<(Bytes, Bytes)>) {
+        env.extend_me();
+        Self::require_role(&env, caller, Role::Upgrader);
+        Self::apply_index_names(env, names)
+    }
+
+    pub(crate) fn apply_index_names(env: Env, names: Vec<(Bytes, Bytes)>) {
+        if Self::schema_version(env.clone()) >= 1 {
+            panic_with_error!(&env, Error::NoMigrationPending);
+        }
+        for (name, tld) in names.iter() {
+            if !env
+                .storage()
+                .instance()
+                .has(&RecordKeys::Name(name.clone(), tld.clone()))
+            {
+                panic_with_error!(&env, Error::NameHasNoRecord);
+            }
+            env.index_name(&name, &tld);
+        }
+    }
+
+    // Migrates up to `batch_size` indexed names and returns whether the whole
+    // migration is done.
+    pub fn migrate(env: Env, caller: Address, batch_size: u32) -> bool {
+        env.extend_me();
+        let admin = Self::require_role(&env, caller, Role::Upgrader);
+        Self::apply_migrate(env, admin, batch_size)
+    }
+
+    pub(crate) fn apply_migrate(env: Env, admin: Address, batch_size: u32) -> bool {
+        let from_version = Self::schema_version(env.clone());
+        if from_version >= CURRENT_SCHEMA_VERSION {
+            panic_with_error!(&env, Error::NoMigrationPending);
+        }
+
+        let count: u32 = env.storage().instance().get(&NAME_COUNT).unwrap_or(0);
+        let cursor: u32 = env.storage().instance().get(&MIGRATION_CURSOR).unwrap_or(0);
+        let end = cursor.saturating_add(batch_size).min(count);
+        for index in cursor..end {
+            let (name, tld): (Bytes, Bytes) = env
+                .storage()
+                .persistent()
+                .get(&RecordKeys::NameAt(index))
+                .unwrap();
+            Self::migrate_name(&env, from_version, &name, &tld);
+        }
+        if end < count {
+            env.storage().instance().set(&MIGRATION_CURSOR, &end);
+            return false;
+        }
+
+        env.storage().instance().remove(&MIGRATION_CURSOR);
+        env.storage()
+            .instance()
+            .set(&SCHEMA_VERSION, &CURRENT_SCHEMA_VERSION);
+
+        MigrateEvent {
+            admin,
+            from_version,
+            to_version: CURRENT_SCHEMA_VERSION,
+        }
+        .publish(&env);
+        true
+    }
+
+    fn migrate_name(env: &Env, from_version: u32, name: &Bytes, tld: &Bytes) {
+        // 0 -> 1: records move out of instance storage and are bound to the
+        // current owner. Records of lapsed names are dropped, as they would not
+        // be served to the next owner anyway.
+        if from_version < 1 {
+            let key = RecordKeys::Name(name.clone(), tld.clone());
+            let Some(record) = env.storage().instance().get::<_, Record>(&key) else {
+                return;
+            };
+            env.storage().instance().remove(&key);
+            if let Some(owner) = Self::lookup_live_owner(env, name, tld) {
+                env.set_owned(&key, &owner, record);
+            }
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use crate::errors::*;
+use crate::events::*;
+use crate::types::*;
+use crate::utils::*;
+use crate::{Resolver, ResolverArgs, ResolverClient, MAX_GATEWAY_URLS, MAX_GATEWAY_URL_LENGTH};
+use soroban_sdk::{
+    contractimpl, panic_with_error, xdr::ToXdr, Address, Bytes, BytesN, Env, String, Vec,
+};
+
+// Off-chain lookup in the spirit of CCIP-read: a name can point at gateways that
+// serve its records, so large profiles do not have to live on-chain. The
+// gateway signs
+//
+//   (resolver address, callback_data, record, expires).to_xdr()
+//
+// with the configured ed25519 key, and the answer is only trusted once
+// `resolve_offchain_response` has checked that signature. While a name has
+// gateways, `resolve_name` and `lookup_record` answer with the same lookup as
+// `resolve_offchain`, so clients learn to ask the gateways without a second call.
+// Like records, the gateways are only used while their owner holds the name.
+#[contractimpl]
+impl Resolver {
+    pub fn set_offchain_resolver(
+        env: Env,
+        name: Bytes,
+        tld: Bytes,
+        urls: Vec<String>,
+        signer: BytesN<32>,
+    ) {
+        env.extend_me();
+        env.require_not_paused();
+        let owner = Self::require_name_owner(&env, &name, &tld);
+        if urls.is_empty() || urls.len() > MAX_GATEWAY_URLS {
+            panic_with_error!(&env, Error::OffchainConfigInvalid);
+        }
+        for url in urls.iter() {
+            if url.is_empty() || url.len() > MAX_GATEWAY_URL_LENGTH {
+                panic_with_error!(&env, Error::OffchainConfigInvalid);
+            }
+        }
+
+        let config = OffchainConfig { urls, signer };
+        env.set_owned(
+            &RecordKeys::Offchain(name.clone(), tld.clone()),
+            &owner,
+            config.clone(),
+        );
+
+        SetOffchainResolverEvent {
+            owner,
+            name,
+            tld,
+            config,
+        }
+        .publish(&env);
+    }
+
+    pub fn remove_offchain_resolver(env: Env, name: Bytes, tld: Bytes) {
+        env.extend_me();
+        env.require_not_paused();
+        let owner = Self::require_name_owner(&env, &name, &tld);
+        let key = RecordKeys::Offchain(name.clone(), tld.clone());
+        if env.get_owned::<OffchainConfig>(&key, &owner).is_none() {
+            panic_with_error!(&env, Error::NameHasNoRecord);
+        }
+        env.storage().persistent().remove(&key);
+
+        RemoveOffchainResolverEvent { owner, name, tld }.publish(&env);
+    }
+
+    pub fn resolve_offchain(env: Env, name: Bytes, tld: Bytes) -> OffchainLookup {
+        env.extend_me();
+        let owner = Self::require_live_owner(&env, &name, &tld);
+        Self::offchain_lookup(&env, &name, &tld, &owner)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NameHasNoRecord))
+    }
+
+    // Checks a gateway answer for `name` and returns the record it carries.
+    pub fn resolve_offchain_response(
+        env: Env,
+        name: Bytes,
+        tld: Bytes,
+        record: Record,
+        expires: u64,
+        signature: BytesN<64>,
+    ) -> Record {
+        env.extend_me();
+        let owner = Self::require_live_owner(&env, &name, &tld);
+        let config = Self::offchain_config(&env, &name, &tld, &owner);
+        if expires < env.ledger().timestamp() {
+            panic_with_error!(&env, Error::OffchainResponseExpired);
+        }
+
+        let callback_data = (name, tld).to_xdr(&env);
+        let message = (
+            env.current_contract_address(),
+            callback_data,
+            record.clone(),
+            expires,
+        )
+            .to_xdr(&env);
+        env.crypto()
+            .ed25519_verify(&config.signer, &message, &signature);
+        record
+    }
+
+    pub(crate) fn offchain_lookup(
+        env: &Env,
+        name: &Bytes,
+        tld: &Bytes,
+        owner: &Address,
+    ) -> Option<OffchainLookup> {
+        let config = Self::load_offchain_config(env, name, tld, owner)?;
+        Some(OffchainLookup {
+            urls: config.urls,
+            callback_data: (name.clone(), tld.clone()).to_xdr(env),
+        })
+    }
+
+    fn offchain_config(env: &Env, name: &Bytes, tld: &Bytes, owner: &Address) -> OffchainConfig {
+        Self::load_offchain_config(env, name, tld, owner)
+            .unwrap_or_else(|| panic_with_error!(env, Error::NameHasNoRecord))
+    }
+
+    fn load_offchain_config(
+        env: &Env,
+        name: &Bytes,
+        tld: &Bytes,
+        owner: &Address,
+    ) -> Option<OffchainConfig> {
+        env.get_owned(&RecordKeys::Offchain(name.clone(), tld.clone()), owner)
+    }
+}
@@ -0,0 +1,148 @@
+use crate::errors::*;
+use crate::events::*;
+use crate::types::*;
+use crate::utils::*;
+use crate::{Resolver, ResolverArgs, ResolverClient};
+use soroban_sdk::{contractimpl, panic_with_error, Address, Bytes, BytesN, Env, Symbol};
+
+const ED25519_KEY_LENGTH: u32 = 32;
+const X25519_KEY_LENGTH: u32 = 32;
+// SEC-1 uncompressed point, which is what `secp256k1_recover` hands back.
+const SECP256K1_KEY_LENGTH: u32 = 65;
+const SECP256K1_UNCOMPRESSED_PREFIX: u8 = 0x04;
+
+// Public keys published under a name, one per purpose (e.g. `messaging`,
+// `login`), so `alice.xlm` can be used as an identity by other applications.
+// A key is only served while the account that published it still owns the
+// name, so it does not carry over to whoever registers or buys the name next.
+#[contractimpl]
+impl Resolver {
+    pub fn set_public_key(
+        env: Env,
+        name: Bytes,
+        tld: Bytes,
+        purpose: Symbol,
+        key_type: KeyType,
+        key: Bytes,
+    ) {
+        env.extend_me();
+        env.require_not_paused();
+        let owner = Self::require_name_owner(&env, &name, &tld);
+        Self::apply_set_public_key(&env, &owner, &name, &tld, purpose, key_type, key);
+    }
+
+    pub fn remove_public_key(env: Env, name: Bytes, tld: Bytes, purpose: Symbol) {
+        env.extend_me();
+        env.require_not_paused();
+        let owner = Self::require_name_owner(&env, &name, &tld);
+        Self::apply_remove_public_key(&env, &owner, &name, &tld, purpose);
+    }
+
+    pub fn public_key(env: Env, name: Bytes, tld: Bytes, purpose: Symbol) -> PublicKey {
+        env.extend_me();
+        let owner = Self::require_live_owner(&env, &name, &tld);
+        env.get_owned(&RecordKeys::PublicKey(name, tld, purpose), &owner)
+            .unwrap_or_else(|| {
+                panic_with_error!(&env, Error::NameHasNoRecord);
+            })
+    }
+
+    // Checks `signature` over `message` against the key published for `purpose`
+    // and panics if it does not match. ed25519 signs the message itself, while
+    // secp256k1 signs its keccak256 digest and needs the recovery id. x25519 keys
+    // are for key agreement only and cannot be used here.
+    //
+    // A secp256k1 mismatch fails with `SignatureInvalid`. A bad ed25519
+    // signature aborts inside the host with a crypto error instead, which a
+    // contract cannot catch.
+    pub fn verify_signature(
+        env: Env,
+        name: Bytes,
+        tld: Bytes,
+        purpose: Symbol,
+        message: Bytes,
+        signature: BytesN<64>,
+        recovery_id: u32,
+    ) {
+        let public_key = Self::public_key(env.clone(), name, tld, purpose);
+        match public_key.key_type {
+            KeyType::Ed25519 => {
+                let key: BytesN<32> = public_key.key.try_into().unwrap();
+                env.crypto().ed25519_verify(&key, &message, &signature);
+            }
+            KeyType::Secp256k1 => {
+                let digest = env.crypto().keccak256(&message);
+                let recovered = env
+                    .crypto()
+                    .secp256k1_recover(&digest, &signature, recovery_id);
+                if Bytes::from(recovered) != public_key.key {
+                    panic_with_error!(&env, Error::SignatureInvalid);
+                }
+            }
+            KeyType::X25519 => panic_with_error!(&env, Error::KeyTypeNotForSigning),
+        }
+    }
+
+    pub(crate) fn apply_set_public_key(
+        env: &Env,
+        owner: &Address,
+        name: &Bytes,
+        tld: &Bytes,
+        purpose: Symbol,
+        key_type: KeyType,
+        key: Bytes,
+    ) {
+        let valid = match key_type {
+            KeyType::Ed25519 => key.len() == ED25519_KEY_LENGTH,
+            KeyType::X25519 => key.len() == X25519_KEY_LENGTH,
+            KeyType::Secp256k1 => {
+                key.len() == SECP256K1_KEY_LENGTH
+                    && key.first_unchecked() == SECP256K1_UNCOMPRESSED_PREFIX
+            }
+        };
+        if !valid {
+            panic_with_error!(env, Error::PublicKeyInvalid);
+        }
+
+        let public_key = PublicKey {
+            purpose: purpose.clone(),
+            key_type,
+            key,
+        };
+        env.set_owned(
+            &RecordKeys::PublicKey(name.clone(), tld.clone(), purpose),
+            owner,
+            public_key.clone(),
+        );
+
+        SetPublicKeyEvent {
+            owner: owner.clone(),
+            name: name.clone(),
+            tld: tld.clone(),
+            public_key,
+        }
+        .publish(env);
+    }
+
+    pub(crate) fn apply_remove_public_key(
+        env: &Env,
+        owner: &Address,
+        name: &Bytes,
+        tld: &Bytes,
+        purpose: Symbol,
+    ) {
+        let key = RecordKeys::PublicKey(name.clone(), tld.clone(), purpose.clone());
+        if env.get_owned::<PublicKey>(&key, owner).is_none() {
+            panic_with_error!(env, Error::NameHasNoRecord);
+        }
+        env.storage().persistent().remove(&key);
+
+        RemovePublicKeyEvent {
+            owner: owner.clone(),
+            name: name.clone(),
+            tld: tld.clone(),
+            purpose,
+        }
+        .publish(env);
+    }
+}
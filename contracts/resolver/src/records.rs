@@ -0,0 +1,333 @@
+use crate::{
+    errors::Error,
+    types::{ContentCodec, ContentHash, Memo, PaymentRecord},
+};
+use soroban_sdk::{panic_with_error, Address, Bytes, BytesN, Env};
+
+const SEPARATOR: u8 = b';';
+const MAX_MEMO_TEXT_LENGTH: u32 = 28;
+
+// strkey version bytes, see SEP-23.
+const ACCOUNT_ID_VERSION: u8 = 6 << 3;
+const CONTRACT_VERSION: u8 = 2 << 3;
+const MUXED_ACCOUNT_VERSION: u8 = 12 << 3;
+const ACCOUNT_ID_LENGTH: usize = 56;
+const MUXED_ACCOUNT_LENGTH: usize = 69;
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+// multicodec table entries used by ENSIP-7 style content hashes.
+const IPFS_NS: u64 = 0xe3;
+const SWARM_NS: u64 = 0xe4;
+const IPNS_NS: u64 = 0xe5;
+const ARWEAVE_NS: u64 = 0xb29910;
+const CID_V1: u64 = 0x01;
+const RAW: u64 = 0x55;
+const DAG_PB: u64 = 0x70;
+const DAG_CBOR: u64 = 0x71;
+const LIBP2P_KEY: u64 = 0x72;
+const SWARM_MANIFEST: u64 = 0xfa;
+const KECCAK_256: u64 = 0x1b;
+const MAX_DIGEST_LENGTH: u64 = 64;
+const ARWEAVE_TX_ID_LENGTH: u32 = 32;
+
+// Parses the `data` of a `payment` record. Accepted forms are:
+//
+//   G.../C...              plain destination
+//   M...                   muxed account, the id routes to the sub-account
+//   G...;id;<u64>          destination plus memo id
+//   G...;text;<utf8>       destination plus memo text (at most 28 bytes)
+//   G...;hash;<64 hex>     destination plus memo hash
+pub fn parse_payment(env: &Env, data: &Bytes) -> PaymentRecord {
+    let (destination, memo_type, memo_value) = split_payment(env, data);
+
+    if destination.first() == Some(b'M') {
+        if memo_type.is_some() {
+            panic_with_error!(env, Error::PaymentRecordInvalid);
+        }
+        let (destination, muxed_id) = decode_muxed_account(env, &destination);
+        return PaymentRecord {
+            destination,
+            muxed_id: Some(muxed_id),
+            memo: Memo::None,
+        };
+    }
+
+    let memo = match (memo_type, memo_value) {
+        (None, _) => Memo::None,
+        (Some(memo_type), Some(memo_value)) => parse_memo(env, &memo_type, &memo_value),
+        _ => panic_with_error!(env, Error::PaymentRecordInvalid),
+    };
+    PaymentRecord {
+        destination: parse_destination(env, &destination),
+        muxed_id: None,
+        memo,
+    }
+}
+
+fn split_payment(env: &Env, data: &Bytes) -> (Bytes, Option<Bytes>, Option<Bytes>) {
+    if data.is_empty() {
+        panic_with_error!(env, Error::PaymentRecordInvalid);
+    }
+    let first = match index_of(data, SEPARATOR) {
+        Some(index) => index,
+        None => return (data.clone(), None, None),
+    };
+    let rest = data.slice(first + 1..);
+    let second = index_of(&rest, SEPARATOR)
+        .unwrap_or_else(|| panic_with_error!(env, Error::PaymentRecordInvalid));
+    (
+        data.slice(..first),
+        Some(rest.slice(..second)),
+        Some(rest.slice(second + 1..)),
+    )
+}
+
+fn index_of(data: &Bytes, needle: u8) -> Option<u32> {
+    data.iter()
+        .position(|byte| byte == needle)
+        .map(|index| index as u32)
+}
+
+fn parse_memo(env: &Env, memo_type: &Bytes, memo_value: &Bytes) -> Memo {
+    if *memo_type == Bytes::from_slice(env, b"id") {
+        Memo::Id(parse_u64(env, memo_value))
+    } else if *memo_type == Bytes::from_slice(env, b"text") {
+        if memo_value.is_empty() || memo_value.len() > MAX_MEMO_TEXT_LENGTH {
+            panic_with_error!(env, Error::PaymentRecordInvalid);
+        }
+        let mut text = [0u8; MAX_MEMO_TEXT_LENGTH as usize];
+        let text = &mut text[..memo_value.len() as usize];
+        memo_value.copy_into_slice(text);
+        if core::str::from_utf8(text).is_err() {
+            panic_with_error!(env, Error::PaymentRecordInvalid);
+        }
+        Memo::Text(memo_value.clone())
+    } else if *memo_type == Bytes::from_slice(env, b"hash") {
+        Memo::Hash(parse_hex_32(env, memo_value))
+    } else {
+        panic_with_error!(env, Error::PaymentRecordInvalid);
+    }
+}
+
+fn parse_u64(env: &Env, value: &Bytes) -> u64 {
+    if value.is_empty() {
+        panic_with_error!(env, Error::PaymentRecordInvalid);
+    }
+    let mut result: u64 = 0;
+    for byte in value.iter() {
+        if !byte.is_ascii_digit() {
+            panic_with_error!(env, Error::PaymentRecordInvalid);
+        }
+        result = result
+            .checked_mul(10)
+            .and_then(|result| result.checked_add((byte - b'0') as u64))
+            .unwrap_or_else(|| panic_with_error!(env, Error::PaymentRecordInvalid));
+    }
+    result
+}
+
+fn parse_hex_32(env: &Env, value: &Bytes) -> BytesN<32> {
+    if value.len() != 64 {
+        panic_with_error!(env, Error::PaymentRecordInvalid);
+    }
+    let mut hash = [0u8; 32];
+    for (i, byte) in value.iter().enumerate() {
+        let nibble = match byte {
+            b'0'..=b'9' => byte - b'0',
+            b'a'..=b'f' => byte - b'a' + 10,
+            b'A'..=b'F' => byte - b'A' + 10,
+            _ => panic_with_error!(env, Error::PaymentRecordInvalid),
+        };
+        hash[i / 2] = (hash[i / 2] << 4) | nibble;
+    }
+    BytesN::from_array(env, &hash)
+}
+
+// Parses the `data` of a `contenthash` record: a multicodec namespace followed
+// by a CIDv1 (IPFS, IPNS, Swarm) or a raw 32 byte transaction id (Arweave).
+// The namespace is returned separately so gateways do not have to decode it.
+pub fn parse_content_hash(env: &Env, data: &Bytes) -> ContentHash {
+    let mut offset: u32 = 0;
+    let namespace = read_varint(env, data, &mut offset);
+    let hash = data.slice(offset..);
+
+    let codec = match namespace {
+        IPFS_NS => {
+            let content_codec = read_cid_v1(env, data, &mut offset);
+            if content_codec != DAG_PB && content_codec != RAW && content_codec != DAG_CBOR {
+                panic_with_error!(env, Error::ContentHashInvalid);
+            }
+            read_multihash(env, data, &mut offset);
+            ContentCodec::Ipfs
+        }
+        IPNS_NS => {
+            if read_cid_v1(env, data, &mut offset) != LIBP2P_KEY {
+                panic_with_error!(env, Error::ContentHashInvalid);
+            }
+            read_multihash(env, data, &mut offset);
+            ContentCodec::Ipns
+        }
+        SWARM_NS => {
+            if read_cid_v1(env, data, &mut offset) != SWARM_MANIFEST
+                || read_multihash(env, data, &mut offset) != (KECCAK_256, 32)
+            {
+                panic_with_error!(env, Error::ContentHashInvalid);
+            }
+            ContentCodec::Swarm
+        }
+        ARWEAVE_NS => {
+            if hash.len() != ARWEAVE_TX_ID_LENGTH {
+                panic_with_error!(env, Error::ContentHashInvalid);
+            }
+            ContentCodec::Arweave
+        }
+        _ => panic_with_error!(env, Error::ContentHashInvalid),
+    };
+    ContentHash { codec, hash }
+}
+
+fn read_cid_v1(env: &Env, data: &Bytes, offset: &mut u32) -> u64 {
+    if read_varint(env, data, offset) != CID_V1 {
+        panic_with_error!(env, Error::ContentHashInvalid);
+    }
+    read_varint(env, data, offset)
+}
+
+// Checks that the rest of `data` is exactly one multihash and returns its
+// hash function code and digest length.
+fn read_multihash(env: &Env, data: &Bytes, offset: &mut u32) -> (u64, u64) {
+    let code = read_varint(env, data, offset);
+    let length = read_varint(env, data, offset);
+    if length == 0 || length > MAX_DIGEST_LENGTH || (data.len() - *offset) as u64 != length {
+        panic_with_error!(env, Error::ContentHashInvalid);
+    }
+    *offset = data.len();
+    (code, length)
+}
+
+// Unsigned LEB128 as used by multiformats, capped at 9 bytes.
+fn read_varint(env: &Env, data: &Bytes, offset: &mut u32) -> u64 {
+    let mut result: u64 = 0;
+    for i in 0..9 {
+        let byte = data
+            .get(*offset)
+            .unwrap_or_else(|| panic_with_error!(env, Error::ContentHashInvalid));
+        *offset += 1;
+        result |= ((byte & 0x7f) as u64) << (7 * i);
+        if byte & 0x80 == 0 {
+            return result;
+        }
+    }
+    panic_with_error!(env, Error::ContentHashInvalid);
+}
+
+// Checks the strkey of a G... account or C... contract before handing it to
+// `Address::from_string_bytes`, which aborts the call on malformed input.
+fn parse_destination(env: &Env, destination: &Bytes) -> Address {
+    if destination.len() as usize != ACCOUNT_ID_LENGTH {
+        panic_with_error!(env, Error::PaymentRecordInvalid);
+    }
+    let mut encoded = [0u8; ACCOUNT_ID_LENGTH];
+    destination.copy_into_slice(&mut encoded);
+    // 1 version byte, 32 key bytes and a 2 byte checksum.
+    let mut raw = [0u8; 35];
+    if !base32_decode(&encoded, &mut raw)
+        || (raw[0] != ACCOUNT_ID_VERSION && raw[0] != CONTRACT_VERSION)
+    {
+        panic_with_error!(env, Error::PaymentRecordInvalid);
+    }
+    if crc16(&raw[..33]).to_le_bytes() != [raw[33], raw[34]] {
+        panic_with_error!(env, Error::PaymentRecordInvalid);
+    }
+    Address::from_string_bytes(destination)
+}
+
+// Splits an M... address into the underlying G... account and the muxed id, since
+// `Address` cannot hold a muxed account.
+fn decode_muxed_account(env: &Env, muxed: &Bytes) -> (Address, u64) {
+    if muxed.len() as usize != MUXED_ACCOUNT_LENGTH {
+        panic_with_error!(env, Error::PaymentRecordInvalid);
+    }
+    let mut encoded = [0u8; MUXED_ACCOUNT_LENGTH];
+    muxed.copy_into_slice(&mut encoded);
+    // 1 version byte, 32 key bytes, 8 id bytes and a 2 byte checksum.
+    let mut raw = [0u8; 43];
+    if !base32_decode(&encoded, &mut raw) || raw[0] != MUXED_ACCOUNT_VERSION {
+        panic_with_error!(env, Error::PaymentRecordInvalid);
+    }
+    if crc16(&raw[..41]).to_le_bytes() != [raw[41], raw[42]] {
+        panic_with_error!(env, Error::PaymentRecordInvalid);
+    }
+
+    let mut account = [0u8; 35];
+    account[0] = ACCOUNT_ID_VERSION;
+    account[1..33].copy_from_slice(&raw[1..33]);
+    let checksum = crc16(&account[..33]).to_le_bytes();
+    account[33..].copy_from_slice(&checksum);
+    let mut account_id = [0u8; ACCOUNT_ID_LENGTH];
+    base32_encode(&account, &mut account_id);
+
+    let mut id = [0u8; 8];
+    id.copy_from_slice(&raw[33..41]);
+    (
+        Address::from_string_bytes(&Bytes::from_slice(env, &account_id)),
+        u64::from_be_bytes(id),
+    )
+}
+
+fn base32_decode(input: &[u8], output: &mut [u8]) -> bool {
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    let mut written = 0;
+    for byte in input {
+        let value = match BASE32_ALPHABET.iter().position(|c| c == byte) {
+            Some(value) => value as u32,
+            None => return false,
+        };
+        buffer = (buffer << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            if written == output.len() {
+                return false;
+            }
+            output[written] = (buffer >> bits) as u8;
+            written += 1;
+        }
+    }
+    written == output.len()
+}
+
+fn base32_encode(input: &[u8], output: &mut [u8]) {
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    let mut written = 0;
+    for byte in input {
+        buffer = (buffer << 8) | *byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            output[written] = BASE32_ALPHABET[((buffer >> bits) & 31) as usize];
+            written += 1;
+        }
+    }
+    if bits > 0 {
+        output[written] = BASE32_ALPHABET[((buffer << (5 - bits)) & 31) as usize];
+    }
+}
+
+// CRC16-XModem, the checksum used by strkey.
+fn crc16(data: &[u8]) -> u16 {
+    let mut crc: u16 = 0;
+    for byte in data {
+        crc ^= (*byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
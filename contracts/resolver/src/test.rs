@@ -1,12 +1,20 @@
 #![cfg(test)]
+#![allow(clippy::bool_assert_comparison, clippy::needless_borrow)]
+extern crate std;
 
 use super::*;
+use access_control::{CancelUpgradeEvent, PauseEvent, Role, UpgradeEvent, MIN_UPGRADE_DELAY};
 use soroban_sdk::{
-    testutils::{Address as _, Ledger},
-    token, vec, Address, Env,
+    testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation, Events, Ledger},
+    token, vec,
+    xdr::ToXdr,
+    Address, Env, IntoVal, String, Val, Vec,
 };
 
 const MAX_ASSET: i128 = 100000;
+const ONE_YEAR_IN_SECONDS: u64 = 365 * 24 * 60 * 60;
+// Version of the events emitted by the imported registry.
+const REGISTRY_EVENT_VERSION: u32 = 1;
 
 fn create_token_contract<'a>(
     e: &Env,
@@ -19,6 +27,17 @@ fn create_token_contract<'a>(
     )
 }
 
+fn get_events_by_contract_id(e: &Env, contract_id: &Address) -> Vec<(Address, Vec<Val>, Val)> {
+    let all_events: Vec<(Address, Vec<Val>, Val)> = e.events().all();
+    let mut contract_events: Vec<(Address, Vec<Val>, Val)> = vec![&e];
+    for event in all_events.iter() {
+        if event.0 == contract_id.clone() {
+            contract_events.push_back(event.clone());
+        }
+    }
+    contract_events
+}
+
 fn address_to_bytes(address: &Address) -> Bytes {
     let mut address_bytes_slice = [0u8; 56];
     address
@@ -43,13 +62,15 @@ fn test_set_resolved_record_type_invalid() {
 
     let resolver_client = ResolverClient::new(&env, &resolver_id);
     let registry_client = registry::Client::new(&env, &registry_id);
+    let controller = Address::generate(&env);
+    registry_client.add_controller(&admin, &controller);
 
     let name = Bytes::from_slice(&env, "test".as_bytes());
     let owner = Address::generate(&env);
 
     token_admin.mint(&owner, &MAX_ASSET);
-    registry_client.set_resolver(&resolver_id);
-    registry_client.register_name(&name, &com_tld, &owner, &1);
+    registry_client.set_resolver(&admin, &resolver_id);
+    registry_client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
 
     let address_to_be_resolved = Address::generate(&env);
 
@@ -81,13 +102,15 @@ fn test_get_resolved_record_type_name() {
 
     let resolver_client = ResolverClient::new(&env, &resolver_id);
     let registry_client = registry::Client::new(&env, &registry_id);
+    let controller = Address::generate(&env);
+    registry_client.add_controller(&admin, &controller);
 
     let name = Bytes::from_slice(&env, "test".as_bytes());
     let owner = Address::generate(&env);
 
     token_admin.mint(&owner, &MAX_ASSET);
-    registry_client.set_resolver(&resolver_id);
-    registry_client.register_name(&name, &com_tld, &owner, &1);
+    registry_client.set_resolver(&admin, &resolver_id);
+    registry_client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
 
     let address_to_be_resolved = Address::generate(&env);
 
@@ -108,8 +131,16 @@ fn test_get_resolved_record_type_name() {
     );
 }
 
+fn hex_to_bytes(env: &Env, hex: &str) -> Bytes {
+    let mut bytes = Bytes::new(env);
+    for i in (0..hex.len()).step_by(2) {
+        bytes.push_back(u8::from_str_radix(&hex[i..i + 2], 16).unwrap());
+    }
+    bytes
+}
+
 #[test]
-fn test_get_resolved_record_type_ipfs() {
+fn test_get_resolved_record_type_content_hash() {
     let env = Env::default();
     env.mock_all_auths();
     let admin = Address::generate(&env);
@@ -123,26 +154,162 @@ fn test_get_resolved_record_type_ipfs() {
 
     let resolver_client = ResolverClient::new(&env, &resolver_id);
     let registry_client = registry::Client::new(&env, &registry_id);
+    let controller = Address::generate(&env);
+    registry_client.add_controller(&admin, &controller);
 
     let name = Bytes::from_slice(&env, "test".as_bytes());
     let owner = Address::generate(&env);
 
     token_admin.mint(&owner, &MAX_ASSET);
-    registry_client.set_resolver(&resolver_id);
-    registry_client.register_name(&name, &com_tld, &owner, &1);
+    registry_client.set_resolver(&admin, &resolver_id);
+    registry_client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
 
-    let my_hash = Bytes::from_slice(&env, "this is my hash".as_bytes());
+    let content_hash_type = Bytes::from_slice(&env, "contenthash".as_bytes());
+    let cases = [
+        (
+            "e3010170122029f2d17be6139079dc48696d1f582a8530eb9805b561eda517e22a892c7e3f1f",
+            ContentCodec::Ipfs,
+            "0170122029f2d17be6139079dc48696d1f582a8530eb9805b561eda517e22a892c7e3f1f",
+        ),
+        (
+            "e50101720024080112205f9f7c8a9e9f5c7a2f1bd2a6b4a9e3b08b1a5a2d3c1f9e0b7a6d5c4b3a291800",
+            ContentCodec::Ipns,
+            "01720024080112205f9f7c8a9e9f5c7a2f1bd2a6b4a9e3b08b1a5a2d3c1f9e0b7a6d5c4b3a291800",
+        ),
+        (
+            "e40101fa011b20d1de9994b4d039f6548d191eb26786769f580809256b4685ef316805265ea162",
+            ContentCodec::Swarm,
+            "01fa011b20d1de9994b4d039f6548d191eb26786769f580809256b4685ef316805265ea162",
+        ),
+        (
+            "90b2ca05a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f90",
+            ContentCodec::Arweave,
+            "a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f90",
+        ),
+    ];
+    for (content_hash, codec, hash) in cases {
+        resolver_client.set_record(
+            &name,
+            &com_tld,
+            &content_hash_type,
+            &hex_to_bytes(&env, content_hash),
+        );
+
+        assert_eq!(
+            resolver_client.resolve_name(&name, &com_tld),
+            Record::ContentHash(ContentHash {
+                codec,
+                hash: hex_to_bytes(&env, hash),
+            })
+        );
+    }
+
+    // Raw IPFS records stored by older versions still resolve, but cannot be
+    // written anymore.
+    let cid = Bytes::from_slice(&env, b"QmRAQB6YaCyidP37UdDnjFY5vQuiBrcqdyoW1CuDgwxkD4");
+    env.as_contract(&resolver_id, || {
+        let key = RecordKeys::Name(name.clone(), com_tld.clone());
+        env.storage().persistent().remove(&key);
+        env.storage()
+            .instance()
+            .set(&key, &Record::Ipfs(cid.clone()));
+    });
+    assert_eq!(
+        resolver_client.resolve_name(&name, &com_tld),
+        Record::Ipfs(cid.clone())
+    );
+    assert_eq!(
+        resolver_client.try_set_record(&name, &com_tld, &Bytes::from_slice(&env, b"ipfs"), &cid),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::RecordTypeInvalid as u32
+        )))
+    );
 
+    // The content hash stays with the owner that set it when the name is
+    // transferred.
     resolver_client.set_record(
         &name,
         &com_tld,
-        &Bytes::from_slice(&env, "ipfs".as_bytes()),
-        &my_hash,
+        &content_hash_type,
+        &hex_to_bytes(&env, cases[0].0),
     );
-
+    registry_client.transfer(&name, &com_tld, &Address::generate(&env));
     assert_eq!(
-        resolver_client.resolve_name(&name, &com_tld),
-        Record::Ipfs(my_hash)
+        resolver_client.try_resolve_name(&name, &com_tld),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::NameHasNoRecord as u32
+        )))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #15)")]
+fn test_set_content_hash_garbage() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let registry_id = env.register(
+        registry::WASM,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let resolver_id = env.register(Resolver, (&admin, &registry_id));
+
+    let resolver_client = ResolverClient::new(&env, &resolver_id);
+    let registry_client = registry::Client::new(&env, &registry_id);
+    let controller = Address::generate(&env);
+    registry_client.add_controller(&admin, &controller);
+
+    let name = Bytes::from_slice(&env, "test".as_bytes());
+    let owner = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET);
+    registry_client.set_resolver(&admin, &resolver_id);
+    registry_client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+
+    resolver_client.set_record(
+        &name,
+        &com_tld,
+        &Bytes::from_slice(&env, "contenthash".as_bytes()),
+        &Bytes::from_slice(&env, "this is my hash".as_bytes()),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #15)")]
+fn test_set_content_hash_truncated_digest() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let registry_id = env.register(
+        registry::WASM,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let resolver_id = env.register(Resolver, (&admin, &registry_id));
+
+    let resolver_client = ResolverClient::new(&env, &resolver_id);
+    let registry_client = registry::Client::new(&env, &registry_id);
+    let controller = Address::generate(&env);
+    registry_client.add_controller(&admin, &controller);
+
+    let name = Bytes::from_slice(&env, "test".as_bytes());
+    let owner = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET);
+    registry_client.set_resolver(&admin, &resolver_id);
+    registry_client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+
+    resolver_client.set_record(
+        &name,
+        &com_tld,
+        &Bytes::from_slice(&env, "contenthash".as_bytes()),
+        &hex_to_bytes(
+            &env,
+            "e3010170122029f2d17be6139079dc48696d1f582a8530eb9805b561eda517e22a892c7e3f",
+        ),
     );
 }
 
@@ -161,13 +328,15 @@ fn test_get_resolved_record_type_text() {
 
     let resolver_client = ResolverClient::new(&env, &resolver_id);
     let registry_client = registry::Client::new(&env, &registry_id);
+    let controller = Address::generate(&env);
+    registry_client.add_controller(&admin, &controller);
 
     let name = Bytes::from_slice(&env, "test".as_bytes());
     let owner = Address::generate(&env);
 
     token_admin.mint(&owner, &MAX_ASSET);
-    registry_client.set_resolver(&resolver_id);
-    registry_client.register_name(&name, &com_tld, &owner, &1);
+    registry_client.set_resolver(&admin, &resolver_id);
+    registry_client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
 
     let my_text = Bytes::from_slice(&env, "this is my text".as_bytes());
 
@@ -200,13 +369,15 @@ fn test_get_record_from_registered_but_not_set() {
 
     let resolver_client = ResolverClient::new(&env, &resolver_id);
     let registry_client = registry::Client::new(&env, &registry_id);
+    let controller = Address::generate(&env);
+    registry_client.add_controller(&admin, &controller);
 
     let name = Bytes::from_slice(&env, "test".as_bytes());
     let owner = Address::generate(&env);
 
     token_admin.mint(&owner, &MAX_ASSET);
-    registry_client.set_resolver(&resolver_id);
-    registry_client.register_name(&name, &com_tld, &owner, &1);
+    registry_client.set_resolver(&admin, &resolver_id);
+    registry_client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
     resolver_client.resolve_name(&name, &com_tld);
 }
 
@@ -275,13 +446,15 @@ fn test_set_resolved_wrong_name_but_there_is_another_is_valid() {
 
     let resolver_client = ResolverClient::new(&env, &resolver_id);
     let registry_client = registry::Client::new(&env, &registry_id);
+    let controller = Address::generate(&env);
+    registry_client.add_controller(&admin, &controller);
 
     let name = Bytes::from_slice(&env, "test".as_bytes());
     let owner = Address::generate(&env);
 
     token_admin.mint(&owner, &MAX_ASSET);
-    registry_client.set_resolver(&resolver_id);
-    registry_client.register_name(&name, &com_tld, &owner, &1);
+    registry_client.set_resolver(&admin, &resolver_id);
+    registry_client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
 
     let address_to_be_resolved = Address::generate(&env);
 
@@ -318,13 +491,15 @@ fn test_name_expired() {
 
     let resolver_client = ResolverClient::new(&env, &resolver_id);
     let registry_client = registry::Client::new(&env, &registry_id);
+    let controller = Address::generate(&env);
+    registry_client.add_controller(&admin, &controller);
 
     let name = Bytes::from_slice(&env, "test".as_bytes());
     let owner = Address::generate(&env);
 
     token_admin.mint(&owner, &MAX_ASSET);
-    registry_client.set_resolver(&resolver_id);
-    registry_client.register_name(&name, &com_tld, &owner, &1);
+    registry_client.set_resolver(&admin, &resolver_id);
+    registry_client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
     let address_to_be_resolved = Address::generate(&env);
 
     let name_sub = Bytes::from_slice(&env, "feiyu.test".as_bytes());
@@ -342,3 +517,2110 @@ fn test_name_expired() {
     env.ledger().set_timestamp(1000000000000000);
     resolver_client.resolve_name(&name_sub, &com_tld);
 }
+
+#[test]
+fn test_set_and_delete_record_events() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let registry_id = env.register(
+        registry::WASM,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let resolver_id = env.register(Resolver, (&admin, &registry_id));
+
+    let resolver_client = ResolverClient::new(&env, &resolver_id);
+    let registry_client = registry::Client::new(&env, &registry_id);
+    let controller = Address::generate(&env);
+    registry_client.add_controller(&admin, &controller);
+
+    let name = Bytes::from_slice(&env, "test".as_bytes());
+    let owner = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET);
+    registry_client.set_resolver(&admin, &resolver_id);
+    registry_client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+
+    let my_text = Bytes::from_slice(&env, "this is my text".as_bytes());
+
+    resolver_client.set_record(
+        &name,
+        &com_tld,
+        &Bytes::from_slice(&env, "text".as_bytes()),
+        &my_text,
+    );
+
+    assert_eq!(
+        get_events_by_contract_id(&env, &resolver_id),
+        vec![
+            &env,
+            (
+                resolver_id.clone(),
+                (Symbol::new(&env, "set_record"), EVENT_VERSION).into_val(&env),
+                SetRecordEvent {
+                    owner: owner.clone(),
+                    name: name.clone(),
+                    tld: com_tld.clone(),
+                    record: Record::Text(my_text),
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+
+    resolver_client.delete_record(&name, &com_tld);
+
+    assert_eq!(
+        get_events_by_contract_id(&env, &resolver_id),
+        vec![
+            &env,
+            (
+                resolver_id.clone(),
+                (Symbol::new(&env, "delete_record"), EVENT_VERSION).into_val(&env),
+                DeleteRecordEvent {
+                    owner: owner.clone(),
+                    name: name.clone(),
+                    tld: com_tld.clone(),
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+    assert!(!resolver_client.is_name_has_record(&name, &com_tld));
+}
+
+#[test]
+fn test_upgrade_events() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let registry_id = env.register(
+        registry::WASM,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let resolver_id = env.register(Resolver, (&admin, &registry_id));
+
+    let resolver_client = ResolverClient::new(&env, &resolver_id);
+    let registry_client = registry::Client::new(&env, &registry_id);
+
+    let wasm_hash = env.deployer().upload_contract_wasm(registry::WASM);
+    let eta = env.ledger().timestamp() + MIN_UPGRADE_DELAY;
+
+    assert_eq!(
+        registry_client.try_schedule_upgrade(&admin, &wasm_hash, &(eta - 1)),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::UpgradeEtaTooEarly as u32
+        )))
+    );
+    registry_client.schedule_upgrade(&admin, &wasm_hash, &eta);
+
+    assert_eq!(
+        get_events_by_contract_id(&env, &registry_id),
+        vec![
+            &env,
+            (
+                registry_id.clone(),
+                (
+                    Symbol::new(&env, "schedule_upgrade"),
+                    REGISTRY_EVENT_VERSION
+                )
+                    .into_val(&env),
+                registry::ScheduleUpgradeEvent {
+                    admin: admin.clone(),
+                    new_wasm_hash: wasm_hash.clone(),
+                    eta,
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+
+    assert_eq!(
+        registry_client.try_execute_upgrade(&admin),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::UpgradeNotReady as u32
+        )))
+    );
+    env.ledger().set_timestamp(eta);
+    registry_client.execute_upgrade(&admin);
+
+    assert_eq!(
+        get_events_by_contract_id(&env, &registry_id),
+        vec![
+            &env,
+            (
+                registry_id.clone(),
+                (Symbol::new(&env, "upgrade"), REGISTRY_EVENT_VERSION).into_val(&env),
+                registry::UpgradeEvent {
+                    admin: admin.clone(),
+                    new_wasm_hash: wasm_hash.clone(),
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+    assert_eq!(registry_client.pending_upgrade(), None);
+
+    let eta = env.ledger().timestamp() + MIN_UPGRADE_DELAY;
+    resolver_client.schedule_upgrade(&admin, &wasm_hash, &eta);
+    assert_eq!(
+        resolver_client.pending_upgrade(),
+        Some(ScheduledUpgrade {
+            new_wasm_hash: wasm_hash.clone(),
+            eta,
+        })
+    );
+    assert_eq!(
+        resolver_client.try_schedule_upgrade(&admin, &wasm_hash, &(eta + 1)),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::UpgradePending as u32
+        )))
+    );
+    resolver_client.cancel_upgrade(&admin);
+
+    assert_eq!(
+        get_events_by_contract_id(&env, &resolver_id),
+        vec![
+            &env,
+            (
+                resolver_id.clone(),
+                (Symbol::new(&env, "cancel_upgrade"), EVENT_VERSION).into_val(&env),
+                CancelUpgradeEvent {
+                    admin: admin.clone(),
+                    new_wasm_hash: wasm_hash.clone(),
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+    assert_eq!(
+        resolver_client.try_execute_upgrade(&admin),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::NoPendingUpgrade as u32
+        )))
+    );
+
+    resolver_client.schedule_upgrade(&admin, &wasm_hash, &eta);
+    env.ledger().set_timestamp(eta);
+    resolver_client.execute_upgrade(&admin);
+
+    assert_eq!(
+        get_events_by_contract_id(&env, &resolver_id),
+        vec![
+            &env,
+            (
+                resolver_id.clone(),
+                (Symbol::new(&env, "upgrade"), EVENT_VERSION).into_val(&env),
+                UpgradeEvent {
+                    admin: admin.clone(),
+                    new_wasm_hash: wasm_hash.clone(),
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+}
+
+#[test]
+fn test_get_resolved_record_type_payment_muxed() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let registry_id = env.register(
+        registry::WASM,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let resolver_id = env.register(Resolver, (&admin, &registry_id));
+
+    let resolver_client = ResolverClient::new(&env, &resolver_id);
+    let registry_client = registry::Client::new(&env, &registry_id);
+    let controller = Address::generate(&env);
+    registry_client.add_controller(&admin, &controller);
+
+    let name = Bytes::from_slice(&env, "test".as_bytes());
+    let owner = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET);
+    registry_client.set_resolver(&admin, &resolver_id);
+    registry_client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+
+    resolver_client.set_record(
+        &name,
+        &com_tld,
+        &Bytes::from_slice(&env, "payment".as_bytes()),
+        &Bytes::from_slice(
+            &env,
+            "MAAQEAYEAUDAOCAJBIFQYDIOB4IBCEQTCQKRMFYYDENBWHA5DYPSAAAAAEPXD6YEZO4WE".as_bytes(),
+        ),
+    );
+
+    assert_eq!(
+        resolver_client.resolve_name(&name, &com_tld),
+        Record::Payment(PaymentRecord {
+            destination: Address::from_str(
+                &env,
+                "GAAQEAYEAUDAOCAJBIFQYDIOB4IBCEQTCQKRMFYYDENBWHA5DYPSABOV"
+            ),
+            muxed_id: Some(1234567890123),
+            memo: Memo::None,
+        })
+    );
+}
+
+#[test]
+fn test_payment_record_not_carried_over_to_new_owner() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let registry_id = env.register(
+        registry::WASM,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let resolver_id = env.register(Resolver, (&admin, &registry_id));
+
+    let resolver_client = ResolverClient::new(&env, &resolver_id);
+    let registry_client = registry::Client::new(&env, &registry_id);
+    let controller = Address::generate(&env);
+    registry_client.add_controller(&admin, &controller);
+
+    let name = Bytes::from_slice(&env, "test".as_bytes());
+    let owner = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET);
+    registry_client.set_resolver(&admin, &resolver_id);
+    registry_client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+
+    let payment = Bytes::from_slice(&env, "payment".as_bytes());
+    resolver_client.set_record(
+        &name,
+        &com_tld,
+        &payment,
+        &Bytes::from_slice(
+            &env,
+            "GAAQEAYEAUDAOCAJBIFQYDIOB4IBCEQTCQKRMFYYDENBWHA5DYPSABOV".as_bytes(),
+        ),
+    );
+    env.as_contract(&resolver_id, || {
+        let key = RecordKeys::Name(name.clone(), com_tld.clone());
+        assert!(env.storage().persistent().has(&key));
+        assert!(!env.storage().instance().has(&key));
+    });
+
+    // Someone else registers the name once the grace period is over.
+    let release_time = registry_client
+        .lookup_release_time(&name, &com_tld)
+        .unwrap();
+    env.ledger().set_timestamp(release_time + 1);
+    let new_owner = Address::generate(&env);
+    registry_client.controller_register(
+        &controller,
+        &name,
+        &com_tld,
+        &new_owner,
+        &ONE_YEAR_IN_SECONDS,
+    );
+
+    assert_eq!(
+        resolver_client.try_resolve_name(&name, &com_tld),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::NameHasNoRecord as u32
+        )))
+    );
+    assert_eq!(resolver_client.lookup_record(&name, &com_tld), None);
+    assert!(!resolver_client.is_name_has_record(&name, &com_tld));
+
+    let new_destination = Address::generate(&env);
+    resolver_client.set_record(
+        &name,
+        &com_tld,
+        &payment,
+        &address_to_bytes(&new_destination),
+    );
+    assert_eq!(
+        resolver_client.resolve_name(&name, &com_tld),
+        Record::Payment(PaymentRecord {
+            destination: new_destination,
+            muxed_id: None,
+            memo: Memo::None,
+        })
+    );
+}
+
+#[test]
+fn test_get_resolved_record_type_payment_with_memo() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let registry_id = env.register(
+        registry::WASM,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let resolver_id = env.register(Resolver, (&admin, &registry_id));
+
+    let resolver_client = ResolverClient::new(&env, &resolver_id);
+    let registry_client = registry::Client::new(&env, &registry_id);
+    let controller = Address::generate(&env);
+    registry_client.add_controller(&admin, &controller);
+
+    let name = Bytes::from_slice(&env, "test".as_bytes());
+    let owner = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET);
+    registry_client.set_resolver(&admin, &resolver_id);
+    registry_client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+
+    let exchange = Address::generate(&env);
+    let payment_type = Bytes::from_slice(&env, "payment".as_bytes());
+
+    let mut data = address_to_bytes(&exchange);
+    data.extend_from_slice(b";id;98765");
+    resolver_client.set_record(&name, &com_tld, &payment_type, &data);
+    assert_eq!(
+        resolver_client.resolve_name(&name, &com_tld),
+        Record::Payment(PaymentRecord {
+            destination: exchange.clone(),
+            muxed_id: None,
+            memo: Memo::Id(98765),
+        })
+    );
+
+    let mut data = address_to_bytes(&exchange);
+    data.extend_from_slice(b";text;alice");
+    resolver_client.set_record(&name, &com_tld, &payment_type, &data);
+    assert_eq!(
+        resolver_client.resolve_name(&name, &com_tld),
+        Record::Payment(PaymentRecord {
+            destination: exchange.clone(),
+            muxed_id: None,
+            memo: Memo::Text(Bytes::from_slice(&env, b"alice")),
+        })
+    );
+
+    let mut data = address_to_bytes(&exchange);
+    data.extend_from_slice(
+        b";hash;000102030405060708090a0b0c0d0e0f101112131415161718191A1B1C1D1E1F",
+    );
+    let mut hash = [0u8; 32];
+    for (i, byte) in hash.iter_mut().enumerate() {
+        *byte = i as u8;
+    }
+    resolver_client.set_record(&name, &com_tld, &payment_type, &data);
+    assert_eq!(
+        resolver_client.resolve_name(&name, &com_tld),
+        Record::Payment(PaymentRecord {
+            destination: exchange.clone(),
+            muxed_id: None,
+            memo: Memo::Hash(BytesN::from_array(&env, &hash)),
+        })
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #14)")]
+fn test_set_payment_record_bad_muxed_checksum() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let registry_id = env.register(
+        registry::WASM,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let resolver_id = env.register(Resolver, (&admin, &registry_id));
+
+    let resolver_client = ResolverClient::new(&env, &resolver_id);
+    let registry_client = registry::Client::new(&env, &registry_id);
+    let controller = Address::generate(&env);
+    registry_client.add_controller(&admin, &controller);
+
+    let name = Bytes::from_slice(&env, "test".as_bytes());
+    let owner = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET);
+    registry_client.set_resolver(&admin, &resolver_id);
+    registry_client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+
+    resolver_client.set_record(
+        &name,
+        &com_tld,
+        &Bytes::from_slice(&env, "payment".as_bytes()),
+        &Bytes::from_slice(
+            &env,
+            "MAAQEAYEAUDAOCAJBIFQYDIOB4IBCEQTCQKRMFYYDENBWHA5DYPSAAAAAEPXD6YEZO4WA".as_bytes(),
+        ),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #14)")]
+fn test_set_payment_record_memo_text_too_long() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let registry_id = env.register(
+        registry::WASM,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let resolver_id = env.register(Resolver, (&admin, &registry_id));
+
+    let resolver_client = ResolverClient::new(&env, &resolver_id);
+    let registry_client = registry::Client::new(&env, &registry_id);
+    let controller = Address::generate(&env);
+    registry_client.add_controller(&admin, &controller);
+
+    let name = Bytes::from_slice(&env, "test".as_bytes());
+    let owner = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET);
+    registry_client.set_resolver(&admin, &resolver_id);
+    registry_client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+
+    let mut data = address_to_bytes(&Address::generate(&env));
+    data.extend_from_slice(b";text;this memo is longer than the stellar limit");
+    resolver_client.set_record(
+        &name,
+        &com_tld,
+        &Bytes::from_slice(&env, "payment".as_bytes()),
+        &data,
+    );
+}
+
+#[test]
+fn test_set_payment_record_malformed() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let registry_id = env.register(
+        registry::WASM,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let resolver_id = env.register(Resolver, (&admin, &registry_id));
+
+    let resolver_client = ResolverClient::new(&env, &resolver_id);
+    let registry_client = registry::Client::new(&env, &registry_id);
+    let controller = Address::generate(&env);
+    registry_client.add_controller(&admin, &controller);
+
+    let name = Bytes::from_slice(&env, "test".as_bytes());
+    let owner = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET);
+    registry_client.set_resolver(&admin, &resolver_id);
+    registry_client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+
+    let payment_type = Bytes::from_slice(&env, "payment".as_bytes());
+    let invalid = Err(Ok(soroban_sdk::Error::from_contract_error(
+        Error::PaymentRecordInvalid as u32,
+    )));
+    let account = "GAAZI4TCR3TY5OJHCTJC2A4QSY6CJWJH5IAJTGKIN2ER7LBNVKOCCWN7";
+    let mut non_utf8_memo = Bytes::from_slice(&env, account.as_bytes());
+    non_utf8_memo.extend_from_slice(b";text;caf\xe9");
+    for data in [
+        // No destination.
+        Bytes::from_slice(&env, b";id;5"),
+        // Too short, and a bad checksum.
+        Bytes::from_slice(&env, b"GABC;id;5"),
+        Bytes::from_slice(
+            &env,
+            b"GAAZI4TCR3TY5OJHCTJC2A4QSY6CJWJH5IAJTGKIN2ER7LBNVKOCCWN6",
+        ),
+        // Not base32, and a seed rather than an account.
+        Bytes::from_slice(
+            &env,
+            b"GAAZI4TCR3TY5OJHCTJC2A4QSY6CJWJH5IAJTGKIN2ER7LBNVKOCCWN1",
+        ),
+        Bytes::from_slice(
+            &env,
+            b"SAAZI4TCR3TY5OJHCTJC2A4QSY6CJWJH5IAJTGKIN2ER7LBNVKOCCWN7",
+        ),
+        non_utf8_memo,
+    ] {
+        assert_eq!(
+            resolver_client.try_set_record(&name, &com_tld, &payment_type, &data),
+            invalid
+        );
+    }
+
+    resolver_client.set_record(
+        &name,
+        &com_tld,
+        &payment_type,
+        &Bytes::from_slice(&env, account.as_bytes()),
+    );
+    assert_eq!(
+        resolver_client.resolve_name(&name, &com_tld),
+        Record::Payment(PaymentRecord {
+            destination: Address::from_str(&env, account),
+            muxed_id: None,
+            memo: Memo::None,
+        })
+    );
+}
+
+#[test]
+fn test_set_and_get_text_records() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let registry_id = env.register(
+        registry::WASM,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let resolver_id = env.register(Resolver, (&admin, &registry_id));
+
+    let resolver_client = ResolverClient::new(&env, &resolver_id);
+    let registry_client = registry::Client::new(&env, &registry_id);
+    let controller = Address::generate(&env);
+    registry_client.add_controller(&admin, &controller);
+
+    let name = Bytes::from_slice(&env, "test".as_bytes());
+    let owner = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET);
+    registry_client.set_resolver(&admin, &resolver_id);
+    registry_client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+
+    let avatar = Bytes::from_slice(&env, "avatar".as_bytes());
+    let twitter = Bytes::from_slice(&env, "com.twitter".as_bytes());
+    let avatar_url = Bytes::from_slice(&env, "https://example.com/me.png".as_bytes());
+    let handle = Bytes::from_slice(&env, "stellarorg".as_bytes());
+
+    resolver_client.set_text(&name, &com_tld, &avatar, &avatar_url);
+
+    assert_eq!(
+        get_events_by_contract_id(&env, &resolver_id),
+        vec![
+            &env,
+            (
+                resolver_id.clone(),
+                (Symbol::new(&env, "set_text"), EVENT_VERSION).into_val(&env),
+                SetTextEvent {
+                    owner: owner.clone(),
+                    name: name.clone(),
+                    tld: com_tld.clone(),
+                    key: avatar.clone(),
+                    value: avatar_url.clone(),
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+
+    resolver_client.set_text(&name, &com_tld, &twitter, &handle);
+
+    assert_eq!(resolver_client.text(&name, &com_tld, &avatar), avatar_url);
+    assert_eq!(resolver_client.text(&name, &com_tld, &twitter), handle);
+    assert_eq!(
+        resolver_client.text_keys(&name, &com_tld),
+        vec![&env, avatar.clone(), twitter.clone()]
+    );
+
+    resolver_client.set_text(&name, &com_tld, &avatar, &Bytes::new(&env));
+
+    assert_eq!(
+        resolver_client.text_keys(&name, &com_tld),
+        vec![&env, twitter.clone()]
+    );
+    assert!(resolver_client.try_text(&name, &com_tld, &avatar).is_err());
+    env.as_contract(&resolver_id, || {
+        let key = RecordKeys::Text(name.clone(), com_tld.clone(), twitter.clone());
+        assert!(env.storage().persistent().has(&key));
+        assert!(!env.storage().instance().has(&key));
+    });
+
+    // Text records are dropped when someone else registers the name after it
+    // expires.
+    let release_time = registry_client
+        .lookup_release_time(&name, &com_tld)
+        .unwrap();
+    env.ledger().set_timestamp(release_time + 1);
+    registry_client.controller_register(
+        &controller,
+        &name,
+        &com_tld,
+        &Address::generate(&env),
+        &ONE_YEAR_IN_SECONDS,
+    );
+    assert_eq!(
+        resolver_client.try_text(&name, &com_tld, &twitter),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::NameHasNoRecord as u32
+        )))
+    );
+    assert_eq!(resolver_client.text_keys(&name, &com_tld), vec![&env]);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #16)")]
+fn test_set_text_key_invalid() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let registry_id = env.register(
+        registry::WASM,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let resolver_id = env.register(Resolver, (&admin, &registry_id));
+
+    let resolver_client = ResolverClient::new(&env, &resolver_id);
+    let registry_client = registry::Client::new(&env, &registry_id);
+    let controller = Address::generate(&env);
+    registry_client.add_controller(&admin, &controller);
+
+    let name = Bytes::from_slice(&env, "test".as_bytes());
+    let owner = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET);
+    registry_client.set_resolver(&admin, &resolver_id);
+    registry_client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+
+    resolver_client.set_text(
+        &name,
+        &com_tld,
+        &Bytes::from_slice(&env, "My Key".as_bytes()),
+        &Bytes::from_slice(&env, "value".as_bytes()),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #17)")]
+fn test_set_text_value_too_long() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let registry_id = env.register(
+        registry::WASM,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let resolver_id = env.register(Resolver, (&admin, &registry_id));
+
+    let resolver_client = ResolverClient::new(&env, &resolver_id);
+    let registry_client = registry::Client::new(&env, &registry_id);
+    let controller = Address::generate(&env);
+    registry_client.add_controller(&admin, &controller);
+
+    let name = Bytes::from_slice(&env, "test".as_bytes());
+    let owner = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET);
+    registry_client.set_resolver(&admin, &resolver_id);
+    registry_client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+
+    resolver_client.set_text(
+        &name,
+        &com_tld,
+        &Bytes::from_slice(&env, "description".as_bytes()),
+        &Bytes::from_slice(&env, &[b'a'; 513]),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #18)")]
+fn test_set_text_too_many_keys() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let registry_id = env.register(
+        registry::WASM,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let resolver_id = env.register(Resolver, (&admin, &registry_id));
+
+    let resolver_client = ResolverClient::new(&env, &resolver_id);
+    let registry_client = registry::Client::new(&env, &registry_id);
+    let controller = Address::generate(&env);
+    registry_client.add_controller(&admin, &controller);
+
+    let name = Bytes::from_slice(&env, "test".as_bytes());
+    let owner = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET);
+    registry_client.set_resolver(&admin, &resolver_id);
+    registry_client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+
+    let value = Bytes::from_slice(&env, "value".as_bytes());
+    for i in 0..33u8 {
+        let key = Bytes::from_slice(&env, &[b'k', b'a' + i / 26, b'a' + i % 26]);
+        resolver_client.set_text(&name, &com_tld, &key, &value);
+    }
+}
+
+#[test]
+fn test_set_and_get_addresses() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let registry_id = env.register(
+        registry::WASM,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let resolver_id = env.register(Resolver, (&admin, &registry_id));
+
+    let resolver_client = ResolverClient::new(&env, &resolver_id);
+    let registry_client = registry::Client::new(&env, &registry_id);
+    let controller = Address::generate(&env);
+    registry_client.add_controller(&admin, &controller);
+
+    let name = Bytes::from_slice(&env, "test".as_bytes());
+    let owner = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET);
+    registry_client.set_resolver(&admin, &resolver_id);
+    registry_client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+
+    // P2PKH script of 1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2.
+    let btc = hex_to_bytes(&env, "76a91477bff20c60e522dfaa3350c39b030a5d004e839a88ac");
+    let eth = hex_to_bytes(&env, "d8da6bf26964af9d7eed9e03e53415d37aa96045");
+    resolver_client.set_addr(&name, &com_tld, &0, &btc);
+
+    assert_eq!(
+        get_events_by_contract_id(&env, &resolver_id),
+        vec![
+            &env,
+            (
+                resolver_id.clone(),
+                (Symbol::new(&env, "set_addr"), EVENT_VERSION).into_val(&env),
+                SetAddrEvent {
+                    owner: owner.clone(),
+                    name: name.clone(),
+                    tld: com_tld.clone(),
+                    coin_type: 0,
+                    addr: btc.clone(),
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+
+    resolver_client.set_addr(&name, &com_tld, &60, &eth);
+    // Polygon, chain id 137.
+    resolver_client.set_addr(&name, &com_tld, &(0x8000_0000 | 137), &eth);
+    // Segwit v0 and taproot outputs.
+    resolver_client.set_addr(
+        &name,
+        &com_tld,
+        &2,
+        &hex_to_bytes(&env, "0014751e76e8199196d454941c45d1b3a323f1433bd6"),
+    );
+    let taproot = hex_to_bytes(
+        &env,
+        "5120a60869f0dbcf1dc659c9cecbaf8050135ea9e8cdc487053f1dc6880949dc684c",
+    );
+    resolver_client.set_addr(&name, &com_tld, &3, &taproot);
+
+    assert_eq!(resolver_client.get_addr(&name, &com_tld, &0), btc);
+    assert_eq!(resolver_client.get_addr(&name, &com_tld, &60), eth);
+    assert_eq!(resolver_client.get_addr(&name, &com_tld, &3), taproot);
+    assert_eq!(
+        resolver_client.addr_coin_types(&name, &com_tld),
+        vec![&env, 0, 60, 0x8000_0000 | 137, 2, 3]
+    );
+
+    resolver_client.set_addr(&name, &com_tld, &60, &Bytes::new(&env));
+    assert!(resolver_client.try_get_addr(&name, &com_tld, &60).is_err());
+    assert_eq!(
+        resolver_client.addr_coin_types(&name, &com_tld),
+        vec![&env, 0, 0x8000_0000 | 137, 2, 3]
+    );
+
+    let invalid = Err(Ok(soroban_sdk::Error::from_contract_error(
+        Error::AddressInvalid as u32,
+    )));
+    for (coin_type, addr) in [
+        // Truncated P2PKH script.
+        (0, "76a91477bff20c60e522dfaa3350c39b030a5d004e839a88"),
+        // Segwit v0 program of the wrong length.
+        (0, "0015751e76e8199196d454941c45d1b3a323f1433bd600"),
+        // Not a script at all.
+        (0, "d8da6bf26964af9d7eed9e03e53415d37aa96045"),
+        // 19 byte Ethereum address.
+        (60, "d8da6bf26964af9d7eed9e03e53415d37aa960"),
+        (
+            0x8000_0000 | 137,
+            "d8da6bf26964af9d7eed9e03e53415d37aa9604500",
+        ),
+        (148, "d8da6bf26964af9d7eed9e03e53415d37aa96045"),
+    ] {
+        assert_eq!(
+            resolver_client.try_set_addr(&name, &com_tld, &coin_type, &hex_to_bytes(&env, addr)),
+            invalid
+        );
+    }
+
+    env.as_contract(&resolver_id, || {
+        let key = RecordKeys::Addr(name.clone(), com_tld.clone(), 0);
+        assert!(env.storage().persistent().has(&key));
+        assert!(!env.storage().instance().has(&key));
+    });
+
+    // Addresses are dropped when someone else registers the name after it
+    // expires.
+    let release_time = registry_client
+        .lookup_release_time(&name, &com_tld)
+        .unwrap();
+    env.ledger().set_timestamp(release_time + 1);
+    let new_owner = Address::generate(&env);
+    registry_client.controller_register(
+        &controller,
+        &name,
+        &com_tld,
+        &new_owner,
+        &ONE_YEAR_IN_SECONDS,
+    );
+    assert_eq!(
+        resolver_client.try_get_addr(&name, &com_tld, &0),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::NameHasNoRecord as u32
+        )))
+    );
+    assert_eq!(resolver_client.addr_coin_types(&name, &com_tld), vec![&env]);
+
+    resolver_client.set_addr(&name, &com_tld, &60, &eth);
+    assert_eq!(
+        resolver_client.addr_coin_types(&name, &com_tld),
+        vec![&env, 60]
+    );
+}
+
+#[test]
+fn test_verify_signature_with_published_keys() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let registry_id = env.register(
+        registry::WASM,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let resolver_id = env.register(Resolver, (&admin, &registry_id));
+
+    let resolver_client = ResolverClient::new(&env, &resolver_id);
+    let registry_client = registry::Client::new(&env, &registry_id);
+    let controller = Address::generate(&env);
+    registry_client.add_controller(&admin, &controller);
+
+    let name = Bytes::from_slice(&env, "test".as_bytes());
+    let owner = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET);
+    registry_client.set_resolver(&admin, &resolver_id);
+    registry_client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+
+    let message = Bytes::from_slice(&env, "login to example.com".as_bytes());
+    let login = Symbol::new(&env, "login");
+    let messaging = Symbol::new(&env, "messaging");
+
+    let ed25519_key = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
+    resolver_client.set_public_key(
+        &name,
+        &com_tld,
+        &login,
+        &KeyType::Ed25519,
+        &Bytes::from_slice(&env, ed25519_key.verifying_key().as_bytes()),
+    );
+    assert_eq!(
+        resolver_client.public_key(&name, &com_tld, &login).key_type,
+        KeyType::Ed25519
+    );
+
+    let mut message_slice = [0u8; 20];
+    message.copy_into_slice(&mut message_slice);
+    let signature_bytes = ed25519_dalek::Signer::sign(&ed25519_key, &message_slice).to_bytes();
+    resolver_client.verify_signature(
+        &name,
+        &com_tld,
+        &login,
+        &message,
+        &BytesN::from_array(&env, &signature_bytes),
+        &0,
+    );
+
+    let secp256k1_key = k256::ecdsa::SigningKey::from_slice(&[9u8; 32]).unwrap();
+    let public_key = secp256k1_key.verifying_key().to_encoded_point(false);
+    resolver_client.set_public_key(
+        &name,
+        &com_tld,
+        &messaging,
+        &KeyType::Secp256k1,
+        &Bytes::from_slice(&env, public_key.as_bytes()),
+    );
+
+    let digest = env.crypto().keccak256(&message).to_array();
+    let (signature, recovery_id) = secp256k1_key.sign_prehash_recoverable(&digest).unwrap();
+    let signature = BytesN::from_array(&env, &signature.to_bytes().into());
+    resolver_client.verify_signature(
+        &name,
+        &com_tld,
+        &messaging,
+        &message,
+        &signature,
+        &(recovery_id.to_byte() as u32),
+    );
+
+    // A signature over another message is rejected for both key types.
+    let other_message = Bytes::from_slice(&env, "login to evil.com".as_bytes());
+    assert!(resolver_client
+        .try_verify_signature(
+            &name,
+            &com_tld,
+            &login,
+            &other_message,
+            &BytesN::from_array(&env, &signature_bytes),
+            &0,
+        )
+        .is_err());
+    assert_eq!(
+        resolver_client.try_verify_signature(
+            &name,
+            &com_tld,
+            &messaging,
+            &other_message,
+            &signature,
+            &(recovery_id.to_byte() as u32),
+        ),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::SignatureInvalid as u32
+        )))
+    );
+    env.as_contract(&resolver_id, || {
+        let key = RecordKeys::PublicKey(name.clone(), com_tld.clone(), login.clone());
+        assert!(env.storage().persistent().has(&key));
+        assert!(!env.storage().instance().has(&key));
+    });
+
+    // Keys are dropped when the name changes hands, including when it expires
+    // and someone else registers it after the grace period.
+    let release_time = registry_client
+        .lookup_release_time(&name, &com_tld)
+        .unwrap();
+    env.ledger().set_timestamp(release_time + 1);
+    let new_owner = Address::generate(&env);
+    registry_client.controller_register(
+        &controller,
+        &name,
+        &com_tld,
+        &new_owner,
+        &ONE_YEAR_IN_SECONDS,
+    );
+    let no_record = soroban_sdk::Error::from_contract_error(Error::NameHasNoRecord as u32);
+    assert_eq!(
+        resolver_client.try_public_key(&name, &com_tld, &login),
+        Err(Ok(no_record))
+    );
+    assert_eq!(
+        resolver_client.try_verify_signature(
+            &name,
+            &com_tld,
+            &messaging,
+            &message,
+            &signature,
+            &(recovery_id.to_byte() as u32),
+        ),
+        Err(Ok(no_record))
+    );
+
+    let new_key = ed25519_dalek::SigningKey::from_bytes(&[8u8; 32]);
+    resolver_client.set_public_key(
+        &name,
+        &com_tld,
+        &login,
+        &KeyType::Ed25519,
+        &Bytes::from_slice(&env, new_key.verifying_key().as_bytes()),
+    );
+    assert_eq!(
+        resolver_client.public_key(&name, &com_tld, &login).key,
+        Bytes::from_slice(&env, new_key.verifying_key().as_bytes())
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #19)")]
+fn test_set_public_key_invalid_length() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let registry_id = env.register(
+        registry::WASM,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let resolver_id = env.register(Resolver, (&admin, &registry_id));
+
+    let resolver_client = ResolverClient::new(&env, &resolver_id);
+    let registry_client = registry::Client::new(&env, &registry_id);
+    let controller = Address::generate(&env);
+    registry_client.add_controller(&admin, &controller);
+
+    let name = Bytes::from_slice(&env, "test".as_bytes());
+    let owner = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET);
+    registry_client.set_resolver(&admin, &resolver_id);
+    registry_client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+
+    resolver_client.set_public_key(
+        &name,
+        &com_tld,
+        &Symbol::new(&env, "messaging"),
+        &KeyType::Ed25519,
+        &Bytes::from_slice(&env, &[1u8; 31]),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #21)")]
+fn test_verify_signature_with_encryption_key() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let registry_id = env.register(
+        registry::WASM,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let resolver_id = env.register(Resolver, (&admin, &registry_id));
+
+    let resolver_client = ResolverClient::new(&env, &resolver_id);
+    let registry_client = registry::Client::new(&env, &registry_id);
+    let controller = Address::generate(&env);
+    registry_client.add_controller(&admin, &controller);
+
+    let name = Bytes::from_slice(&env, "test".as_bytes());
+    let owner = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET);
+    registry_client.set_resolver(&admin, &resolver_id);
+    registry_client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+
+    let messaging = Symbol::new(&env, "messaging");
+    resolver_client.set_public_key(
+        &name,
+        &com_tld,
+        &messaging,
+        &KeyType::X25519,
+        &Bytes::from_slice(&env, &[1u8; 32]),
+    );
+    resolver_client.verify_signature(
+        &name,
+        &com_tld,
+        &messaging,
+        &Bytes::from_slice(&env, "hello".as_bytes()),
+        &BytesN::from_array(&env, &[0u8; 64]),
+        &0,
+    );
+}
+
+#[test]
+fn test_set_records_batch() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let registry_id = env.register(
+        registry::WASM,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let resolver_id = env.register(Resolver, (&admin, &registry_id));
+
+    let resolver_client = ResolverClient::new(&env, &resolver_id);
+    let registry_client = registry::Client::new(&env, &registry_id);
+    let controller = Address::generate(&env);
+    registry_client.add_controller(&admin, &controller);
+
+    let name = Bytes::from_slice(&env, "test".as_bytes());
+    let owner = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET);
+    registry_client.set_resolver(&admin, &resolver_id);
+    registry_client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+
+    let address_to_be_resolved = Address::generate(&env);
+    let url = Bytes::from_slice(&env, "url".as_bytes());
+    let email = Bytes::from_slice(&env, "email".as_bytes());
+    let messaging = Symbol::new(&env, "messaging");
+
+    resolver_client.set_records(
+        &name,
+        &com_tld,
+        &vec![
+            &env,
+            RecordUpdate::SetRecord(
+                Bytes::from_slice(&env, "name".as_bytes()),
+                address_to_bytes(&address_to_be_resolved),
+            ),
+            RecordUpdate::SetText(
+                url.clone(),
+                Bytes::from_slice(&env, "https://a.b".as_bytes()),
+            ),
+            RecordUpdate::SetText(email.clone(), Bytes::from_slice(&env, "a@b.c".as_bytes())),
+            RecordUpdate::SetPublicKey(
+                messaging.clone(),
+                KeyType::X25519,
+                Bytes::from_slice(&env, &[1u8; 32]),
+            ),
+        ],
+    );
+
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            owner.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    resolver_id.clone(),
+                    Symbol::new(&env, "set_records"),
+                    (
+                        name.clone(),
+                        com_tld.clone(),
+                        vec![
+                            &env,
+                            RecordUpdate::SetRecord(
+                                Bytes::from_slice(&env, "name".as_bytes()),
+                                address_to_bytes(&address_to_be_resolved),
+                            ),
+                            RecordUpdate::SetText(
+                                url.clone(),
+                                Bytes::from_slice(&env, "https://a.b".as_bytes())
+                            ),
+                            RecordUpdate::SetText(
+                                email.clone(),
+                                Bytes::from_slice(&env, "a@b.c".as_bytes())
+                            ),
+                            RecordUpdate::SetPublicKey(
+                                messaging.clone(),
+                                KeyType::X25519,
+                                Bytes::from_slice(&env, &[1u8; 32]),
+                            ),
+                        ],
+                    )
+                        .into_val(&env),
+                )),
+                sub_invocations: std::vec![],
+            }
+        )]
+    );
+    assert_eq!(get_events_by_contract_id(&env, &resolver_id).len(), 4);
+    assert_eq!(
+        resolver_client.resolve_name(&name, &com_tld),
+        Record::Name(address_to_be_resolved)
+    );
+    assert_eq!(
+        resolver_client.text_keys(&name, &com_tld),
+        vec![&env, url.clone(), email.clone()]
+    );
+
+    resolver_client.set_records(
+        &name,
+        &com_tld,
+        &vec![
+            &env,
+            RecordUpdate::DeleteRecord,
+            RecordUpdate::SetText(email.clone(), Bytes::new(&env)),
+            RecordUpdate::RemovePublicKey(messaging.clone()),
+        ],
+    );
+
+    assert_eq!(get_events_by_contract_id(&env, &resolver_id).len(), 3);
+    assert!(!resolver_client.is_name_has_record(&name, &com_tld));
+    assert_eq!(
+        resolver_client.text_keys(&name, &com_tld),
+        vec![&env, url.clone()]
+    );
+    assert!(resolver_client
+        .try_public_key(&name, &com_tld, &messaging)
+        .is_err());
+}
+
+#[test]
+fn test_set_records_batch_is_atomic() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let registry_id = env.register(
+        registry::WASM,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let resolver_id = env.register(Resolver, (&admin, &registry_id));
+
+    let resolver_client = ResolverClient::new(&env, &resolver_id);
+    let registry_client = registry::Client::new(&env, &registry_id);
+    let controller = Address::generate(&env);
+    registry_client.add_controller(&admin, &controller);
+
+    let name = Bytes::from_slice(&env, "test".as_bytes());
+    let owner = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET);
+    registry_client.set_resolver(&admin, &resolver_id);
+    registry_client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+
+    let url = Bytes::from_slice(&env, "url".as_bytes());
+    let result = resolver_client.try_set_records(
+        &name,
+        &com_tld,
+        &vec![
+            &env,
+            RecordUpdate::SetText(
+                url.clone(),
+                Bytes::from_slice(&env, "https://a.b".as_bytes()),
+            ),
+            RecordUpdate::SetRecord(
+                Bytes::from_slice(&env, "abi".as_bytes()),
+                Bytes::from_slice(&env, "data".as_bytes()),
+            ),
+        ],
+    );
+
+    assert_eq!(
+        result,
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::RecordTypeInvalid as u32
+        )))
+    );
+    assert_eq!(resolver_client.text_keys(&name, &com_tld), vec![&env]);
+}
+
+#[test]
+fn test_wildcard_record_precedence() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let registry_id = env.register(
+        registry::WASM,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let resolver_id = env.register(Resolver, (&admin, &registry_id));
+
+    let resolver_client = ResolverClient::new(&env, &resolver_id);
+    let registry_client = registry::Client::new(&env, &registry_id);
+    let controller = Address::generate(&env);
+    registry_client.add_controller(&admin, &controller);
+
+    let name = Bytes::from_slice(&env, "test".as_bytes());
+    let owner = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET);
+    registry_client.set_resolver(&admin, &resolver_id);
+    registry_client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+
+    let name_type = Bytes::from_slice(&env, "name".as_bytes());
+    let wildcard = Bytes::from_slice(&env, "*.test".as_bytes());
+    let alice = Bytes::from_slice(&env, "alice.test".as_bytes());
+    let bob = Bytes::from_slice(&env, "bob.test".as_bytes());
+    let hosting_contract = Address::generate(&env);
+    let alice_address = Address::generate(&env);
+
+    resolver_client.set_record(
+        &wildcard,
+        &com_tld,
+        &name_type,
+        &address_to_bytes(&hosting_contract),
+    );
+
+    assert_eq!(
+        resolver_client.resolve_name(&bob, &com_tld),
+        Record::Name(hosting_contract.clone())
+    );
+    assert_eq!(
+        resolver_client.resolve_name(&alice, &com_tld),
+        Record::Name(hosting_contract.clone())
+    );
+    assert_eq!(
+        resolver_client.try_resolve_name(&name, &com_tld),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::NameHasNoRecord as u32
+        )))
+    );
+
+    resolver_client.set_record(
+        &alice,
+        &com_tld,
+        &name_type,
+        &address_to_bytes(&alice_address),
+    );
+
+    assert_eq!(
+        resolver_client.resolve_name(&alice, &com_tld),
+        Record::Name(alice_address.clone())
+    );
+    assert_eq!(
+        resolver_client.resolve_name(&bob, &com_tld),
+        Record::Name(hosting_contract.clone())
+    );
+
+    resolver_client.delete_record(&wildcard, &com_tld);
+
+    assert_eq!(
+        resolver_client.resolve_name(&alice, &com_tld),
+        Record::Name(alice_address)
+    );
+    assert!(resolver_client.try_resolve_name(&bob, &com_tld).is_err());
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #3)")]
+fn test_wildcard_not_leftmost_label() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let registry_id = env.register(
+        registry::WASM,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let resolver_id = env.register(Resolver, (&admin, &registry_id));
+
+    let resolver_client = ResolverClient::new(&env, &resolver_id);
+    let registry_client = registry::Client::new(&env, &registry_id);
+    let controller = Address::generate(&env);
+    registry_client.add_controller(&admin, &controller);
+
+    let name = Bytes::from_slice(&env, "test".as_bytes());
+    let owner = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET);
+    registry_client.set_resolver(&admin, &resolver_id);
+    registry_client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+
+    resolver_client.set_record(
+        &Bytes::from_slice(&env, "a*.test".as_bytes()),
+        &com_tld,
+        &Bytes::from_slice(&env, "text".as_bytes()),
+        &Bytes::from_slice(&env, "hello".as_bytes()),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #4)")]
+fn test_wildcard_record_name_expired() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let registry_id = env.register(
+        registry::WASM,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let resolver_id = env.register(Resolver, (&admin, &registry_id));
+
+    let resolver_client = ResolverClient::new(&env, &resolver_id);
+    let registry_client = registry::Client::new(&env, &registry_id);
+    let controller = Address::generate(&env);
+    registry_client.add_controller(&admin, &controller);
+
+    let name = Bytes::from_slice(&env, "test".as_bytes());
+    let owner = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET);
+    registry_client.set_resolver(&admin, &resolver_id);
+    registry_client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+
+    resolver_client.set_record(
+        &Bytes::from_slice(&env, "*.test".as_bytes()),
+        &com_tld,
+        &Bytes::from_slice(&env, "text".as_bytes()),
+        &Bytes::from_slice(&env, "hello".as_bytes()),
+    );
+
+    env.ledger().set_timestamp(1000000000000000);
+    resolver_client.resolve_name(&Bytes::from_slice(&env, "bob.test".as_bytes()), &com_tld);
+}
+
+#[test]
+fn test_lookup_record() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let registry_id = env.register(
+        registry::WASM,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let resolver_id = env.register(Resolver, (&admin, &registry_id));
+
+    let resolver_client = ResolverClient::new(&env, &resolver_id);
+    let registry_client = registry::Client::new(&env, &registry_id);
+    let controller = Address::generate(&env);
+    registry_client.add_controller(&admin, &controller);
+
+    let name = Bytes::from_slice(&env, "test".as_bytes());
+    let unregistered = Bytes::from_slice(&env, "nobody".as_bytes());
+    let owner = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET);
+    registry_client.set_resolver(&admin, &resolver_id);
+    registry_client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+
+    assert_eq!(resolver_client.lookup_record(&unregistered, &com_tld), None);
+    assert_eq!(resolver_client.lookup_record(&name, &com_tld), None);
+
+    resolver_client.set_record(
+        &Bytes::from_slice(&env, "*.test".as_bytes()),
+        &com_tld,
+        &Bytes::from_slice(&env, "text".as_bytes()),
+        &Bytes::from_slice(&env, "hello".as_bytes()),
+    );
+    let bob = Bytes::from_slice(&env, "bob.test".as_bytes());
+    assert_eq!(
+        resolver_client.lookup_record(&bob, &com_tld),
+        Some(Record::Text(Bytes::from_slice(&env, "hello".as_bytes())))
+    );
+
+    env.ledger().set_timestamp(1000000000000000);
+    assert_eq!(resolver_client.lookup_record(&bob, &com_tld), None);
+}
+
+#[test]
+fn test_record_history() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let registry_id = env.register(
+        registry::WASM,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let resolver_id = env.register(Resolver, (&admin, &registry_id));
+
+    let resolver_client = ResolverClient::new(&env, &resolver_id);
+    let registry_client = registry::Client::new(&env, &registry_id);
+    let controller = Address::generate(&env);
+    registry_client.add_controller(&admin, &controller);
+
+    let name = Bytes::from_slice(&env, "test".as_bytes());
+    let owner = Address::generate(&env);
+    let text_type = Bytes::from_slice(&env, "text".as_bytes());
+
+    token_admin.mint(&owner, &MAX_ASSET);
+    registry_client.set_resolver(&admin, &resolver_id);
+    registry_client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+
+    assert_eq!(
+        resolver_client.try_get_record_meta(&name, &com_tld),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::NameHasNoRecord as u32
+        )))
+    );
+
+    for i in 1..=12u32 {
+        env.ledger().with_mut(|ledger| {
+            ledger.sequence_number = 100 + i;
+            ledger.timestamp = 1000 + i as u64;
+        });
+        let value = std::format!("value {}", i);
+        resolver_client.set_record(
+            &name,
+            &com_tld,
+            &text_type,
+            &Bytes::from_slice(&env, value.as_bytes()),
+        );
+    }
+
+    assert_eq!(
+        resolver_client.get_record_meta(&name, &com_tld),
+        RecordMeta {
+            version: 12,
+            ledger: 112,
+            timestamp: 1012,
+        }
+    );
+    assert_eq!(
+        resolver_client.resolve_name_at_version(&name, &com_tld, &12),
+        resolver_client.resolve_name(&name, &com_tld)
+    );
+    assert_eq!(
+        resolver_client.resolve_name_at_version(&name, &com_tld, &3),
+        Record::Text(Bytes::from_slice(&env, "value 3".as_bytes()))
+    );
+    assert_eq!(
+        resolver_client.get_record_version(&name, &com_tld, &3),
+        RecordVersion {
+            record: Record::Text(Bytes::from_slice(&env, "value 3".as_bytes())),
+            ledger: 103,
+            timestamp: 1003,
+        }
+    );
+    assert_eq!(
+        resolver_client.get_record_version(&name, &com_tld, &7),
+        RecordVersion {
+            record: Record::Text(Bytes::from_slice(&env, "value 7".as_bytes())),
+            ledger: 107,
+            timestamp: 1007,
+        }
+    );
+    assert_eq!(
+        resolver_client.try_resolve_name_at_version(&name, &com_tld, &2),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::RecordVersionUnavailable as u32
+        )))
+    );
+
+    resolver_client.delete_record(&name, &com_tld);
+    assert_eq!(resolver_client.get_record_meta(&name, &com_tld).version, 13);
+    assert_eq!(
+        resolver_client.resolve_name_at_version(&name, &com_tld, &13),
+        Record::NullRecord
+    );
+    assert_eq!(
+        resolver_client.resolve_name_at_version(&name, &com_tld, &12),
+        Record::Text(Bytes::from_slice(&env, "value 12".as_bytes()))
+    );
+    env.as_contract(&resolver_id, || {
+        let key = RecordKeys::RecordHistory(name.clone(), com_tld.clone(), 13);
+        assert!(env.storage().persistent().has(&key));
+        assert!(!env.storage().instance().has(&key));
+    });
+}
+
+#[test]
+fn test_migrate_from_v0() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let registry_id = env.register(
+        registry::WASM,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let resolver_id = env.register(Resolver, (&admin, &registry_id));
+
+    let resolver_client = ResolverClient::new(&env, &resolver_id);
+    let registry_client = registry::Client::new(&env, &registry_id);
+    let controller = Address::generate(&env);
+    registry_client.add_controller(&admin, &controller);
+    registry_client.set_resolver(&admin, &resolver_id);
+
+    let owner = Address::generate(&env);
+    let alice = Bytes::from_slice(&env, "alice".as_bytes());
+    let bob = Bytes::from_slice(&env, "bob".as_bytes());
+    let carol = Bytes::from_slice(&env, "carol".as_bytes());
+    let erin = Bytes::from_slice(&env, "erin".as_bytes());
+    let value = |text: &str| Record::Text(Bytes::from_slice(&env, text.as_bytes()));
+    for name in [&alice, &bob, &carol] {
+        registry_client.controller_register(
+            &controller,
+            name,
+            &com_tld,
+            &owner,
+            &ONE_YEAR_IN_SECONDS,
+        );
+    }
+    registry_client.controller_register(&controller, &erin, &com_tld, &owner, &1);
+    assert_eq!(resolver_client.schema_version(), 1);
+    assert_eq!(
+        resolver_client.try_migrate(&admin, &10),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::NoMigrationPending as u32
+        )))
+    );
+
+    // Lay out alice, bob and erin the way the first release did: records in
+    // instance storage and no name index.
+    env.as_contract(&resolver_id, || {
+        for name in [&alice, &bob, &erin] {
+            env.storage().instance().set(
+                &RecordKeys::Name(name.clone(), com_tld.clone()),
+                &value("one"),
+            );
+        }
+        env.storage().instance().remove(&SCHEMA_VERSION);
+    });
+    assert_eq!(resolver_client.schema_version(), 0);
+    assert_eq!(resolver_client.resolve_name(&alice, &com_tld), value("one"));
+    env.ledger().set_timestamp(env.ledger().timestamp() + 10);
+
+    // Names given a record after the upgrade are indexed as they go.
+    resolver_client.set_record(
+        &carol,
+        &com_tld,
+        &Bytes::from_slice(&env, "text".as_bytes()),
+        &Bytes::from_slice(&env, b"one"),
+    );
+    assert_eq!(
+        resolver_client.try_index_names(
+            &admin,
+            &vec![&env, (Bytes::from_slice(&env, b"dave"), com_tld.clone())]
+        ),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::NameHasNoRecord as u32
+        )))
+    );
+    resolver_client.index_names(
+        &admin,
+        &vec![
+            &env,
+            (alice.clone(), com_tld.clone()),
+            (bob.clone(), com_tld.clone()),
+            (erin.clone(), com_tld.clone()),
+        ],
+    );
+
+    assert!(!resolver_client.migrate(&admin, &2));
+    assert_eq!(resolver_client.schema_version(), 0);
+    assert!(resolver_client.migrate(&admin, &2));
+    assert_eq!(
+        get_events_by_contract_id(&env, &resolver_id),
+        vec![
+            &env,
+            (
+                resolver_id.clone(),
+                (Symbol::new(&env, "migrate"), EVENT_VERSION).into_val(&env),
+                MigrateEvent {
+                    admin: admin.clone(),
+                    from_version: 0,
+                    to_version: 1,
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+    assert_eq!(resolver_client.schema_version(), 1);
+
+    env.as_contract(&resolver_id, || {
+        for name in [&alice, &bob, &erin] {
+            let key = RecordKeys::Name(name.clone(), com_tld.clone());
+            assert!(!env.storage().instance().has(&key));
+            assert_eq!(env.storage().persistent().has(&key), *name != erin);
+        }
+        for index in 0..4 {
+            assert!(env.storage().persistent().has(&RecordKeys::NameAt(index)));
+            assert!(!env.storage().instance().has(&RecordKeys::NameAt(index)));
+        }
+    });
+    assert_eq!(resolver_client.resolve_name(&bob, &com_tld), value("one"));
+    // Migrated records are bound to the owner at the time of the migration.
+    registry_client.transfer(&alice, &com_tld, &Address::generate(&env));
+    assert_eq!(resolver_client.lookup_record(&alice, &com_tld), None);
+    assert_eq!(
+        resolver_client.try_index_names(&admin, &vec![&env, (alice.clone(), com_tld.clone())]),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::NoMigrationPending as u32
+        )))
+    );
+}
+
+// Stands in for an off-chain gateway: answers a lookup with `record`, signed
+// the way `resolve_offchain_response` expects.
+fn sign_gateway_response(
+    env: &Env,
+    key: &ed25519_dalek::SigningKey,
+    resolver_id: &Address,
+    callback_data: &Bytes,
+    record: &Record,
+    expires: u64,
+) -> BytesN<64> {
+    let message = (
+        resolver_id.clone(),
+        callback_data.clone(),
+        record.clone(),
+        expires,
+    )
+        .to_xdr(env);
+    let mut message_slice = std::vec![0u8; message.len() as usize];
+    message.copy_into_slice(&mut message_slice);
+    let signature = ed25519_dalek::Signer::sign(key, &message_slice);
+    BytesN::from_array(env, &signature.to_bytes())
+}
+
+#[test]
+fn test_offchain_lookup() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let registry_id = env.register(
+        registry::WASM,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let resolver_id = env.register(Resolver, (&admin, &registry_id));
+
+    let resolver_client = ResolverClient::new(&env, &resolver_id);
+    let registry_client = registry::Client::new(&env, &registry_id);
+    let controller = Address::generate(&env);
+    registry_client.add_controller(&admin, &controller);
+
+    let name = Bytes::from_slice(&env, "test".as_bytes());
+    let owner = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET);
+    registry_client.set_resolver(&admin, &resolver_id);
+    registry_client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+
+    let text = Bytes::from_slice(&env, "on-chain".as_bytes());
+    resolver_client.set_record(
+        &name,
+        &com_tld,
+        &Bytes::from_slice(&env, "text".as_bytes()),
+        &text,
+    );
+
+    let gateway_key = ed25519_dalek::SigningKey::from_bytes(&[3u8; 32]);
+    let urls = vec![
+        &env,
+        String::from_str(&env, "https://gateway.example.com/lookup"),
+    ];
+    resolver_client.set_offchain_resolver(
+        &name,
+        &com_tld,
+        &urls,
+        &BytesN::from_array(&env, gateway_key.verifying_key().as_bytes()),
+    );
+
+    let lookup = resolver_client.resolve_offchain(&name, &com_tld);
+    assert_eq!(lookup.urls, urls);
+    assert_eq!(
+        lookup.callback_data,
+        (name.clone(), com_tld.clone()).to_xdr(&env)
+    );
+    // Gateways take precedence over the on-chain record.
+    assert_eq!(
+        resolver_client.resolve_name(&name, &com_tld),
+        Record::Offchain(lookup.clone())
+    );
+    assert_eq!(
+        resolver_client.lookup_record(&name, &com_tld),
+        Some(Record::Offchain(lookup.clone()))
+    );
+
+    let record = Record::Text(Bytes::from_slice(&env, "large profile".as_bytes()));
+    let expires = env.ledger().timestamp() + 300;
+    let signature = sign_gateway_response(
+        &env,
+        &gateway_key,
+        &resolver_id,
+        &lookup.callback_data,
+        &record,
+        expires,
+    );
+    assert_eq!(
+        resolver_client.resolve_offchain_response(&name, &com_tld, &record, &expires, &signature),
+        record
+    );
+
+    let forged = Record::Text(Bytes::from_slice(&env, "forged".as_bytes()));
+    assert!(resolver_client
+        .try_resolve_offchain_response(&name, &com_tld, &forged, &expires, &signature)
+        .is_err());
+
+    env.ledger().set_timestamp(expires + 1);
+    assert_eq!(
+        resolver_client
+            .try_resolve_offchain_response(&name, &com_tld, &record, &expires, &signature),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::OffchainResponseExpired as u32
+        )))
+    );
+
+    resolver_client.remove_offchain_resolver(&name, &com_tld);
+    assert_eq!(
+        resolver_client.resolve_name(&name, &com_tld),
+        Record::Text(text)
+    );
+    assert_eq!(
+        resolver_client.try_resolve_offchain(&name, &com_tld),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::NameHasNoRecord as u32
+        )))
+    );
+
+    // The gateways stay with the owner that configured them.
+    resolver_client.set_offchain_resolver(
+        &name,
+        &com_tld,
+        &urls,
+        &BytesN::from_array(&env, gateway_key.verifying_key().as_bytes()),
+    );
+    env.as_contract(&resolver_id, || {
+        let key = RecordKeys::Offchain(name.clone(), com_tld.clone());
+        assert!(env.storage().persistent().has(&key));
+    });
+    registry_client.transfer(&name, &com_tld, &Address::generate(&env));
+    let no_record = soroban_sdk::Error::from_contract_error(Error::NameHasNoRecord as u32);
+    assert_eq!(
+        resolver_client.try_resolve_offchain(&name, &com_tld),
+        Err(Ok(no_record))
+    );
+    assert_eq!(
+        resolver_client.try_resolve_name(&name, &com_tld),
+        Err(Ok(no_record))
+    );
+    assert_eq!(
+        resolver_client.try_remove_offchain_resolver(&name, &com_tld),
+        Err(Ok(no_record))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #24)")]
+fn test_set_offchain_resolver_without_urls() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let registry_id = env.register(
+        registry::WASM,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let resolver_id = env.register(Resolver, (&admin, &registry_id));
+
+    let resolver_client = ResolverClient::new(&env, &resolver_id);
+    let registry_client = registry::Client::new(&env, &registry_id);
+    let controller = Address::generate(&env);
+    registry_client.add_controller(&admin, &controller);
+
+    let name = Bytes::from_slice(&env, "test".as_bytes());
+    let owner = Address::generate(&env);
+
+    token_admin.mint(&owner, &MAX_ASSET);
+    registry_client.set_resolver(&admin, &resolver_id);
+    registry_client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+
+    resolver_client.set_offchain_resolver(
+        &name,
+        &com_tld,
+        &Vec::new(&env),
+        &BytesN::from_array(&env, &[0u8; 32]),
+    );
+}
+
+#[test]
+fn test_admin_handover() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let registry_id = env.register(
+        registry::WASM,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let resolver_id = env.register(Resolver, (&admin, &registry_id));
+    let resolver_client = ResolverClient::new(&env, &resolver_id);
+
+    let new_admin = Address::generate(&env);
+    resolver_client.propose_admin(&new_admin);
+    assert_eq!(resolver_client.admin(), admin);
+    assert_eq!(resolver_client.pending_admin(), Some(new_admin.clone()));
+
+    resolver_client.accept_admin();
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            new_admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    resolver_id.clone(),
+                    Symbol::new(&env, "accept_admin"),
+                    ().into_val(&env),
+                )),
+                sub_invocations: std::vec![],
+            }
+        )]
+    );
+    assert_eq!(resolver_client.admin(), new_admin);
+    assert_eq!(
+        resolver_client.try_cancel_admin_proposal(),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::NoPendingAdmin as u32
+        )))
+    );
+}
+
+#[test]
+fn test_pause() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let registry_id = env.register(
+        registry::WASM,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let resolver_id = env.register(Resolver, (&admin, &registry_id));
+
+    let resolver_client = ResolverClient::new(&env, &resolver_id);
+    let registry_client = registry::Client::new(&env, &registry_id);
+    let controller = Address::generate(&env);
+    registry_client.add_controller(&admin, &controller);
+
+    let name = Bytes::from_slice(&env, "test".as_bytes());
+    let owner = Address::generate(&env);
+    let text_type = Bytes::from_slice(&env, "text".as_bytes());
+    let hello = Bytes::from_slice(&env, "hello".as_bytes());
+    let avatar = Bytes::from_slice(&env, "avatar".as_bytes());
+    let login = Symbol::new(&env, "login");
+
+    token_admin.mint(&owner, &MAX_ASSET);
+    registry_client.set_resolver(&admin, &resolver_id);
+    registry_client.controller_register(&controller, &name, &com_tld, &owner, &ONE_YEAR_IN_SECONDS);
+    resolver_client.set_record(&name, &com_tld, &text_type, &hello);
+    resolver_client.set_text(&name, &com_tld, &avatar, &hello);
+
+    resolver_client.pause(&admin);
+    assert_eq!(
+        get_events_by_contract_id(&env, &resolver_id),
+        vec![
+            &env,
+            (
+                resolver_id.clone(),
+                (symbol_short!("pause"), EVENT_VERSION).into_val(&env),
+                PauseEvent {
+                    pauser: admin.clone(),
+                }
+                .into_val(&env),
+            )
+        ]
+    );
+
+    let paused = Err(Ok(soroban_sdk::Error::from_contract_error(
+        Error::Paused as u32,
+    )));
+    assert_eq!(
+        resolver_client.try_set_record(&name, &com_tld, &text_type, &hello),
+        paused
+    );
+    assert_eq!(resolver_client.try_delete_record(&name, &com_tld), paused);
+    assert_eq!(
+        resolver_client.try_set_text(&name, &com_tld, &avatar, &hello),
+        paused
+    );
+    assert_eq!(
+        resolver_client.try_set_records(&name, &com_tld, &Vec::new(&env)),
+        paused
+    );
+    assert_eq!(
+        resolver_client.try_set_public_key(
+            &name,
+            &com_tld,
+            &login,
+            &KeyType::Ed25519,
+            &Bytes::from_array(&env, &[1u8; 32]),
+        ),
+        paused
+    );
+    assert_eq!(
+        resolver_client.try_remove_public_key(&name, &com_tld, &login),
+        paused
+    );
+    assert_eq!(
+        resolver_client.try_set_offchain_resolver(
+            &name,
+            &com_tld,
+            &vec![&env, String::from_str(&env, "https://gateway.example.com")],
+            &BytesN::from_array(&env, &[0u8; 32]),
+        ),
+        paused
+    );
+    assert_eq!(
+        resolver_client.try_remove_offchain_resolver(&name, &com_tld),
+        paused
+    );
+
+    assert_eq!(
+        resolver_client.resolve_name(&name, &com_tld),
+        Record::Text(hello.clone())
+    );
+    assert_eq!(resolver_client.text(&name, &com_tld, &avatar), hello);
+
+    resolver_client.unpause(&admin);
+    resolver_client.delete_record(&name, &com_tld);
+    assert!(!resolver_client.is_name_has_record(&name, &com_tld));
+}
+
+#[test]
+fn test_multisig_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (_, token_admin) = create_token_contract(&env, &admin);
+    let com_tld = Bytes::from_slice(&env, "com".as_bytes());
+    let registry_id = env.register(
+        registry::WASM,
+        (&admin, &token_admin.address, vec![&env, com_tld.clone()]),
+    );
+    let resolver_id = env.register(Resolver, (&admin, &registry_id));
+    let resolver_client = ResolverClient::new(&env, &resolver_id);
+
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    resolver_client.set_admin_signers(&vec![&env, first.clone(), second.clone()], &2);
+
+    let wasm_hash = env.deployer().upload_contract_wasm(registry::WASM);
+    let eta = env.ledger().timestamp() + MIN_UPGRADE_DELAY;
+    assert_eq!(
+        resolver_client.try_schedule_upgrade(&admin, &wasm_hash, &eta),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::NotAuthorized as u32
+        )))
+    );
+
+    let id = resolver_client.propose_admin_action(
+        &first,
+        &AdminAction::ScheduleUpgrade(wasm_hash.clone(), eta),
+    );
+    assert_eq!(
+        resolver_client.admin_action(&id).approvals,
+        vec![&env, first.clone()]
+    );
+    resolver_client.approve_admin_action(&second, &id);
+    resolver_client.execute_admin_action(&second, &id);
+    assert_eq!(
+        resolver_client.pending_upgrade(),
+        Some(ScheduledUpgrade {
+            new_wasm_hash: wasm_hash,
+            eta,
+        })
+    );
+
+    // The multisig holds every role, and pauses through admin actions.
+    assert!(resolver_client.has_role(&Role::Pauser, &resolver_id));
+    let pause = resolver_client.propose_admin_action(&first, &AdminAction::Pause);
+    resolver_client.approve_admin_action(&second, &pause);
+    resolver_client.execute_admin_action(&first, &pause);
+    assert!(resolver_client.is_paused());
+    let unpause = resolver_client.propose_admin_action(&second, &AdminAction::Unpause);
+    resolver_client.approve_admin_action(&first, &unpause);
+    resolver_client.execute_admin_action(&second, &unpause);
+    assert!(!resolver_client.is_paused());
+}
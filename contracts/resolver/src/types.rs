@@ -1,16 +1,176 @@
-use soroban_sdk::{contracttype, Address, Bytes};
-
+use access_control::Role;
+use soroban_sdk::{contracttype, Address, Bytes, BytesN, String, Symbol, Vec};
 
 #[contracttype]
 pub enum RecordKeys {
     Name(Bytes, Bytes),
+    // domain, tld, key
+    Text(Bytes, Bytes, Bytes),
+    TextKeys(Bytes, Bytes),
+    // domain, tld, purpose
+    PublicKey(Bytes, Bytes, Symbol),
+    RecordMeta(Bytes, Bytes),
+    // domain, tld, version
+    RecordHistory(Bytes, Bytes, u32),
+    Offchain(Bytes, Bytes),
+    AdminAction(u32),
+    // domain, tld, SLIP-44 coin type
+    Addr(Bytes, Bytes, u32),
+    AddrCoinTypes(Bytes, Bytes),
+    // position -> (domain, tld), every name with a record history, in
+    // persistent storage
+    NameAt(u32),
 }
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[allow(clippy::enum_variant_names)]
 pub enum Record {
     NullRecord,
     Name(Address),
+    ContentHash(ContentHash),
+    Text(Bytes),
+    Payment(PaymentRecord),
+    // Raw IPFS data written before `contenthash` records replaced it. Kept so
+    // those records still decode; nothing writes it anymore.
     Ipfs(Bytes),
+    // Returned instead of the on-chain record when the name resolves through
+    // gateways, see `offchain.rs`. Never stored.
+    Offchain(OffchainLookup),
+}
+
+// Bumped on every set or delete of a name record. Deletions are kept in the
+// history as `Record::NullRecord`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecordMeta {
+    pub version: u32,
+    pub ledger: u32,
+    pub timestamp: u64,
+}
+
+// A name record as it was at one version, and when that version was written.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecordVersion {
+    pub record: Record,
+    pub ledger: u32,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ContentCodec {
+    Ipfs,
+    Ipns,
+    Swarm,
+    Arweave,
+}
+
+// `hash` is the content hash without its namespace prefix: the binary CID for
+// IPFS, IPNS and Swarm, or the raw transaction id for Arweave.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContentHash {
+    pub codec: ContentCodec,
+    pub hash: Bytes,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Memo {
+    None,
+    Id(u64),
     Text(Bytes),
-}
\ No newline at end of file
+    Hash(BytesN<32>),
+}
+
+// Where payments to a name should go. Exchanges either hand out a muxed
+// account or ask for a memo next to their deposit address, never both.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentRecord {
+    pub destination: Address,
+    pub muxed_id: Option<u64>,
+    pub memo: Memo,
+}
+
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum KeyType {
+    Ed25519,
+    Secp256k1,
+    X25519,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PublicKey {
+    pub purpose: Symbol,
+    pub key_type: KeyType,
+    pub key: Bytes,
+}
+
+// One change inside a `set_records` batch.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RecordUpdate {
+    // record type, data
+    SetRecord(Bytes, Bytes),
+    DeleteRecord,
+    // key, value
+    SetText(Bytes, Bytes),
+    // purpose, key type, key
+    SetPublicKey(Symbol, KeyType, Bytes),
+    RemovePublicKey(Symbol),
+    // SLIP-44 coin type, address
+    SetAddr(u32, Bytes),
+}
+
+// Gateways serving the records of a name off-chain, and the ed25519 key they
+// sign their responses with.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OffchainConfig {
+    pub urls: Vec<String>,
+    pub signer: BytesN<32>,
+}
+
+// Returned instead of a record for names resolved off-chain. Clients pass
+// `callback_data` to one of the `urls` and hand the signed answer to
+// `resolve_offchain_response`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OffchainLookup {
+    pub urls: Vec<String>,
+    pub callback_data: Bytes,
+}
+
+// Admin entrypoints that the signers can run together once the admin is a
+// multisig.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AdminAction {
+    GrantRole(Role, Address),
+    RevokeRole(Role, Address),
+    Pause,
+    Unpause,
+    // signers, threshold
+    SetAdminSigners(Vec<Address>, u32),
+    ProposeAdmin(Address),
+    CancelAdminProposal,
+    // new wasm hash, eta
+    ScheduleUpgrade(BytesN<32>, u64),
+    CancelUpgrade,
+    ExecuteUpgrade,
+    IndexNames(Vec<(Bytes, Bytes)>),
+    // batch size
+    Migrate(u32),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminProposal {
+    pub action: AdminAction,
+    pub approvals: Vec<Address>,
+}
@@ -1,10 +1,13 @@
-use crate::{errors::Error, registry::DataKey};
-use soroban_sdk::{panic_with_error, Bytes, Env};
+use crate::{errors::Error, types::RecordKeys, NAME_COUNT};
+use soroban_sdk::{panic_with_error, Address, Bytes, Env, IntoVal, TryFromVal, Val};
 
 const DOT_IN_BYTE: u8 = 46;
+const WILDCARD_IN_BYTE: u8 = 42;
 
 pub trait BytesValidator {
     fn validate_name(&self, env: &Env, allow_subdomain: bool);
+    fn wildcard_name(&self, env: &Env) -> Option<Bytes>;
+    fn validate_text_key(&self, env: &Env, max_length: u32);
 }
 
 impl BytesValidator for Bytes {
@@ -27,9 +30,17 @@ impl BytesValidator for Bytes {
                 panic_with_error!(env, Error::NameInvalid);
             }
 
+            // `*.name` holds the records served for every subdomain of `name`
+            // that has none of its own.
+            let is_wildcard =
+                self.first_unchecked() == WILDCARD_IN_BYTE && self.get_unchecked(1) == DOT_IN_BYTE;
+
             let mut previous_char_is_dot = false;
             let mut dots_count = 0;
-            for byte in self.iter() {
+            for (index, byte) in self.iter().enumerate() {
+                if index == 0 && is_wildcard {
+                    continue;
+                }
                 if byte == DOT_IN_BYTE {
                     if previous_char_is_dot {
                         panic_with_error!(env, Error::NameInvalid);
@@ -50,11 +61,51 @@ impl BytesValidator for Bytes {
         }
     }
 
+    fn wildcard_name(&self, env: &Env) -> Option<Bytes> {
+        if self.first() == Some(WILDCARD_IN_BYTE) {
+            return None;
+        }
+        let mut wildcard: Bytes = Bytes::from_array(env, &[WILDCARD_IN_BYTE]);
+        let mut is_after_dot: bool = false;
+        for byte in self.iter() {
+            if byte == DOT_IN_BYTE {
+                is_after_dot = true;
+            }
+            if is_after_dot {
+                wildcard.push_back(byte);
+            }
+        }
+        if is_after_dot {
+            Some(wildcard)
+        } else {
+            None
+        }
+    }
+
+    fn validate_text_key(&self, env: &Env, max_length: u32) {
+        if self.is_empty() || self.len() > max_length {
+            panic_with_error!(env, Error::TextKeyInvalid);
+        }
+        for byte in self.iter() {
+            if !(byte.is_ascii_lowercase()
+                || byte.is_ascii_digit()
+                || byte == DOT_IN_BYTE
+                || byte == b'-'
+                || byte == b'_')
+            {
+                panic_with_error!(env, Error::TextKeyInvalid);
+            }
+        }
+    }
 }
 
 pub trait Base {
     fn extend_me(&self);
-    fn delete_name(&self, name: &Bytes, tld: &Bytes);
+    fn extend_persistent(&self, key: &RecordKeys);
+    fn get_owned<V: TryFromVal<Env, Val>>(&self, key: &RecordKeys, owner: &Address) -> Option<V>;
+    fn set_owned<V: IntoVal<Env, Val>>(&self, key: &RecordKeys, owner: &Address, value: V);
+    fn require_not_paused(&self);
+    fn index_name(&self, name: &Bytes, tld: &Bytes);
 }
 
 impl Base for Env {
@@ -62,9 +113,47 @@ impl Base for Env {
         self.storage().instance().extend_ttl(17280, 17280 * 30);
     }
 
-    fn delete_name(&self, name: &Bytes, tld: &Bytes) {
+    fn extend_persistent(&self, key: &RecordKeys) {
+        self.storage()
+            .persistent()
+            .extend_ttl(key, 17280, 17280 * 30);
+    }
+
+    // Records are stored next to the owner that wrote them and only served
+    // while that account still owns the name, so they do not carry over to
+    // whoever registers or buys the name next.
+    fn get_owned<V: TryFromVal<Env, Val>>(&self, key: &RecordKeys, owner: &Address) -> Option<V> {
+        let (writer, value): (Address, V) = self.storage().persistent().get(key)?;
+        self.extend_persistent(key);
+        if writer != *owner {
+            return None;
+        }
+        Some(value)
+    }
+
+    fn set_owned<V: IntoVal<Env, Val>>(&self, key: &RecordKeys, owner: &Address, value: V) {
+        let value: Val = value.into_val(self);
         self.storage()
-            .instance()
-            .remove(&DataKey::Name(name.clone(), tld.clone()));
+            .persistent()
+            .set(key, &(owner.clone(), value));
+        self.extend_persistent(key);
     }
-}
\ No newline at end of file
+
+    fn require_not_paused(&self) {
+        if access_control::is_paused(self) {
+            panic_with_error!(self, Error::Paused);
+        }
+    }
+
+    // The index grows with every name ever given a record, so its entries live
+    // in persistent storage and only the count is kept in the instance.
+    fn index_name(&self, name: &Bytes, tld: &Bytes) {
+        let count: u32 = self.storage().instance().get(&NAME_COUNT).unwrap_or(0);
+        let key = RecordKeys::NameAt(count);
+        self.storage()
+            .persistent()
+            .set(&key, &(name.clone(), tld.clone()));
+        self.extend_persistent(&key);
+        self.storage().instance().set(&NAME_COUNT, &(count + 1));
+    }
+}
@@ -0,0 +1,419 @@
+use crate::error::{Error, Result};
+use crate::name::Name;
+use crate::scval;
+use crate::types::{KeyType, Role};
+use stellar_xdr::curr::{InvokeContractArgs, ScAddress, ScVal};
+
+// Each method builds the arguments of one contract call. Read-only calls only
+// need simulating; calls that write must be signed by the address noted on
+// the method.
+
+fn contract_address(contract_id: &str) -> Result<ScAddress> {
+    match contract_id.parse() {
+        Ok(address @ ScAddress::Contract(_)) => Ok(address),
+        _ => Err(Error::InvalidAddress(contract_id.to_string())),
+    }
+}
+
+fn call(contract: &ScAddress, function: &str, args: Vec<ScVal>) -> Result<InvokeContractArgs> {
+    Ok(InvokeContractArgs {
+        contract_address: contract.clone(),
+        function_name: scval::symbol(function)?,
+        args: args.try_into()?,
+    })
+}
+
+// Calls on a name take `name` and `tld` first.
+fn invoke(
+    contract: &ScAddress,
+    function: &str,
+    name: &Name,
+    args: impl IntoIterator<Item = ScVal>,
+) -> Result<InvokeContractArgs> {
+    let mut all_args = name.args()?;
+    all_args.extend(args);
+    call(contract, function, all_args)
+}
+
+/// Admin calls shared by the contracts. Admin rotation and role grants are
+/// signed by the admin, and `accept_admin` by the proposed admin. Calls taking
+/// a `caller` are signed by it and need the role noted on the method; the
+/// admin holds every role.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Admin {
+    address: ScAddress,
+}
+
+impl Admin {
+    pub fn new(contract_id: &str) -> Result<Self> {
+        Ok(Self {
+            address: contract_address(contract_id)?,
+        })
+    }
+
+    /// Needs `Role::Pauser`.
+    pub fn pause(&self, caller: &str) -> Result<InvokeContractArgs> {
+        call(&self.address, "pause", vec![scval::address_arg(caller)?])
+    }
+
+    /// Needs `Role::Pauser`.
+    pub fn unpause(&self, caller: &str) -> Result<InvokeContractArgs> {
+        call(&self.address, "unpause", vec![scval::address_arg(caller)?])
+    }
+
+    pub fn has_role(&self, role: Role, account: &str) -> Result<InvokeContractArgs> {
+        call(
+            &self.address,
+            "has_role",
+            vec![role.arg()?, scval::address_arg(account)?],
+        )
+    }
+
+    pub fn grant_role(&self, role: Role, account: &str) -> Result<InvokeContractArgs> {
+        call(
+            &self.address,
+            "grant_role",
+            vec![role.arg()?, scval::address_arg(account)?],
+        )
+    }
+
+    pub fn revoke_role(&self, role: Role, account: &str) -> Result<InvokeContractArgs> {
+        call(
+            &self.address,
+            "revoke_role",
+            vec![role.arg()?, scval::address_arg(account)?],
+        )
+    }
+
+    /// Signed by `account`, which gives up `role`.
+    pub fn renounce_role(&self, account: &str, role: Role) -> Result<InvokeContractArgs> {
+        call(
+            &self.address,
+            "renounce_role",
+            vec![scval::address_arg(account)?, role.arg()?],
+        )
+    }
+
+    pub fn propose_admin(&self, new_admin: &str) -> Result<InvokeContractArgs> {
+        call(
+            &self.address,
+            "propose_admin",
+            vec![scval::address_arg(new_admin)?],
+        )
+    }
+
+    pub fn accept_admin(&self) -> Result<InvokeContractArgs> {
+        call(&self.address, "accept_admin", vec![])
+    }
+
+    pub fn cancel_admin_proposal(&self) -> Result<InvokeContractArgs> {
+        call(&self.address, "cancel_admin_proposal", vec![])
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Registry {
+    address: ScAddress,
+}
+
+impl Registry {
+    pub fn new(contract_id: &str) -> Result<Self> {
+        Ok(Self {
+            address: contract_address(contract_id)?,
+        })
+    }
+
+    pub fn get_name(&self, name: &Name) -> Result<InvokeContractArgs> {
+        invoke(&self.address, "get_name", name, [])
+    }
+
+    pub fn lookup_name(&self, name: &Name) -> Result<InvokeContractArgs> {
+        invoke(&self.address, "lookup_name", name, [])
+    }
+
+    pub fn lookup_owner(&self, name: &Name) -> Result<InvokeContractArgs> {
+        invoke(&self.address, "lookup_owner", name, [])
+    }
+
+    pub fn lookup_sell_offer(&self, name: &Name) -> Result<InvokeContractArgs> {
+        invoke(&self.address, "lookup_sell_offer", name, [])
+    }
+
+    pub fn is_name_expired(&self, name: &Name) -> Result<InvokeContractArgs> {
+        invoke(&self.address, "is_name_expired", name, [])
+    }
+
+    /// Up to `limit` of the names held by `owner`, starting at `cursor`.
+    pub fn names_of(&self, owner: &str, cursor: u32, limit: u32) -> Result<InvokeContractArgs> {
+        call(
+            &self.address,
+            "names_of",
+            vec![
+                scval::address_arg(owner)?,
+                ScVal::U32(cursor),
+                ScVal::U32(limit),
+            ],
+        )
+    }
+
+    /// Signed by the owner.
+    pub fn transfer(&self, name: &Name, new_owner: &str) -> Result<InvokeContractArgs> {
+        invoke(
+            &self.address,
+            "transfer",
+            name,
+            [scval::address_arg(new_owner)?],
+        )
+    }
+
+    /// Signed by `caller`, the owner or one of its operators.
+    pub fn set_name_resolver(
+        &self,
+        name: &Name,
+        caller: &str,
+        resolver: &str,
+    ) -> Result<InvokeContractArgs> {
+        invoke(
+            &self.address,
+            "set_name_resolver",
+            name,
+            [scval::address_arg(caller)?, scval::address_arg(resolver)?],
+        )
+    }
+
+    /// Signed by the owner.
+    pub fn make_sell_offer(&self, name: &Name, price: u64) -> Result<InvokeContractArgs> {
+        invoke(&self.address, "make_sell_offer", name, [ScVal::U64(price)])
+    }
+
+    /// Signed by the seller.
+    pub fn cancel_sell_offer(&self, name: &Name) -> Result<InvokeContractArgs> {
+        invoke(&self.address, "cancel_sell_offer", name, [])
+    }
+
+    /// Signed by `buyer`.
+    pub fn buy_name(&self, name: &Name, buyer: &str) -> Result<InvokeContractArgs> {
+        invoke(
+            &self.address,
+            "buy_name",
+            name,
+            [scval::address_arg(buyer)?],
+        )
+    }
+
+    /// Needs `Role::SuperAdmin`. The resolver given to newly registered names.
+    pub fn set_resolver(&self, caller: &str, resolver: &str) -> Result<InvokeContractArgs> {
+        call(
+            &self.address,
+            "set_resolver",
+            vec![scval::address_arg(caller)?, scval::address_arg(resolver)?],
+        )
+    }
+
+    /// Needs `Role::SuperAdmin`.
+    pub fn add_controller(&self, caller: &str, controller: &str) -> Result<InvokeContractArgs> {
+        call(
+            &self.address,
+            "add_controller",
+            vec![scval::address_arg(caller)?, scval::address_arg(controller)?],
+        )
+    }
+
+    /// Needs `Role::SuperAdmin`.
+    pub fn remove_controller(&self, caller: &str, controller: &str) -> Result<InvokeContractArgs> {
+        call(
+            &self.address,
+            "remove_controller",
+            vec![scval::address_arg(caller)?, scval::address_arg(controller)?],
+        )
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Resolver {
+    address: ScAddress,
+}
+
+impl Resolver {
+    pub fn new(contract_id: &str) -> Result<Self> {
+        Ok(Self {
+            address: contract_address(contract_id)?,
+        })
+    }
+
+    pub fn resolve_name(&self, name: &Name) -> Result<InvokeContractArgs> {
+        invoke(&self.address, "resolve_name", name, [])
+    }
+
+    pub fn lookup_record(&self, name: &Name) -> Result<InvokeContractArgs> {
+        invoke(&self.address, "lookup_record", name, [])
+    }
+
+    /// Checks a gateway's answer for `name` and returns the record in it.
+    /// `record` is passed on as the gateway encoded it.
+    pub fn resolve_offchain_response(
+        &self,
+        name: &Name,
+        record: ScVal,
+        expires: u64,
+        signature: &[u8; 64],
+    ) -> Result<InvokeContractArgs> {
+        invoke(
+            &self.address,
+            "resolve_offchain_response",
+            name,
+            [record, ScVal::U64(expires), scval::bytes(signature)?],
+        )
+    }
+
+    pub fn text(&self, name: &Name, key: &str) -> Result<InvokeContractArgs> {
+        invoke(&self.address, "text", name, [scval::bytes(key.as_bytes())?])
+    }
+
+    /// The address of `name` on the chain with SLIP-44 `coin_type`, in the
+    /// chain's binary form.
+    pub fn get_addr(&self, name: &Name, coin_type: u32) -> Result<InvokeContractArgs> {
+        invoke(&self.address, "get_addr", name, [ScVal::U32(coin_type)])
+    }
+
+    /// Signed by the owner. `data` is in the format the resolver expects for
+    /// `record_type`, e.g. a strkey for `name` records.
+    pub fn set_record(
+        &self,
+        name: &Name,
+        record_type: &str,
+        data: &[u8],
+    ) -> Result<InvokeContractArgs> {
+        invoke(
+            &self.address,
+            "set_record",
+            name,
+            [scval::bytes(record_type.as_bytes())?, scval::bytes(data)?],
+        )
+    }
+
+    /// Signed by the owner.
+    pub fn delete_record(&self, name: &Name) -> Result<InvokeContractArgs> {
+        invoke(&self.address, "delete_record", name, [])
+    }
+
+    /// Signed by the owner. An empty `addr` removes the address.
+    pub fn set_addr(&self, name: &Name, coin_type: u32, addr: &[u8]) -> Result<InvokeContractArgs> {
+        invoke(
+            &self.address,
+            "set_addr",
+            name,
+            [ScVal::U32(coin_type), scval::bytes(addr)?],
+        )
+    }
+
+    /// Signed by the owner. An empty `value` removes the key.
+    pub fn set_text(&self, name: &Name, key: &str, value: &[u8]) -> Result<InvokeContractArgs> {
+        invoke(
+            &self.address,
+            "set_text",
+            name,
+            [scval::bytes(key.as_bytes())?, scval::bytes(value)?],
+        )
+    }
+
+    /// Signed by the owner. Publishes `key` for `purpose`, e.g. `messaging`,
+    /// replacing the key held for it.
+    pub fn set_public_key(
+        &self,
+        name: &Name,
+        purpose: &str,
+        key_type: KeyType,
+        key: &[u8],
+    ) -> Result<InvokeContractArgs> {
+        invoke(
+            &self.address,
+            "set_public_key",
+            name,
+            [
+                ScVal::Symbol(scval::symbol(purpose)?),
+                key_type.arg()?,
+                scval::bytes(key)?,
+            ],
+        )
+    }
+
+    /// Signed by the owner.
+    pub fn remove_public_key(&self, name: &Name, purpose: &str) -> Result<InvokeContractArgs> {
+        invoke(
+            &self.address,
+            "remove_public_key",
+            name,
+            [ScVal::Symbol(scval::symbol(purpose)?)],
+        )
+    }
+
+    /// Signed by the owner. Hands the name's records to the gateways at
+    /// `urls`, whose answers must be signed with the ed25519 key `signer`.
+    pub fn set_offchain_resolver(
+        &self,
+        name: &Name,
+        urls: &[String],
+        signer: &[u8; 32],
+    ) -> Result<InvokeContractArgs> {
+        invoke(
+            &self.address,
+            "set_offchain_resolver",
+            name,
+            [scval::string_vec(urls)?, scval::bytes(signer)?],
+        )
+    }
+
+    /// Signed by the owner.
+    pub fn remove_offchain_resolver(&self, name: &Name) -> Result<InvokeContractArgs> {
+        invoke(&self.address, "remove_offchain_resolver", name, [])
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RegistrarController {
+    address: ScAddress,
+}
+
+impl RegistrarController {
+    pub fn new(contract_id: &str) -> Result<Self> {
+        Ok(Self {
+            address: contract_address(contract_id)?,
+        })
+    }
+
+    /// Signed by `owner`, who pays the fee.
+    pub fn register_name(
+        &self,
+        name: &Name,
+        owner: &str,
+        number_of_years: u64,
+        referrer: Option<&str>,
+    ) -> Result<InvokeContractArgs> {
+        invoke(
+            &self.address,
+            "register_name",
+            name,
+            [
+                scval::address_arg(owner)?,
+                ScVal::U64(number_of_years),
+                referrer.map_or(Ok(ScVal::Void), scval::address_arg)?,
+            ],
+        )
+    }
+
+    /// Signed by `payer`.
+    pub fn renew_name(
+        &self,
+        name: &Name,
+        payer: &str,
+        number_of_years: u64,
+    ) -> Result<InvokeContractArgs> {
+        invoke(
+            &self.address,
+            "renew_name",
+            name,
+            [scval::address_arg(payer)?, ScVal::U64(number_of_years)],
+        )
+    }
+}
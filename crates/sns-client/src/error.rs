@@ -0,0 +1,105 @@
+use core::fmt;
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("invalid name `{0}`")]
+    InvalidName(String),
+    #[error("invalid address `{0}`")]
+    InvalidAddress(String),
+    #[error("invalid role `{0}`")]
+    InvalidRole(String),
+    #[error("invalid key type `{0}`")]
+    InvalidKeyType(String),
+    #[error("unexpected value, expected {0}")]
+    UnexpectedValue(&'static str),
+    #[error("contract error: {0}")]
+    Contract(ContractError),
+    #[error("unknown contract error code {0}")]
+    UnknownContractError(u32),
+    #[error(transparent)]
+    Xdr(#[from] stellar_xdr::curr::Error),
+}
+
+macro_rules! contract_errors {
+    ($($variant:ident = $code:literal,)*) => {
+        /// Errors raised by the contracts. The registry, resolver and
+        /// registrar controller share one list of codes, but each contract
+        /// only declares the errors it raises.
+        #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord, Hash)]
+        #[repr(u32)]
+        pub enum ContractError {
+            $($variant = $code,)*
+        }
+
+        impl ContractError {
+            pub fn from_code(code: u32) -> Option<Self> {
+                match code {
+                    $($code => Some(Self::$variant),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+contract_errors! {
+    NameAlreadyRegistered = 1,
+    NameNotRegistered = 2,
+    NameInvalid = 3,
+    NameExpired = 4,
+    NameHasNoRecord = 5,
+    ResolverAlreadySet = 6,
+    TLDNotSupported = 7,
+    NoOffer = 8,
+    RecordTypeInvalid = 9,
+    NotAuthorized = 10,
+    IncorrectOwner = 11,
+    InvalidLiveUntilLedger = 12,
+    NameNotExpired = 13,
+    PaymentRecordInvalid = 14,
+    ContentHashInvalid = 15,
+    TextKeyInvalid = 16,
+    TextValueTooLong = 17,
+    TooManyTextRecords = 18,
+    PublicKeyInvalid = 19,
+    SignatureInvalid = 20,
+    KeyTypeNotForSigning = 21,
+    RecordUpdatesInvalid = 22,
+    RecordVersionUnavailable = 23,
+    OffchainConfigInvalid = 24,
+    OffchainResponseExpired = 25,
+    NoPendingAdmin = 26,
+    UpgradeEtaTooEarly = 27,
+    UpgradeNotReady = 28,
+    NoPendingUpgrade = 29,
+    Paused = 30,
+    NoMigrationPending = 31,
+    AdminActionNotFound = 32,
+    NotEnoughApprovals = 33,
+    AlreadyApproved = 34,
+    AdminSignersInvalid = 35,
+    PriceInvalid = 36,
+    NameReserved = 37,
+    NameNotReserved = 38,
+    RegistrationPeriodInvalid = 39,
+    RegistrationClosed = 40,
+    NotAllowlisted = 41,
+    LaunchScheduleInvalid = 42,
+    ReferralShareInvalid = 43,
+    ReferrerInvalid = 44,
+    NoReferralRewards = 45,
+    TokenUriTooLong = 46,
+    AddressInvalid = 47,
+    TooManyAddresses = 48,
+    UpgradePending = 49,
+    RoleNotGranted = 50,
+    WithdrawalTooLarge = 51,
+}
+
+impl fmt::Display for ContractError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} (#{})", self, *self as u32)
+    }
+}
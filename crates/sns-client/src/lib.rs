@@ -0,0 +1,25 @@
+//! Off-chain client for the SNS registry, resolver and registrar controller.
+//!
+//! It builds the `InvokeContractArgs` and transactions for contract calls and
+//! decodes the values the contracts return, so integrators do not have to
+//! hand-encode `Bytes` arguments or walk `ScVal` maps. Submitting transactions
+//! (simulation, signing, sending) is left to the caller's RPC client.
+mod contracts;
+mod error;
+mod name;
+mod scval;
+mod transaction;
+mod types;
+
+pub use contracts::{Admin, RegistrarController, Registry, Resolver};
+pub use error::{ContractError, Error, Result};
+pub use name::Name;
+pub use scval::{decode, decode_option, decode_vec, FromScVal};
+pub use stellar_xdr::curr as xdr;
+pub use transaction::{invoke_transaction, transaction_hash, unsigned_envelope};
+pub use types::{
+    ContentCodec, ContentHash, Domain, KeyType, Memo, OffchainLookup, Offer, PaymentRecord, Record,
+    Role,
+};
+
+mod test;
@@ -0,0 +1,74 @@
+use crate::error::{Error, Result};
+use crate::scval;
+use core::fmt;
+use core::str::FromStr;
+use stellar_xdr::curr::ScVal;
+
+/// A name split into the `name` and `tld` arguments every contract takes,
+/// e.g. `pay.alice.xlm` is `pay.alice` under `xlm`.
+///
+/// Parsing applies the registry's rules: lowercase ASCII letters only, at most
+/// one level of subdomain, and both the root label (`alice` above) and the
+/// whole `name` 3 to 24 characters long. The subdomain may be `*`, as in
+/// `*.alice.xlm`, which holds the records the resolver serves for any
+/// subdomain of `alice.xlm` without records of its own.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Name {
+    pub name: String,
+    pub tld: String,
+}
+
+impl Name {
+    pub fn parse(value: &str) -> Result<Self> {
+        let invalid = || Error::InvalidName(value.to_string());
+        let (name, tld) = value.rsplit_once('.').ok_or_else(invalid)?;
+        let is_label =
+            |label: &str| !label.is_empty() && label.bytes().all(|byte| byte.is_ascii_lowercase());
+        let labels = name.strip_prefix("*.").unwrap_or(name);
+        if !is_label(tld) || !labels.split('.').all(is_label) || name.matches('.').count() > 1 {
+            return Err(invalid());
+        }
+        let root = name.rsplit('.').next().unwrap();
+        if !(3..=24).contains(&root.len()) || !(3..=24).contains(&name.len()) {
+            return Err(invalid());
+        }
+        Ok(Self {
+            name: name.to_string(),
+            tld: tld.to_string(),
+        })
+    }
+
+    /// The name without its subdomains, which is what the registry stores.
+    pub fn root(&self) -> Self {
+        Self {
+            name: self.name.rsplit('.').next().unwrap().to_string(),
+            tld: self.tld.clone(),
+        }
+    }
+
+    pub fn is_subdomain(&self) -> bool {
+        self.name.contains('.')
+    }
+
+    /// `name` and `tld` encoded as the leading `Bytes` arguments of a call.
+    pub fn args(&self) -> Result<Vec<ScVal>> {
+        Ok(vec![
+            scval::bytes(self.name.as_bytes())?,
+            scval::bytes(self.tld.as_bytes())?,
+        ])
+    }
+}
+
+impl FromStr for Name {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        Self::parse(value)
+    }
+}
+
+impl fmt::Display for Name {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.name, self.tld)
+    }
+}
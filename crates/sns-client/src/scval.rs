@@ -0,0 +1,204 @@
+use crate::error::{ContractError, Error, Result};
+use stellar_xdr::curr::{
+    Limits, ReadXdr, ScAddress, ScBytes, ScError, ScMap, ScString, ScSymbol, ScVal, ScVec,
+};
+
+/// Types the contracts return, decoded from their `ScVal` encoding.
+pub trait FromScVal: Sized {
+    fn from_scval(val: &ScVal) -> Result<Self>;
+}
+
+/// Decodes a base64 `ScVal`, e.g. the return value of a simulated call. A
+/// contract error comes back as `Error::Contract`.
+pub fn decode<T: FromScVal>(xdr: &str) -> Result<T> {
+    let val = ScVal::from_xdr_base64(xdr, Limits::none())?;
+    if let ScVal::Error(ScError::Contract(code)) = val {
+        return Err(ContractError::from_code(code)
+            .map_or(Error::UnknownContractError(code), Error::Contract));
+    }
+    T::from_scval(&val)
+}
+
+/// Like `decode`, for the `lookup_*` functions that return an `Option`.
+pub fn decode_option<T: FromScVal>(xdr: &str) -> Result<Option<T>> {
+    decode::<Optional<T>>(xdr).map(|Optional(value)| value)
+}
+
+struct Optional<T>(Option<T>);
+
+impl<T: FromScVal> FromScVal for Optional<T> {
+    fn from_scval(val: &ScVal) -> Result<Self> {
+        Ok(Self(option(val)?))
+    }
+}
+
+/// Decodes a base64 `ScVal` holding a vec, e.g. the result of `names_of`.
+pub fn decode_vec<T: FromScVal>(xdr: &str) -> Result<Vec<T>> {
+    decode::<Items<T>>(xdr).map(|Items(items)| items)
+}
+
+struct Items<T>(Vec<T>);
+
+impl<T: FromScVal> FromScVal for Items<T> {
+    fn from_scval(val: &ScVal) -> Result<Self> {
+        match val {
+            ScVal::Vec(Some(ScVec(items))) => items
+                .iter()
+                .map(T::from_scval)
+                .collect::<Result<_>>()
+                .map(Self),
+            _ => Err(Error::UnexpectedValue("vec")),
+        }
+    }
+}
+
+impl FromScVal for ScAddress {
+    fn from_scval(val: &ScVal) -> Result<Self> {
+        match val {
+            ScVal::Address(address) => Ok(address.clone()),
+            _ => Err(Error::UnexpectedValue("address")),
+        }
+    }
+}
+
+impl FromScVal for Vec<u8> {
+    fn from_scval(val: &ScVal) -> Result<Self> {
+        match val {
+            ScVal::Bytes(bytes) => Ok(bytes.to_vec()),
+            _ => Err(Error::UnexpectedValue("bytes")),
+        }
+    }
+}
+
+impl FromScVal for String {
+    fn from_scval(val: &ScVal) -> Result<Self> {
+        match val {
+            ScVal::String(value) => {
+                String::from_utf8(value.to_vec()).map_err(|_| Error::UnexpectedValue("string"))
+            }
+            _ => Err(Error::UnexpectedValue("string")),
+        }
+    }
+}
+
+impl FromScVal for bool {
+    fn from_scval(val: &ScVal) -> Result<Self> {
+        match val {
+            ScVal::Bool(value) => Ok(*value),
+            _ => Err(Error::UnexpectedValue("bool")),
+        }
+    }
+}
+
+impl FromScVal for u32 {
+    fn from_scval(val: &ScVal) -> Result<Self> {
+        match val {
+            ScVal::U32(value) => Ok(*value),
+            _ => Err(Error::UnexpectedValue("u32")),
+        }
+    }
+}
+
+impl FromScVal for u64 {
+    fn from_scval(val: &ScVal) -> Result<Self> {
+        match val {
+            ScVal::U64(value) => Ok(*value),
+            _ => Err(Error::UnexpectedValue("u64")),
+        }
+    }
+}
+
+impl<A: FromScVal, B: FromScVal> FromScVal for (A, B) {
+    fn from_scval(val: &ScVal) -> Result<Self> {
+        match val {
+            ScVal::Vec(Some(ScVec(items))) if items.len() == 2 => {
+                Ok((A::from_scval(&items[0])?, B::from_scval(&items[1])?))
+            }
+            _ => Err(Error::UnexpectedValue("tuple")),
+        }
+    }
+}
+
+pub(crate) fn option<T: FromScVal>(val: &ScVal) -> Result<Option<T>> {
+    match val {
+        ScVal::Void => Ok(None),
+        _ => T::from_scval(val).map(Some),
+    }
+}
+
+pub(crate) fn vec<T: FromScVal>(val: &ScVal) -> Result<Vec<T>> {
+    Items::from_scval(val).map(|Items(items)| items)
+}
+
+pub(crate) fn address(val: &ScVal) -> Result<String> {
+    ScAddress::from_scval(val).map(|address| address.to_string())
+}
+
+// Fields of a `#[contracttype]` struct, which is encoded as a map keyed by
+// field name.
+pub(crate) struct Fields<'a>(&'a ScMap);
+
+impl<'a> Fields<'a> {
+    pub(crate) fn new(val: &'a ScVal, what: &'static str) -> Result<Self> {
+        match val {
+            ScVal::Map(Some(map)) => Ok(Self(map)),
+            _ => Err(Error::UnexpectedValue(what)),
+        }
+    }
+
+    pub(crate) fn get(&self, key: &'static str) -> Result<&'a ScVal> {
+        self.find(key).ok_or(Error::UnexpectedValue(key))
+    }
+
+    pub(crate) fn find(&self, key: &str) -> Option<&'a ScVal> {
+        self.0.iter().find_map(|entry| match &entry.key {
+            ScVal::Symbol(symbol) if symbol.as_slice() == key.as_bytes() => Some(&entry.val),
+            _ => None,
+        })
+    }
+}
+
+// A `#[contracttype]` enum variant, encoded as a vec holding the variant name
+// followed by its fields.
+pub(crate) fn variant<'a>(val: &'a ScVal, what: &'static str) -> Result<(&'a str, &'a [ScVal])> {
+    let ScVal::Vec(Some(ScVec(items))) = val else {
+        return Err(Error::UnexpectedValue(what));
+    };
+    match items.split_first() {
+        Some((ScVal::Symbol(name), fields)) => Ok((
+            core::str::from_utf8(name.as_slice()).map_err(|_| Error::UnexpectedValue(what))?,
+            fields,
+        )),
+        _ => Err(Error::UnexpectedValue(what)),
+    }
+}
+
+// Encodes a variant without fields, the inverse of `variant`.
+pub(crate) fn unit_variant(name: &str) -> Result<ScVal> {
+    Ok(ScVal::Vec(Some(ScVec(
+        vec![ScVal::Symbol(symbol(name)?)].try_into()?,
+    ))))
+}
+
+pub(crate) fn bytes(value: &[u8]) -> Result<ScVal> {
+    Ok(ScVal::Bytes(ScBytes(value.to_vec().try_into()?)))
+}
+
+pub(crate) fn symbol(value: &str) -> Result<ScSymbol> {
+    Ok(ScSymbol(value.try_into()?))
+}
+
+pub(crate) fn string_vec(values: &[String]) -> Result<ScVal> {
+    let items = values
+        .iter()
+        .map(|value| Ok(ScVal::String(ScString(value.as_bytes().try_into()?))))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(ScVal::Vec(Some(ScVec(items.try_into()?))))
+}
+
+pub(crate) fn address_arg(value: &str) -> Result<ScVal> {
+    value
+        .parse()
+        .map(ScVal::Address)
+        .map_err(|_| Error::InvalidAddress(value.to_string()))
+}
@@ -0,0 +1,321 @@
+#![cfg(test)]
+
+use super::*;
+use xdr::{Hash, Limits, OperationBody, ReadXdr, ScAddress, ScVal, TransactionEnvelope, WriteXdr};
+
+// The fixtures are return values encoded by the contracts themselves. Their
+// addresses are the test contract ids 0...01 and 0...02.
+fn test_address(last_byte: u8) -> String {
+    let mut id = [0; 32];
+    id[31] = last_byte;
+    ScAddress::Contract(Hash(id)).to_string()
+}
+
+const ACCOUNT: &str = "GAAZI4TCR3TY5OJHCTJC2A4QSY6CJWJH5IAJTGKIN2ER7LBNVKOCCWN7";
+
+#[test]
+fn test_name_parse() {
+    let name = Name::parse("alice.xlm").unwrap();
+    assert_eq!(name.name, "alice");
+    assert_eq!(name.tld, "xlm");
+    assert!(!name.is_subdomain());
+    assert_eq!(name.to_string(), "alice.xlm");
+
+    let subdomain: Name = "pay.alice.xlm".parse().unwrap();
+    assert_eq!(subdomain.name, "pay.alice");
+    assert!(subdomain.is_subdomain());
+    assert_eq!(subdomain.root(), name);
+    assert!(Name::parse("a.abcdefghijklmnopqrstuv.xlm").is_ok());
+
+    let wildcard = Name::parse("*.alice.xlm").unwrap();
+    assert_eq!(wildcard.name, "*.alice");
+    assert!(wildcard.is_subdomain());
+    assert_eq!(wildcard.root(), name);
+
+    for invalid in [
+        "alice",
+        "Alice.xlm",
+        "al.xlm",
+        "alice.",
+        ".xlm",
+        "pay..alice.xlm",
+        "al1ce.xlm",
+        "abcdefghijklmnopqrstuvwxy.xlm",
+        "a.b.alice.xlm",
+        "payments.abcdefghijklmnop.xlm",
+        "*.xlm",
+        "*.*.xlm",
+        "*.pay.alice.xlm",
+        "pay.*.xlm",
+        "p*y.alice.xlm",
+    ] {
+        assert!(
+            matches!(Name::parse(invalid), Err(Error::InvalidName(_))),
+            "{invalid}"
+        );
+    }
+}
+
+#[test]
+fn test_decode_domain() {
+    let domain: Domain = decode(include_str!("../testdata/domain.xdr").trim()).unwrap();
+    assert_eq!(
+        domain,
+        Domain {
+            owner: test_address(1),
+            resolver: test_address(2),
+            expiry: 1_700_000_000,
+        }
+    );
+
+    let lookup: Option<Domain> =
+        decode_option(include_str!("../testdata/domain.xdr").trim()).unwrap();
+    assert_eq!(lookup, Some(domain));
+    let void = ScVal::Void.to_xdr_base64(Limits::none()).unwrap();
+    assert_eq!(decode_option::<Domain>(&void).unwrap(), None);
+}
+
+#[test]
+fn test_decode_offer() {
+    let offer: Offer = decode(include_str!("../testdata/offer.xdr").trim()).unwrap();
+    assert_eq!(
+        offer,
+        Offer {
+            seller: test_address(1),
+            name: b"alice".to_vec(),
+            tld: b"xlm".to_vec(),
+            price: 250,
+        }
+    );
+}
+
+#[test]
+fn test_decode_records() {
+    let record = |xdr: &str| decode::<Record>(xdr.trim()).unwrap();
+    assert_eq!(
+        record(include_str!("../testdata/record_null.xdr")),
+        Record::NullRecord
+    );
+    assert_eq!(
+        record(include_str!("../testdata/record_name.xdr")),
+        Record::Name(test_address(1))
+    );
+    assert_eq!(
+        record(include_str!("../testdata/record_text.xdr")),
+        Record::Text(b"hello".to_vec())
+    );
+    assert_eq!(
+        record(include_str!("../testdata/record_content_hash.xdr")),
+        Record::ContentHash(ContentHash {
+            codec: ContentCodec::Ipfs,
+            hash: vec![1, 112, 18, 32],
+        })
+    );
+    assert_eq!(
+        record(include_str!("../testdata/record_payment.xdr")),
+        Record::Payment(PaymentRecord {
+            destination: test_address(1),
+            muxed_id: None,
+            memo: Memo::Id(42),
+        })
+    );
+    assert_eq!(
+        record(include_str!("../testdata/record_payment_hash.xdr")),
+        Record::Payment(PaymentRecord {
+            destination: test_address(1),
+            muxed_id: Some(7),
+            memo: Memo::Hash([9; 32]),
+        })
+    );
+    assert_eq!(
+        record(include_str!("../testdata/record_ipfs.xdr")),
+        Record::Ipfs(b"QmRAQB6YaCyidP37UdDnjFY5vQuiBrcqdyoW1CuDgwxkD4".to_vec())
+    );
+    assert_eq!(
+        record(include_str!("../testdata/record_offchain.xdr")),
+        Record::Offchain(OffchainLookup {
+            urls: vec!["https://gateway.example.com/lookup".to_string()],
+            callback_data: vec![1, 2, 3],
+        })
+    );
+
+    // A value of another type is rejected rather than misread.
+    assert!(matches!(
+        decode::<Record>(include_str!("../testdata/domain.xdr").trim()),
+        Err(Error::UnexpectedValue("Record"))
+    ));
+}
+
+#[test]
+fn test_decode_contract_error() {
+    assert!(matches!(
+        decode::<Domain>(include_str!("../testdata/error.xdr").trim()),
+        Err(Error::Contract(ContractError::NameNotRegistered))
+    ));
+    assert_eq!(
+        ContractError::from_code(51),
+        Some(ContractError::WithdrawalTooLarge)
+    );
+    assert_eq!(ContractError::from_code(52), None);
+    assert_eq!(
+        ContractError::NotAuthorized.to_string(),
+        "NotAuthorized (#10)"
+    );
+}
+
+#[test]
+fn test_invoke_args() {
+    let contract_id = test_address(3);
+    let name = Name::parse("alice.xlm").unwrap();
+
+    assert!(matches!(
+        Registry::new(ACCOUNT),
+        Err(Error::InvalidAddress(_))
+    ));
+
+    let args = Registry::new(&contract_id)
+        .unwrap()
+        .transfer(&name, ACCOUNT)
+        .unwrap();
+    assert_eq!(args.contract_address.to_string(), contract_id);
+    assert_eq!(args.function_name.0.as_slice(), b"transfer");
+    assert_eq!(
+        args.args.to_vec(),
+        vec![
+            ScVal::Bytes(b"alice".to_vec().try_into().unwrap()),
+            ScVal::Bytes(b"xlm".to_vec().try_into().unwrap()),
+            ScVal::Address(ACCOUNT.parse().unwrap()),
+        ]
+    );
+
+    let controller = RegistrarController::new(&contract_id).unwrap();
+    let args = controller.register_name(&name, ACCOUNT, 2, None).unwrap();
+    assert_eq!(args.function_name.0.as_slice(), b"register_name");
+    assert_eq!(args.args[3], ScVal::U64(2));
+    assert_eq!(args.args[4], ScVal::Void);
+    let args = controller
+        .register_name(&name, ACCOUNT, 2, Some(&contract_id))
+        .unwrap();
+    assert_eq!(args.args[4], ScVal::Address(contract_id.parse().unwrap()));
+    assert!(matches!(
+        controller.register_name(&name, "not-an-address", 2, None),
+        Err(Error::InvalidAddress(_))
+    ));
+
+    let args = Resolver::new(&contract_id)
+        .unwrap()
+        .set_text(&name, "url", b"https://example.com")
+        .unwrap();
+    assert_eq!(args.function_name.0.as_slice(), b"set_text");
+    assert_eq!(args.args.len(), 4);
+
+    let resolver = Resolver::new(&contract_id).unwrap();
+    let args = resolver
+        .set_public_key(&name, "messaging", KeyType::X25519, &[7; 32])
+        .unwrap();
+    assert_eq!(args.function_name.0.as_slice(), b"set_public_key");
+    assert_eq!(args.args[2], ScVal::Symbol("messaging".try_into().unwrap()));
+    assert_eq!(
+        args.args[3],
+        ScVal::Vec(Some(
+            vec![ScVal::Symbol("X25519".try_into().unwrap())]
+                .try_into()
+                .unwrap()
+        ))
+    );
+    assert_eq!("secp256k1".parse::<KeyType>().unwrap(), KeyType::Secp256k1);
+    assert!(matches!(
+        "rsa".parse::<KeyType>(),
+        Err(Error::InvalidKeyType(_))
+    ));
+
+    let urls = ["https://gateway.example.com".to_string()];
+    let args = resolver
+        .set_offchain_resolver(&name, &urls, &[9; 32])
+        .unwrap();
+    assert_eq!(args.function_name.0.as_slice(), b"set_offchain_resolver");
+    assert_eq!(
+        args.args[2],
+        ScVal::Vec(Some(
+            vec![ScVal::String(xdr::ScString(
+                "https://gateway.example.com".try_into().unwrap()
+            ))]
+            .try_into()
+            .unwrap()
+        ))
+    );
+    assert_eq!(args.args[3], ScVal::Bytes(vec![9; 32].try_into().unwrap()));
+
+    let args = resolver
+        .resolve_offchain_response(&name, ScVal::Void, 100, &[1; 64])
+        .unwrap();
+    assert_eq!(
+        args.function_name.0.as_slice(),
+        b"resolve_offchain_response"
+    );
+    assert_eq!(args.args[2..4], [ScVal::Void, ScVal::U64(100)]);
+
+    let admin = Admin::new(&contract_id).unwrap();
+    let args = admin
+        .grant_role(Role::PricingManager, &contract_id)
+        .unwrap();
+    assert_eq!(args.function_name.0.as_slice(), b"grant_role");
+    assert_eq!(
+        args.args[0],
+        ScVal::Vec(Some(
+            vec![ScVal::Symbol("PricingManager".try_into().unwrap())]
+                .try_into()
+                .unwrap()
+        ))
+    );
+    let args = admin.renounce_role(ACCOUNT, Role::Pauser).unwrap();
+    assert_eq!(args.args[0], ScVal::Address(ACCOUNT.parse().unwrap()));
+    assert_eq!(
+        decode::<Role>(&args.args[1].to_xdr_base64(Limits::none()).unwrap()).unwrap(),
+        Role::Pauser
+    );
+    assert_eq!("tld-manager".parse::<Role>().unwrap(), Role::TldManager);
+    assert!(matches!(
+        "TldManager".parse::<Role>(),
+        Err(Error::InvalidRole(_))
+    ));
+}
+
+#[test]
+fn test_invoke_transaction() {
+    let name = Name::parse("alice.xlm").unwrap();
+    let invoke = Resolver::new(&test_address(3))
+        .unwrap()
+        .resolve_name(&name)
+        .unwrap();
+    let transaction = invoke_transaction(ACCOUNT, 41, 100, invoke.clone()).unwrap();
+    assert_eq!(transaction.seq_num.0, 42);
+    assert_eq!(transaction.source_account.to_string(), ACCOUNT);
+    assert_eq!(
+        transaction.operations[0].body,
+        OperationBody::InvokeHostFunction(xdr::InvokeHostFunctionOp {
+            host_function: xdr::HostFunction::InvokeContract(invoke.clone()),
+            auth: Default::default(),
+        })
+    );
+
+    let envelope = unsigned_envelope(transaction.clone())
+        .to_xdr_base64(Limits::none())
+        .unwrap();
+    assert_eq!(
+        TransactionEnvelope::from_xdr_base64(&envelope, Limits::none()).unwrap(),
+        unsigned_envelope(transaction.clone())
+    );
+
+    let testnet = transaction_hash(&transaction, "Test SDF Network ; September 2015").unwrap();
+    let mainnet = transaction_hash(
+        &transaction,
+        "Public Global Stellar Network ; September 2015",
+    )
+    .unwrap();
+    assert_ne!(testnet, mainnet);
+    assert!(matches!(
+        invoke_transaction("not-an-account", 41, 100, invoke),
+        Err(Error::InvalidAddress(_))
+    ));
+}
@@ -0,0 +1,59 @@
+use crate::error::{Error, Result};
+use sha2::{Digest, Sha256};
+use stellar_xdr::curr::{
+    Hash, HostFunction, InvokeContractArgs, InvokeHostFunctionOp, Limits, Memo, MuxedAccount,
+    Operation, OperationBody, Preconditions, SequenceNumber, Transaction, TransactionEnvelope,
+    TransactionExt, TransactionSignaturePayload, TransactionSignaturePayloadTaggedTransaction,
+    TransactionV1Envelope, WriteXdr,
+};
+
+/// Builds a transaction making one contract call from `source_account`.
+///
+/// `account_sequence` is the account's current sequence number; the
+/// transaction uses the next one. The result still needs `simulateTransaction`
+/// to fill in its Soroban data, auth entries and resource fee before it is
+/// signed.
+pub fn invoke_transaction(
+    source_account: &str,
+    account_sequence: i64,
+    fee: u32,
+    invoke: InvokeContractArgs,
+) -> Result<Transaction> {
+    let source_account: MuxedAccount = source_account
+        .parse()
+        .map_err(|_| Error::InvalidAddress(source_account.to_string()))?;
+    let operation = Operation {
+        source_account: None,
+        body: OperationBody::InvokeHostFunction(InvokeHostFunctionOp {
+            host_function: HostFunction::InvokeContract(invoke),
+            auth: Default::default(),
+        }),
+    };
+    Ok(Transaction {
+        source_account,
+        fee,
+        seq_num: SequenceNumber(account_sequence + 1),
+        cond: Preconditions::None,
+        memo: Memo::None,
+        operations: vec![operation].try_into()?,
+        ext: TransactionExt::V0,
+    })
+}
+
+/// The hash the source account signs, for the network with
+/// `network_passphrase`.
+pub fn transaction_hash(transaction: &Transaction, network_passphrase: &str) -> Result<[u8; 32]> {
+    let payload = TransactionSignaturePayload {
+        network_id: Hash(Sha256::digest(network_passphrase).into()),
+        tagged_transaction: TransactionSignaturePayloadTaggedTransaction::Tx(transaction.clone()),
+    };
+    Ok(Sha256::digest(payload.to_xdr(Limits::none())?).into())
+}
+
+/// Wraps a transaction for `simulateTransaction`, which takes it unsigned.
+pub fn unsigned_envelope(transaction: Transaction) -> TransactionEnvelope {
+    TransactionEnvelope::Tx(TransactionV1Envelope {
+        tx: transaction,
+        signatures: Default::default(),
+    })
+}
@@ -0,0 +1,261 @@
+use crate::error::{Error, Result};
+use crate::scval::{self, Fields, FromScVal};
+use core::str::FromStr;
+use stellar_xdr::curr::ScVal;
+
+// Addresses are kept as strkeys (`G...` accounts, `C...` contracts).
+
+/// A registered name, as returned by the registry's `get_name`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Domain {
+    pub owner: String,
+    pub resolver: String,
+    pub expiry: u64,
+}
+
+impl FromScVal for Domain {
+    fn from_scval(val: &ScVal) -> Result<Self> {
+        let fields = Fields::new(val, "Domain")?;
+        Ok(Self {
+            owner: scval::address(fields.get("owner")?)?,
+            resolver: scval::address(fields.get("resolver")?)?,
+            expiry: u64::from_scval(fields.get("expiry")?)?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Offer {
+    pub seller: String,
+    pub name: Vec<u8>,
+    pub tld: Vec<u8>,
+    pub price: u64,
+}
+
+impl FromScVal for Offer {
+    fn from_scval(val: &ScVal) -> Result<Self> {
+        let fields = Fields::new(val, "Offer")?;
+        Ok(Self {
+            seller: scval::address(fields.get("seller")?)?,
+            name: Vec::from_scval(fields.get("name")?)?,
+            tld: Vec::from_scval(fields.get("tld")?)?,
+            price: u64::from_scval(fields.get("price")?)?,
+        })
+    }
+}
+
+/// A name record, as returned by the resolver's `resolve_name`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Record {
+    NullRecord,
+    Name(String),
+    ContentHash(ContentHash),
+    Text(Vec<u8>),
+    Payment(PaymentRecord),
+    /// Raw IPFS data from before `contenthash` records, never written now.
+    Ipfs(Vec<u8>),
+    /// The name is served by gateways; ask them and check the answer with
+    /// the resolver's `resolve_offchain_response`.
+    Offchain(OffchainLookup),
+}
+
+impl FromScVal for Record {
+    fn from_scval(val: &ScVal) -> Result<Self> {
+        match scval::variant(val, "Record")? {
+            ("NullRecord", []) => Ok(Self::NullRecord),
+            ("Name", [address]) => Ok(Self::Name(scval::address(address)?)),
+            ("ContentHash", [hash]) => Ok(Self::ContentHash(ContentHash::from_scval(hash)?)),
+            ("Text", [text]) => Ok(Self::Text(Vec::from_scval(text)?)),
+            ("Payment", [payment]) => Ok(Self::Payment(PaymentRecord::from_scval(payment)?)),
+            ("Ipfs", [data]) => Ok(Self::Ipfs(Vec::from_scval(data)?)),
+            ("Offchain", [lookup]) => Ok(Self::Offchain(OffchainLookup::from_scval(lookup)?)),
+            _ => Err(Error::UnexpectedValue("Record")),
+        }
+    }
+}
+
+/// Where to look a name up off-chain, as returned by `resolve_offchain`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OffchainLookup {
+    pub urls: Vec<String>,
+    pub callback_data: Vec<u8>,
+}
+
+impl FromScVal for OffchainLookup {
+    fn from_scval(val: &ScVal) -> Result<Self> {
+        let fields = Fields::new(val, "OffchainLookup")?;
+        Ok(Self {
+            urls: scval::vec(fields.get("urls")?)?,
+            callback_data: Vec::from_scval(fields.get("callback_data")?)?,
+        })
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ContentCodec {
+    Ipfs,
+    Ipns,
+    Swarm,
+    Arweave,
+}
+
+impl FromScVal for ContentCodec {
+    fn from_scval(val: &ScVal) -> Result<Self> {
+        match scval::variant(val, "ContentCodec")? {
+            ("Ipfs", []) => Ok(Self::Ipfs),
+            ("Ipns", []) => Ok(Self::Ipns),
+            ("Swarm", []) => Ok(Self::Swarm),
+            ("Arweave", []) => Ok(Self::Arweave),
+            _ => Err(Error::UnexpectedValue("ContentCodec")),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContentHash {
+    pub codec: ContentCodec,
+    pub hash: Vec<u8>,
+}
+
+impl FromScVal for ContentHash {
+    fn from_scval(val: &ScVal) -> Result<Self> {
+        let fields = Fields::new(val, "ContentHash")?;
+        Ok(Self {
+            codec: ContentCodec::from_scval(fields.get("codec")?)?,
+            hash: Vec::from_scval(fields.get("hash")?)?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Memo {
+    None,
+    Id(u64),
+    Text(Vec<u8>),
+    Hash([u8; 32]),
+}
+
+impl FromScVal for Memo {
+    fn from_scval(val: &ScVal) -> Result<Self> {
+        match scval::variant(val, "Memo")? {
+            ("None", []) => Ok(Self::None),
+            ("Id", [id]) => Ok(Self::Id(u64::from_scval(id)?)),
+            ("Text", [text]) => Ok(Self::Text(Vec::from_scval(text)?)),
+            ("Hash", [hash]) => Vec::from_scval(hash)?
+                .try_into()
+                .map(Self::Hash)
+                .map_err(|_| Error::UnexpectedValue("Memo")),
+            _ => Err(Error::UnexpectedValue("Memo")),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentRecord {
+    pub destination: String,
+    pub muxed_id: Option<u64>,
+    pub memo: Memo,
+}
+
+impl FromScVal for PaymentRecord {
+    fn from_scval(val: &ScVal) -> Result<Self> {
+        let fields = Fields::new(val, "PaymentRecord")?;
+        Ok(Self {
+            destination: scval::address(fields.get("destination")?)?,
+            muxed_id: scval::option(fields.get("muxed_id")?)?,
+            memo: Memo::from_scval(fields.get("memo")?)?,
+        })
+    }
+}
+
+/// A permission on the contracts, granted by the admin. The admin and a super
+/// admin hold every role.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Role {
+    SuperAdmin,
+    PricingManager,
+    TldManager,
+    Treasurer,
+    Pauser,
+    Upgrader,
+}
+
+impl Role {
+    const ALL: [(Self, &'static str, &'static str); 6] = [
+        (Self::SuperAdmin, "SuperAdmin", "super-admin"),
+        (Self::PricingManager, "PricingManager", "pricing-manager"),
+        (Self::TldManager, "TldManager", "tld-manager"),
+        (Self::Treasurer, "Treasurer", "treasurer"),
+        (Self::Pauser, "Pauser", "pauser"),
+        (Self::Upgrader, "Upgrader", "upgrader"),
+    ];
+
+    pub(crate) fn arg(self) -> Result<ScVal> {
+        let (_, variant, _) = Self::ALL.iter().find(|(role, ..)| *role == self).unwrap();
+        scval::unit_variant(variant)
+    }
+}
+
+impl FromScVal for Role {
+    fn from_scval(val: &ScVal) -> Result<Self> {
+        match scval::variant(val, "Role")? {
+            (name, []) => Self::ALL
+                .iter()
+                .find(|(_, variant, _)| *variant == name)
+                .map(|(role, ..)| *role)
+                .ok_or(Error::UnexpectedValue("Role")),
+            _ => Err(Error::UnexpectedValue("Role")),
+        }
+    }
+}
+
+/// Parses the kebab-case name, e.g. `pricing-manager`.
+impl FromStr for Role {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        Self::ALL
+            .iter()
+            .find(|(.., name)| *name == value)
+            .map(|(role, ..)| *role)
+            .ok_or_else(|| Error::InvalidRole(value.to_string()))
+    }
+}
+
+/// How a key published with `set_public_key` is used: ed25519 and secp256k1
+/// keys verify signatures, x25519 keys are for key agreement.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum KeyType {
+    Ed25519,
+    Secp256k1,
+    X25519,
+}
+
+impl KeyType {
+    const ALL: [(Self, &'static str, &'static str); 3] = [
+        (Self::Ed25519, "Ed25519", "ed25519"),
+        (Self::Secp256k1, "Secp256k1", "secp256k1"),
+        (Self::X25519, "X25519", "x25519"),
+    ];
+
+    pub(crate) fn arg(self) -> Result<ScVal> {
+        let (_, variant, _) = Self::ALL
+            .iter()
+            .find(|(key_type, ..)| *key_type == self)
+            .unwrap();
+        scval::unit_variant(variant)
+    }
+}
+
+/// Parses the lowercase name, e.g. `secp256k1`.
+impl FromStr for KeyType {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        Self::ALL
+            .iter()
+            .find(|(.., name)| *name == value)
+            .map(|(key_type, ..)| *key_type)
+            .ok_or_else(|| Error::InvalidKeyType(value.to_string()))
+    }
+}
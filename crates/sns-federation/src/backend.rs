@@ -0,0 +1,151 @@
+use anyhow::{anyhow, bail, Context, Result};
+use base64::Engine;
+use serde_json::{json, Value};
+use sns_client::xdr::{InvokeContractArgs, Limits, ReadXdr, ScVal, WriteXdr};
+use sns_client::{
+    decode, decode_option, decode_vec, invoke_transaction, unsigned_envelope, Name, OffchainLookup,
+    Record, Registry, Resolver,
+};
+
+/// Where the server reads names from.
+pub trait Backend {
+    /// The record of `name`, or `None` when the name is not registered, has
+    /// expired or has no record.
+    fn record(&self, name: &Name) -> Result<Option<Record>>;
+
+    /// The names owned by `account`.
+    fn names_of(&self, account: &str) -> Result<Vec<Name>>;
+
+    /// The record the gateways of `name` serve, once the resolver has checked
+    /// their signature.
+    fn offchain_record(&self, name: &Name, lookup: &OffchainLookup) -> Result<Record>;
+}
+
+// Calls are simulated from this account; reads need no real source.
+const READ_SOURCE: &str = "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF";
+
+// Page size used to walk `names_of`.
+const PAGE_SIZE: u32 = 50;
+
+/// Reads the contracts by simulating calls on a Soroban RPC server.
+pub struct RpcBackend {
+    url: String,
+    registry: Registry,
+    resolver: Resolver,
+    resolver_id: String,
+}
+
+impl RpcBackend {
+    pub fn new(url: &str, registry: &str, resolver: &str) -> Result<Self> {
+        Ok(Self {
+            url: url.to_string(),
+            registry: Registry::new(registry)?,
+            resolver: Resolver::new(resolver)?,
+            resolver_id: resolver.to_string(),
+        })
+    }
+
+    // Gateways are asked with a POST of
+    //
+    //   {"sender": <resolver id>, "data": <base64 callback_data>}
+    //
+    // and answer with the fields of `resolve_offchain_response`:
+    //
+    //   {"record": <base64 Record>, "expires": <unix time>, "signature": <base64>}
+    fn ask_gateway(&self, url: &str, name: &Name, lookup: &OffchainLookup) -> Result<Record> {
+        let base64 = base64::engine::general_purpose::STANDARD;
+        let answer: Value = ureq::post(url)
+            .send_json(json!({
+                "sender": self.resolver_id,
+                "data": base64.encode(&lookup.callback_data),
+            }))?
+            .into_json()?;
+        let field = |key: &str| {
+            answer[key]
+                .as_str()
+                .ok_or_else(|| anyhow!("the answer has no `{key}`"))
+        };
+        let record = ScVal::from_xdr_base64(field("record")?, Limits::none())?;
+        let expires = answer["expires"]
+            .as_u64()
+            .ok_or_else(|| anyhow!("the answer has no `expires`"))?;
+        let signature: [u8; 64] = base64
+            .decode(field("signature")?)?
+            .try_into()
+            .map_err(|_| anyhow!("the signature is not 64 bytes"))?;
+        let xdr = self.simulate(
+            self.resolver
+                .resolve_offchain_response(name, record, expires, &signature)?,
+        )?;
+        Ok(decode(&xdr)?)
+    }
+
+    // Returns the base64 result of a read-only call.
+    fn simulate(&self, invoke: InvokeContractArgs) -> Result<String> {
+        let transaction = invoke_transaction(READ_SOURCE, 0, 100, invoke)?;
+        let response: Value = ureq::post(&self.url)
+            .send_json(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "simulateTransaction",
+                "params": {
+                    "transaction": unsigned_envelope(transaction).to_xdr_base64(Limits::none())?,
+                },
+            }))
+            .with_context(|| format!("simulateTransaction request to {} failed", self.url))?
+            .into_json()?;
+        if let Some(error) = response.get("error") {
+            bail!("simulateTransaction failed: {error}");
+        }
+        let result = &response["result"];
+        if let Some(error) = result["error"].as_str() {
+            bail!("simulation failed: {error}");
+        }
+        result["results"][0]["xdr"]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| anyhow!("simulation returned no result"))
+    }
+}
+
+impl Backend for RpcBackend {
+    fn record(&self, name: &Name) -> Result<Option<Record>> {
+        let xdr = self.simulate(self.resolver.lookup_record(name)?)?;
+        Ok(decode_option(&xdr)?)
+    }
+
+    fn names_of(&self, account: &str) -> Result<Vec<Name>> {
+        let mut names = Vec::new();
+        let mut cursor = 0;
+        loop {
+            let xdr = self.simulate(self.registry.names_of(account, cursor, PAGE_SIZE)?)?;
+            let page = decode_vec::<(Vec<u8>, Vec<u8>)>(&xdr)?;
+            for (name, tld) in &page {
+                names.push(Name {
+                    name: String::from_utf8_lossy(name).into_owned(),
+                    tld: String::from_utf8_lossy(tld).into_owned(),
+                });
+            }
+            if page.len() < PAGE_SIZE as usize {
+                return Ok(names);
+            }
+            cursor += PAGE_SIZE;
+        }
+    }
+
+    // The gateways are tried in order until one gives an answer the resolver
+    // accepts.
+    fn offchain_record(&self, name: &Name, lookup: &OffchainLookup) -> Result<Record> {
+        let mut failure = anyhow!("{name} has no gateways");
+        for url in &lookup.urls {
+            match self.ask_gateway(url, name, lookup) {
+                Ok(record) => return Ok(record),
+                Err(error) => {
+                    log::warn!("gateway {url} failed for {name}: {error:#}");
+                    failure = error.context(format!("gateway {url} failed"));
+                }
+            }
+        }
+        Err(failure)
+    }
+}
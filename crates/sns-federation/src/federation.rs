@@ -0,0 +1,173 @@
+use crate::backend::Backend;
+use base64::Engine;
+use serde_json::{json, Map, Value};
+use sns_client::{Memo, Name, Record};
+use stellar_strkey::ed25519::{MuxedAccount, PublicKey};
+
+/// A federation reply: the HTTP status and the JSON body.
+#[derive(Debug, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: Value,
+}
+
+enum Failure {
+    BadRequest(String),
+    NotFound(String),
+    NotImplemented(String),
+    Backend(anyhow::Error),
+}
+
+/// Answers SEP-2 federation queries for the names the server's domain hosts.
+/// The Stellar address of `alice.xlm` is `alice.xlm*<domain>`.
+pub struct Federation<B> {
+    backend: B,
+    domain: String,
+}
+
+impl<B: Backend> Federation<B> {
+    pub fn new(backend: B, domain: &str) -> Self {
+        Self {
+            backend,
+            domain: domain.to_ascii_lowercase(),
+        }
+    }
+
+    /// Handles the query string of a `/federation` request.
+    pub fn handle(&self, query: &str) -> Response {
+        let mut q = None;
+        let mut kind = None;
+        for (key, value) in form_urlencoded::parse(query.as_bytes()) {
+            match &*key {
+                "q" => q = Some(value.into_owned()),
+                "type" => kind = Some(value.into_owned()),
+                _ => {}
+            }
+        }
+        let result = match (q, kind.as_deref()) {
+            (None, _) | (_, None) => Err(Failure::BadRequest(
+                "`q` and `type` are required".to_string(),
+            )),
+            (Some(q), Some("name")) => self.lookup_name(&q),
+            (Some(q), Some("id")) => self.lookup_id(&q),
+            (Some(_), Some(kind)) => Err(Failure::NotImplemented(format!(
+                "`{kind}` queries are not supported"
+            ))),
+        };
+        match result {
+            Ok(body) => Response { status: 200, body },
+            Err(failure) => {
+                let (status, detail) = match failure {
+                    Failure::BadRequest(detail) => (400, detail),
+                    Failure::NotFound(detail) => (404, detail),
+                    Failure::NotImplemented(detail) => (501, detail),
+                    Failure::Backend(error) => {
+                        log::error!("lookup failed: {error:#}");
+                        (500, "lookup failed".to_string())
+                    }
+                };
+                Response {
+                    status,
+                    body: json!({ "detail": detail }),
+                }
+            }
+        }
+    }
+
+    fn lookup_name(&self, address: &str) -> Result<Value, Failure> {
+        let address = address.to_ascii_lowercase();
+        let (name, domain) = address
+            .rsplit_once('*')
+            .ok_or_else(|| Failure::BadRequest(format!("`{address}` is not a Stellar address")))?;
+        if domain != self.domain {
+            return Err(Failure::NotFound(format!("`{domain}` is not served here")));
+        }
+        let name = Name::parse(name)
+            .map_err(|_| Failure::NotFound(format!("`{name}` is not a valid name")))?;
+        let record = self
+            .record(&name)?
+            .ok_or_else(|| Failure::NotFound(format!("`{name}` has no record")))?;
+        self.reply(&name, &record)
+            .ok_or_else(|| Failure::NotFound(format!("`{name}` has no payment address")))
+    }
+
+    // Finds a name owned by the account whose record points back at it.
+    // Only root names are checked: the registry lists the names an account
+    // owns, but not their subdomains, so a subdomain pointing at the account
+    // is never found here even though `lookup_name` serves it.
+    fn lookup_id(&self, account: &str) -> Result<Value, Failure> {
+        let root = if let Ok(muxed) = MuxedAccount::from_string(account) {
+            PublicKey(muxed.ed25519).to_string()
+        } else if PublicKey::from_string(account).is_ok() {
+            account.to_string()
+        } else {
+            return Err(Failure::BadRequest(format!(
+                "`{account}` is not an account id"
+            )));
+        };
+        for name in self.backend.names_of(&root).map_err(Failure::Backend)? {
+            let Some(record) = self.record(&name)? else {
+                continue;
+            };
+            if let Some(reply) = self.reply(&name, &record) {
+                if reply["account_id"] == account {
+                    return Ok(reply);
+                }
+            }
+        }
+        Err(Failure::NotFound(format!("no name points at `{account}`")))
+    }
+
+    // Gateways set for a name take precedence over its on-chain record, so
+    // their answer is what the name resolves to.
+    fn record(&self, name: &Name) -> Result<Option<Record>, Failure> {
+        match self.backend.record(name).map_err(Failure::Backend)? {
+            Some(Record::Offchain(lookup)) => self
+                .backend
+                .offchain_record(name, &lookup)
+                .map(Some)
+                .map_err(Failure::Backend),
+            record => Ok(record),
+        }
+    }
+
+    // The federation record for `name`, if its record is a payment address.
+    // Contracts cannot be paid through federation, so they have none. The
+    // resolver only accepts UTF-8 text memos; anything else is not served.
+    fn reply(&self, name: &Name, record: &Record) -> Option<Value> {
+        let mut reply = Map::new();
+        reply.insert(
+            "stellar_address".to_string(),
+            json!(format!("{name}*{}", self.domain)),
+        );
+        let (destination, muxed_id, memo) = match record {
+            Record::Name(address) => (address, None, &Memo::None),
+            Record::Payment(payment) => (&payment.destination, payment.muxed_id, &payment.memo),
+            _ => return None,
+        };
+        let account = PublicKey::from_string(destination).ok()?;
+        let account_id = match muxed_id {
+            Some(id) => MuxedAccount {
+                ed25519: account.0,
+                id,
+            }
+            .to_string(),
+            None => account.to_string(),
+        };
+        reply.insert("account_id".to_string(), json!(account_id));
+        let memo = match memo {
+            Memo::None => None,
+            Memo::Id(id) => Some(("id", id.to_string())),
+            Memo::Text(text) => Some(("text", String::from_utf8(text.clone()).ok()?)),
+            Memo::Hash(hash) => Some((
+                "hash",
+                base64::engine::general_purpose::STANDARD.encode(hash),
+            )),
+        };
+        if let Some((memo_type, memo)) = memo {
+            reply.insert("memo_type".to_string(), json!(memo_type));
+            reply.insert("memo".to_string(), json!(memo));
+        }
+        Some(Value::Object(reply))
+    }
+}
@@ -0,0 +1,76 @@
+//! `sns-federation`: a SEP-2 federation server for SNS names.
+//!
+//! Wallets look up `alice.xlm*<domain>` at `/federation` and get the account
+//! and memo from the name's record, read through the resolver or, for names
+//! with gateways, from the gateways. Point `FEDERATION_SERVER` in the domain's
+//! `stellar.toml` at this server.
+mod backend;
+mod federation;
+
+use anyhow::{anyhow, Result};
+use backend::RpcBackend;
+use clap::Parser;
+use federation::{Federation, Response};
+use tiny_http::{Header, Method, Request, Server};
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "sns-federation",
+    version,
+    about = "SEP-2 federation server for SNS names"
+)]
+struct Cli {
+    /// Address to listen on.
+    #[arg(long, env = "SNS_FEDERATION_LISTEN", default_value = "127.0.0.1:8000")]
+    listen: String,
+    /// Domain of the Stellar addresses served, e.g. `alice.xlm*<domain>`.
+    #[arg(long, env = "SNS_FEDERATION_DOMAIN")]
+    domain: String,
+    /// Soroban RPC server the contracts are read through.
+    #[arg(long, env = "SNS_RPC_URL")]
+    rpc_url: String,
+    #[arg(long, env = "SNS_REGISTRY")]
+    registry: String,
+    #[arg(long, env = "SNS_RESOLVER")]
+    resolver: String,
+}
+
+fn respond(request: Request, response: Response) -> Result<()> {
+    let headers = [
+        Header::from_bytes("Content-Type", "application/json"),
+        Header::from_bytes("Access-Control-Allow-Origin", "*"),
+    ];
+    let mut reply = tiny_http::Response::from_string(response.body.to_string())
+        .with_status_code(response.status);
+    for header in headers {
+        reply.add_header(header.map_err(|_| anyhow!("invalid header"))?);
+    }
+    Ok(request.respond(reply)?)
+}
+
+fn main() -> Result<()> {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+    let cli = Cli::parse();
+    let backend = RpcBackend::new(&cli.rpc_url, &cli.registry, &cli.resolver)?;
+    let federation = Federation::new(backend, &cli.domain);
+    let server = Server::http(&cli.listen).map_err(|error| anyhow!("{error}"))?;
+    log::info!("serving {} on {}", cli.domain, cli.listen);
+
+    for request in server.incoming_requests() {
+        let (path, query) = request.url().split_once('?').unwrap_or((request.url(), ""));
+        let response = if *request.method() != Method::Get || path != "/federation" {
+            Response {
+                status: 404,
+                body: serde_json::json!({ "detail": "not found" }),
+            }
+        } else {
+            federation.handle(query)
+        };
+        if let Err(error) = respond(request, response) {
+            log::error!("failed to respond: {error:#}");
+        }
+    }
+    Ok(())
+}
+
+mod test;
@@ -0,0 +1,230 @@
+#![cfg(test)]
+
+use super::*;
+use backend::Backend;
+use serde_json::json;
+use sns_client::{Memo, Name, OffchainLookup, PaymentRecord, Record};
+use std::collections::BTreeMap;
+
+const ALICE: &str = "GAAZI4TCR3TY5OJHCTJC2A4QSY6CJWJH5IAJTGKIN2ER7LBNVKOCCWN7";
+const BOB: &str = "GAAQEAYEAUDAOCAJBIFQYDIOB4IBCEQTCQKRMFYYDENBWHA5DYPSABOV";
+const CONTRACT: &str = "CAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAHK3M";
+
+// Names and records held in memory, as of some ledger, and the records their
+// gateways answer with.
+#[derive(Default)]
+struct Snapshot {
+    owners: BTreeMap<String, Vec<Name>>,
+    records: BTreeMap<String, Record>,
+    gateways: BTreeMap<String, Record>,
+}
+
+impl Snapshot {
+    fn with(mut self, name: &str, owner: &str, record: Option<Record>) -> Self {
+        let name = Name::parse(name).unwrap();
+        if let Some(record) = record {
+            self.records.insert(name.to_string(), record);
+        }
+        self.owners.entry(owner.to_string()).or_default().push(name);
+        self
+    }
+
+    fn with_gateway(mut self, name: &str, owner: &str, answer: Option<Record>) -> Self {
+        let lookup = OffchainLookup {
+            urls: vec!["https://gateway.example".to_string()],
+            callback_data: name.as_bytes().to_vec(),
+        };
+        if let Some(answer) = answer {
+            self.gateways.insert(name.to_string(), answer);
+        }
+        self.with(name, owner, Some(Record::Offchain(lookup)))
+    }
+}
+
+impl Backend for Snapshot {
+    fn record(&self, name: &Name) -> anyhow::Result<Option<Record>> {
+        Ok(self.records.get(&name.to_string()).cloned())
+    }
+
+    fn names_of(&self, account: &str) -> anyhow::Result<Vec<Name>> {
+        Ok(self.owners.get(account).cloned().unwrap_or_default())
+    }
+
+    fn offchain_record(&self, name: &Name, lookup: &OffchainLookup) -> anyhow::Result<Record> {
+        assert_eq!(lookup.callback_data, name.to_string().as_bytes());
+        self.gateways
+            .get(&name.to_string())
+            .cloned()
+            .ok_or_else(|| anyhow!("gateway unreachable"))
+    }
+}
+
+struct Unavailable;
+
+impl Backend for Unavailable {
+    fn record(&self, _: &Name) -> anyhow::Result<Option<Record>> {
+        Err(anyhow!("connection refused"))
+    }
+
+    fn names_of(&self, _: &str) -> anyhow::Result<Vec<Name>> {
+        Err(anyhow!("connection refused"))
+    }
+
+    fn offchain_record(&self, _: &Name, _: &OffchainLookup) -> anyhow::Result<Record> {
+        Err(anyhow!("connection refused"))
+    }
+}
+
+fn payment(destination: &str, muxed_id: Option<u64>, memo: Memo) -> Option<Record> {
+    Some(Record::Payment(PaymentRecord {
+        destination: destination.to_string(),
+        muxed_id,
+        memo,
+    }))
+}
+
+fn federation() -> Federation<Snapshot> {
+    let snapshot = Snapshot::default()
+        .with("alice.xlm", ALICE, Some(Record::Name(ALICE.to_string())))
+        .with("shop.xlm", ALICE, payment(BOB, None, Memo::Id(42)))
+        .with("exchange.xlm", BOB, payment(BOB, Some(7), Memo::None))
+        .with("memo.xlm", BOB, payment(BOB, None, Memo::Hash([1; 32])))
+        .with(
+            "contract.xlm",
+            BOB,
+            Some(Record::Name(CONTRACT.to_string())),
+        )
+        .with("notes.xlm", BOB, Some(Record::Text(b"hello".to_vec())))
+        .with(
+            "garbled.xlm",
+            BOB,
+            payment(BOB, None, Memo::Text(vec![0xff])),
+        )
+        .with("empty.xlm", BOB, None)
+        .with_gateway("served.xlm", ALICE, payment(ALICE, None, Memo::Id(9)))
+        .with_gateway("textual.xlm", BOB, Some(Record::Text(b"hi".to_vec())))
+        .with_gateway("offline.xlm", BOB, None);
+    Federation::new(snapshot, "Names.Example")
+}
+
+fn status(federation: &Federation<impl Backend>, query: &str) -> u16 {
+    federation.handle(query).status
+}
+
+#[test]
+fn test_name_queries() {
+    let federation = federation();
+    assert_eq!(
+        federation.handle("q=alice.xlm*names.example&type=name"),
+        Response {
+            status: 200,
+            body: json!({
+                "stellar_address": "alice.xlm*names.example",
+                "account_id": ALICE,
+            }),
+        }
+    );
+    // Addresses are case-insensitive and may be percent-encoded.
+    assert_eq!(
+        federation
+            .handle("q=Shop.XLM%2Anames.example&type=name")
+            .body,
+        json!({
+            "stellar_address": "shop.xlm*names.example",
+            "account_id": BOB,
+            "memo_type": "id",
+            "memo": "42",
+        })
+    );
+    assert_eq!(
+        federation.handle("q=memo.xlm*names.example&type=name").body["memo"],
+        "AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQE="
+    );
+
+    // Muxed destinations are returned as an M... account.
+    let body = federation
+        .handle("q=exchange.xlm*names.example&type=name")
+        .body;
+    assert!(body["account_id"].as_str().unwrap().starts_with('M'));
+    assert_eq!(body.get("memo"), None);
+
+    // Names with gateways are answered from them.
+    assert_eq!(
+        federation
+            .handle("q=served.xlm*names.example&type=name")
+            .body,
+        json!({
+            "stellar_address": "served.xlm*names.example",
+            "account_id": ALICE,
+            "memo_type": "id",
+            "memo": "9",
+        })
+    );
+    assert_eq!(
+        status(&federation, "q=offline.xlm*names.example&type=name"),
+        500
+    );
+
+    for missing in [
+        "q=bob.xlm*names.example&type=name",
+        "q=textual.xlm*names.example&type=name",
+        "q=empty.xlm*names.example&type=name",
+        "q=notes.xlm*names.example&type=name",
+        "q=garbled.xlm*names.example&type=name",
+        "q=contract.xlm*names.example&type=name",
+        "q=alice.xlm*other.example&type=name",
+        "q=a.xlm*names.example&type=name",
+    ] {
+        assert_eq!(status(&federation, missing), 404, "{missing}");
+    }
+    assert_eq!(status(&federation, "q=alice.xlm&type=name"), 400);
+    assert_eq!(status(&federation, "q=alice.xlm*names.example"), 400);
+    assert_eq!(status(&federation, "type=name"), 400);
+}
+
+#[test]
+fn test_id_queries() {
+    let federation = federation();
+    assert_eq!(
+        federation.handle(&format!("q={ALICE}&type=id")).body,
+        json!({
+            "stellar_address": "alice.xlm*names.example",
+            "account_id": ALICE,
+        })
+    );
+    // Bob owns names, but only one of them routes to a muxed account of his.
+    let muxed = federation
+        .handle("q=exchange.xlm*names.example&type=name")
+        .body["account_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    assert_eq!(
+        federation.handle(&format!("q={muxed}&type=id")).body["stellar_address"],
+        "exchange.xlm*names.example"
+    );
+    assert_eq!(
+        federation.handle(&format!("q={BOB}&type=id")).body["stellar_address"],
+        "memo.xlm*names.example"
+    );
+
+    let federation = Federation::new(
+        Snapshot::default().with("shop.xlm", ALICE, payment(BOB, None, Memo::None)),
+        "names.example",
+    );
+    assert_eq!(status(&federation, &format!("q={ALICE}&type=id")), 404);
+    assert_eq!(status(&federation, &format!("q={CONTRACT}&type=id")), 400);
+    assert_eq!(status(&federation, "q=alice&type=id"), 400);
+}
+
+#[test]
+fn test_unsupported_and_failing_queries() {
+    let federation = federation();
+    assert_eq!(status(&federation, "q=abc&type=txid"), 501);
+    assert_eq!(status(&federation, "q=abc&type=forward"), 501);
+
+    let response =
+        Federation::new(Unavailable, "names.example").handle("q=alice.xlm*names.example&type=name");
+    assert_eq!(response.status, 500);
+    assert_eq!(response.body, json!({ "detail": "lookup failed" }));
+}
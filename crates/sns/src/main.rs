@@ -0,0 +1,534 @@
+//! `sns`: inspect and manage SNS names from the command line.
+//!
+//! Reads are simulated against a Soroban RPC server and printed decoded.
+//! Writes print a transaction envelope as base64, unsigned or signed with
+//! `--key-file`, ready to be submitted with any Stellar tool.
+mod print;
+mod rpc;
+mod sign;
+
+use anyhow::{anyhow, bail, Result};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use rpc::Rpc;
+use sns_client::xdr::{InvokeContractArgs, Limits, WriteXdr};
+use sns_client::{
+    decode, decode_option, decode_vec, invoke_transaction, unsigned_envelope, Admin, Domain,
+    KeyType, Name, Offer, Record, RegistrarController, Registry, Resolver, Role,
+};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+const TESTNET_PASSPHRASE: &str = "Test SDF Network ; September 2015";
+
+// Reads are simulated from this account unless `--source` is given.
+const READ_SOURCE: &str = "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF";
+
+// Page size used to walk `names_of`.
+const PAGE_SIZE: u32 = 50;
+
+#[derive(Debug, Parser)]
+#[command(name = "sns", version, about = "Inspect and manage SNS names")]
+struct Cli {
+    #[command(flatten)]
+    network: Network,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Args)]
+struct Network {
+    /// Soroban RPC server, used for reads and to simulate writes.
+    #[arg(long, env = "SNS_RPC_URL", global = true)]
+    rpc_url: Option<String>,
+    #[arg(long, env = "SNS_NETWORK_PASSPHRASE", default_value = TESTNET_PASSPHRASE, global = true)]
+    network_passphrase: String,
+    #[arg(long, env = "SNS_REGISTRY", global = true)]
+    registry: Option<String>,
+    #[arg(long, env = "SNS_RESOLVER", global = true)]
+    resolver: Option<String>,
+    #[arg(long, env = "SNS_CONTROLLER", global = true)]
+    controller: Option<String>,
+    /// Account sending the transaction. Defaults to the key file's account.
+    #[arg(long, env = "SNS_SOURCE", global = true)]
+    source: Option<String>,
+    /// Current sequence number of the source account. Fetched from the RPC
+    /// server when not given.
+    #[arg(long, global = true)]
+    sequence: Option<i64>,
+    /// Base fee in stroops, to which simulation adds the resource fee.
+    #[arg(long, default_value_t = 100, global = true)]
+    fee: u32,
+    /// File holding the secret key (`S...`) to sign writes with.
+    #[arg(long, env = "SNS_KEY_FILE", global = true)]
+    key_file: Option<PathBuf>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Show a name's owner, resolver and expiry.
+    Info {
+        name: Name,
+    },
+    /// Resolve a name to its record.
+    Resolve {
+        name: Name,
+    },
+    /// Register a name through the registrar controller.
+    Register {
+        name: Name,
+        /// Defaults to the source account, which pays either way.
+        #[arg(long)]
+        owner: Option<String>,
+        #[arg(long, default_value_t = 1)]
+        years: u64,
+        #[arg(long)]
+        referrer: Option<String>,
+    },
+    /// Renew a name through the registrar controller.
+    Renew {
+        name: Name,
+        #[arg(long, default_value_t = 1)]
+        years: u64,
+    },
+    /// Transfer a name to a new owner.
+    Transfer {
+        name: Name,
+        new_owner: String,
+    },
+    /// Set a name's record.
+    ///
+    /// `data` is in the resolver's format for the type, e.g. `payment` takes
+    /// `G...;id;42`.
+    SetRecord {
+        name: Name,
+        #[arg(value_parser = ["name", "contenthash", "text", "payment"])]
+        record_type: String,
+        data: String,
+    },
+    /// Delete a name's record.
+    DeleteRecord {
+        name: Name,
+    },
+    /// Set a name's address on another chain.
+    ///
+    /// `addr` is hex in the resolver's binary form for the chain, e.g. the 20
+    /// byte account for Ethereum (`60`). `0x` removes the address.
+    SetAddr {
+        name: Name,
+        /// SLIP-44 coin type.
+        coin_type: u32,
+        addr: Hex,
+    },
+    /// Publish a public key of the owner under a name.
+    SetPublicKey {
+        name: Name,
+        /// What the key is for, e.g. `messaging` or `login`.
+        purpose: String,
+        /// `ed25519`, `secp256k1` or `x25519`.
+        key_type: KeyType,
+        /// Hex, 65 byte uncompressed for secp256k1.
+        key: Hex,
+    },
+    RemovePublicKey {
+        name: Name,
+        purpose: String,
+    },
+    /// Serve a name's records from off-chain gateways.
+    SetOffchain {
+        name: Name,
+        /// A gateway URL, repeated for fallbacks.
+        #[arg(long = "url", required = true)]
+        urls: Vec<String>,
+        /// Hex ed25519 key the gateways sign their answers with.
+        signer: Hex,
+    },
+    RemoveOffchain {
+        name: Name,
+    },
+    /// Sell offers on the registry.
+    #[command(subcommand)]
+    Offers(OffersCommand),
+    /// Admin calls on the contracts, sent as the source account.
+    #[command(subcommand)]
+    Admin(AdminCommand),
+    /// Decode a base64 contract value, e.g. one copied from an explorer.
+    Decode {
+        kind: Kind,
+        xdr: String,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum OffersCommand {
+    /// Show the offer on a name.
+    Show { name: Name },
+    /// List the offers on an account's names.
+    List { owner: String },
+    /// Offer a name for sale.
+    Make { name: Name, price: u64 },
+    /// Withdraw the offer on a name.
+    Cancel { name: Name },
+    /// Buy an offered name.
+    Buy {
+        name: Name,
+        /// Defaults to the source account.
+        #[arg(long)]
+        buyer: Option<String>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum AdminCommand {
+    Pause {
+        contract: Target,
+    },
+    Unpause {
+        contract: Target,
+    },
+    /// Grant a role, as the admin.
+    GrantRole {
+        contract: Target,
+        /// e.g. `pauser` or `pricing-manager`.
+        role: Role,
+        account: String,
+    },
+    /// Revoke a granted role, as the admin.
+    RevokeRole {
+        contract: Target,
+        role: Role,
+        account: String,
+    },
+    /// Give up a role of the source account.
+    RenounceRole {
+        contract: Target,
+        role: Role,
+    },
+    ProposeAdmin {
+        contract: Target,
+        new_admin: String,
+    },
+    AcceptAdmin {
+        contract: Target,
+    },
+    CancelAdminProposal {
+        contract: Target,
+    },
+    /// Set the registry's default resolver.
+    SetResolver {
+        resolver: String,
+    },
+    /// Let a registrar controller register and renew names.
+    AddController {
+        controller: String,
+    },
+    RemoveController {
+        controller: String,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+enum Target {
+    Registry,
+    Resolver,
+    Controller,
+}
+
+// Binary arguments, given as hex with an optional `0x` prefix.
+#[derive(Clone, Debug, PartialEq)]
+struct Hex(Vec<u8>);
+
+impl FromStr for Hex {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        let digits = value.strip_prefix("0x").unwrap_or(value);
+        if !digits.len().is_multiple_of(2) || !digits.is_ascii() {
+            bail!("`{value}` is not hex");
+        }
+        (0..digits.len())
+            .step_by(2)
+            .map(|index| u8::from_str_radix(&digits[index..index + 2], 16))
+            .collect::<core::result::Result<_, _>>()
+            .map(Self)
+            .map_err(|_| anyhow!("`{value}` is not hex"))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+enum Kind {
+    Domain,
+    Offer,
+    Record,
+}
+
+impl Network {
+    fn rpc(&self) -> Result<Rpc> {
+        self.rpc_url
+            .as_deref()
+            .map(Rpc::new)
+            .ok_or_else(|| anyhow!("--rpc-url (or SNS_RPC_URL) is required"))
+    }
+
+    fn registry(&self) -> Result<Registry> {
+        let id = required(&self.registry, "--registry (or SNS_REGISTRY)")?;
+        Ok(Registry::new(id)?)
+    }
+
+    fn resolver(&self) -> Result<Resolver> {
+        let id = required(&self.resolver, "--resolver (or SNS_RESOLVER)")?;
+        Ok(Resolver::new(id)?)
+    }
+
+    fn controller(&self) -> Result<RegistrarController> {
+        let id = required(&self.controller, "--controller (or SNS_CONTROLLER)")?;
+        Ok(RegistrarController::new(id)?)
+    }
+
+    fn admin(&self, target: Target) -> Result<Admin> {
+        let id = match target {
+            Target::Registry => required(&self.registry, "--registry (or SNS_REGISTRY)")?,
+            Target::Resolver => required(&self.resolver, "--resolver (or SNS_RESOLVER)")?,
+            Target::Controller => required(&self.controller, "--controller (or SNS_CONTROLLER)")?,
+        };
+        Ok(Admin::new(id)?)
+    }
+
+    // The account writes are sent from.
+    fn sender(&self) -> Result<String> {
+        match (&self.source, &self.key_file) {
+            (Some(source), _) => Ok(source.clone()),
+            (None, Some(path)) => Ok(sign::account(&sign::read_key(path)?)),
+            (None, None) => bail!("--source or --key-file is required"),
+        }
+    }
+
+    // Simulates a read-only call and returns its base64 result.
+    fn read(&self, invoke: InvokeContractArgs) -> Result<String> {
+        let source = self.source.as_deref().unwrap_or(READ_SOURCE);
+        let transaction = invoke_transaction(source, 0, self.fee, invoke)?;
+        Ok(self
+            .rpc()?
+            .simulate(&unsigned_envelope(transaction))?
+            .result)
+    }
+
+    // Builds the transaction for a write and returns its envelope as base64.
+    // Without an RPC server it is left unsimulated for the caller to finish.
+    fn write(&self, invoke: InvokeContractArgs) -> Result<String> {
+        let source = self.sender()?;
+        let sequence = match self.sequence {
+            Some(sequence) => sequence,
+            None => self.rpc()?.account_sequence(&source)?,
+        };
+        let mut transaction = invoke_transaction(&source, sequence, self.fee, invoke)?;
+        if self.rpc_url.is_some() {
+            let simulation = self
+                .rpc()?
+                .simulate(&unsigned_envelope(transaction.clone()))?;
+            transaction = rpc::assemble(transaction, simulation)?;
+        }
+        let envelope = match &self.key_file {
+            Some(_) if self.rpc_url.is_none() => {
+                bail!("signing needs --rpc-url to simulate the transaction first")
+            }
+            Some(path) => sign::sign(
+                transaction,
+                &sign::read_key(path)?,
+                &self.network_passphrase,
+            )?,
+            None => unsigned_envelope(transaction),
+        };
+        Ok(envelope.to_xdr_base64(Limits::none())?)
+    }
+}
+
+fn required<'a>(value: &'a Option<String>, flag: &str) -> Result<&'a str> {
+    value
+        .as_deref()
+        .ok_or_else(|| anyhow!("{flag} is required"))
+}
+
+fn run(cli: Cli) -> Result<String> {
+    let network = &cli.network;
+    match cli.command {
+        Command::Info { name } => {
+            let root = name.root();
+            let xdr = network.read(network.registry()?.lookup_name(&root)?)?;
+            match decode_option::<Domain>(&xdr)? {
+                Some(domain) => Ok(format!("name:          {root}\n{}", print::domain(&domain))),
+                None => Ok(format!("{root} is not registered")),
+            }
+        }
+        Command::Resolve { name } => {
+            let xdr = network.read(network.resolver()?.resolve_name(&name)?)?;
+            Ok(print::record(&decode::<Record>(&xdr)?))
+        }
+        Command::Register {
+            name,
+            owner,
+            years,
+            referrer,
+        } => {
+            let owner = match owner {
+                Some(owner) => owner,
+                None => network.sender()?,
+            };
+            network.write(network.controller()?.register_name(
+                &name,
+                &owner,
+                years,
+                referrer.as_deref(),
+            )?)
+        }
+        Command::Renew { name, years } => {
+            let payer = network.sender()?;
+            network.write(network.controller()?.renew_name(&name, &payer, years)?)
+        }
+        Command::Transfer { name, new_owner } => {
+            network.write(network.registry()?.transfer(&name, &new_owner)?)
+        }
+        Command::SetRecord {
+            name,
+            record_type,
+            data,
+        } => network.write(
+            network
+                .resolver()?
+                .set_record(&name, &record_type, data.as_bytes())?,
+        ),
+        Command::DeleteRecord { name } => network.write(network.resolver()?.delete_record(&name)?),
+        Command::SetAddr {
+            name,
+            coin_type,
+            addr,
+        } => network.write(network.resolver()?.set_addr(&name, coin_type, &addr.0)?),
+        Command::SetPublicKey {
+            name,
+            purpose,
+            key_type,
+            key,
+        } => network.write(
+            network
+                .resolver()?
+                .set_public_key(&name, &purpose, key_type, &key.0)?,
+        ),
+        Command::RemovePublicKey { name, purpose } => {
+            network.write(network.resolver()?.remove_public_key(&name, &purpose)?)
+        }
+        Command::SetOffchain { name, urls, signer } => {
+            let signer: [u8; 32] = signer
+                .0
+                .try_into()
+                .map_err(|_| anyhow!("the signer must be a 32 byte ed25519 key"))?;
+            network.write(
+                network
+                    .resolver()?
+                    .set_offchain_resolver(&name, &urls, &signer)?,
+            )
+        }
+        Command::RemoveOffchain { name } => {
+            network.write(network.resolver()?.remove_offchain_resolver(&name)?)
+        }
+        Command::Offers(command) => offers(network, command),
+        Command::Admin(command) => admin(network, command),
+        Command::Decode { kind, xdr } => Ok(match kind {
+            Kind::Domain => print::domain(&decode::<Domain>(&xdr)?),
+            Kind::Offer => print::offer(&decode::<Offer>(&xdr)?),
+            Kind::Record => print::record(&decode::<Record>(&xdr)?),
+        }),
+    }
+}
+
+fn offers(network: &Network, command: OffersCommand) -> Result<String> {
+    let registry = network.registry()?;
+    match command {
+        OffersCommand::Show { name } => {
+            let xdr = network.read(registry.lookup_sell_offer(&name)?)?;
+            match decode_option::<Offer>(&xdr)? {
+                Some(offer) => Ok(print::offer(&offer)),
+                None => Ok(format!("{name} is not for sale")),
+            }
+        }
+        OffersCommand::List { owner } => {
+            let mut lines = Vec::new();
+            let mut cursor = 0;
+            loop {
+                let xdr = network.read(registry.names_of(&owner, cursor, PAGE_SIZE)?)?;
+                let names = decode_vec::<(Vec<u8>, Vec<u8>)>(&xdr)?;
+                for (name, tld) in &names {
+                    let name = Name::parse(&format!("{}.{}", print::text(name), print::text(tld)))?;
+                    let xdr = network.read(registry.lookup_sell_offer(&name)?)?;
+                    if let Some(offer) = decode_option::<Offer>(&xdr)? {
+                        lines.push(print::offer(&offer));
+                    }
+                }
+                if names.len() < PAGE_SIZE as usize {
+                    break;
+                }
+                cursor += PAGE_SIZE;
+            }
+            if lines.is_empty() {
+                lines.push(format!("no offers on names owned by {owner}"));
+            }
+            Ok(lines.join("\n"))
+        }
+        OffersCommand::Make { name, price } => {
+            network.write(registry.make_sell_offer(&name, price)?)
+        }
+        OffersCommand::Cancel { name } => network.write(registry.cancel_sell_offer(&name)?),
+        OffersCommand::Buy { name, buyer } => {
+            let buyer = match buyer {
+                Some(buyer) => buyer,
+                None => network.sender()?,
+            };
+            network.write(registry.buy_name(&name, &buyer)?)
+        }
+    }
+}
+
+fn admin(network: &Network, command: AdminCommand) -> Result<String> {
+    let invoke = match command {
+        AdminCommand::Pause { contract } => network.admin(contract)?.pause(&network.sender()?)?,
+        AdminCommand::Unpause { contract } => {
+            network.admin(contract)?.unpause(&network.sender()?)?
+        }
+        AdminCommand::GrantRole {
+            contract,
+            role,
+            account,
+        } => network.admin(contract)?.grant_role(role, &account)?,
+        AdminCommand::RevokeRole {
+            contract,
+            role,
+            account,
+        } => network.admin(contract)?.revoke_role(role, &account)?,
+        AdminCommand::RenounceRole { contract, role } => network
+            .admin(contract)?
+            .renounce_role(&network.sender()?, role)?,
+        AdminCommand::ProposeAdmin {
+            contract,
+            new_admin,
+        } => network.admin(contract)?.propose_admin(&new_admin)?,
+        AdminCommand::AcceptAdmin { contract } => network.admin(contract)?.accept_admin()?,
+        AdminCommand::CancelAdminProposal { contract } => {
+            network.admin(contract)?.cancel_admin_proposal()?
+        }
+        AdminCommand::SetResolver { resolver } => network
+            .registry()?
+            .set_resolver(&network.sender()?, &resolver)?,
+        AdminCommand::AddController { controller } => network
+            .registry()?
+            .add_controller(&network.sender()?, &controller)?,
+        AdminCommand::RemoveController { controller } => network
+            .registry()?
+            .remove_controller(&network.sender()?, &controller)?,
+    };
+    network.write(invoke)
+}
+
+fn main() -> Result<()> {
+    println!("{}", run(Cli::parse())?);
+    Ok(())
+}
+
+mod test;
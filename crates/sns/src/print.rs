@@ -0,0 +1,66 @@
+use sns_client::{ContentCodec, Domain, Memo, Offer, Record};
+
+// Contract values in a form support staff can read. Bytes are shown as text
+// when they are UTF-8 and as hex otherwise.
+
+pub fn domain(domain: &Domain) -> String {
+    format!(
+        "owner:      {}\n\
+         resolver:   {}\n\
+         expires at: {}",
+        domain.owner, domain.resolver, domain.expiry
+    )
+}
+
+pub fn offer(offer: &Offer) -> String {
+    format!(
+        "{}.{} for {} by {}",
+        text(&offer.name),
+        text(&offer.tld),
+        offer.price,
+        offer.seller
+    )
+}
+
+pub fn record(record: &Record) -> String {
+    match record {
+        Record::NullRecord => "no record".to_string(),
+        Record::Name(address) => format!("name: {address}"),
+        Record::Text(value) => format!("text: {}", text(value)),
+        Record::Ipfs(value) => format!("ipfs: {}", text(value)),
+        Record::Offchain(lookup) => format!("offchain: {}", lookup.urls.join(", ")),
+        Record::ContentHash(content_hash) => {
+            let codec = match content_hash.codec {
+                ContentCodec::Ipfs => "ipfs",
+                ContentCodec::Ipns => "ipns",
+                ContentCodec::Swarm => "swarm",
+                ContentCodec::Arweave => "arweave",
+            };
+            format!("contenthash: {codec} {}", hex(&content_hash.hash))
+        }
+        Record::Payment(payment) => {
+            let mut line = format!("payment: {}", payment.destination);
+            if let Some(id) = payment.muxed_id {
+                line.push_str(&format!(" (muxed id {id})"));
+            }
+            match &payment.memo {
+                Memo::None => {}
+                Memo::Id(id) => line.push_str(&format!(", memo id {id}")),
+                Memo::Text(value) => line.push_str(&format!(", memo text {}", text(value))),
+                Memo::Hash(hash) => line.push_str(&format!(", memo hash {}", hex(hash))),
+            }
+            line
+        }
+    }
+}
+
+pub fn text(bytes: &[u8]) -> String {
+    match core::str::from_utf8(bytes) {
+        Ok(value) => value.to_string(),
+        Err(_) => format!("0x{}", hex(bytes)),
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
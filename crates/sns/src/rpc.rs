@@ -0,0 +1,130 @@
+use anyhow::{anyhow, bail, Context, Result};
+use serde_json::{json, Value};
+use sns_client::xdr::{
+    AccountId, LedgerEntryData, LedgerKey, LedgerKeyAccount, Limits, OperationBody, ReadXdr,
+    SorobanAuthorizationEntry, SorobanTransactionData, Transaction, TransactionEnvelope,
+    TransactionExt, WriteXdr,
+};
+
+// Just the Soroban RPC methods the CLI needs.
+pub struct Rpc {
+    url: String,
+}
+
+#[derive(Debug)]
+pub struct Simulation {
+    // Base64 `ScVal` returned by the call.
+    pub result: String,
+    pub auth: Vec<SorobanAuthorizationEntry>,
+    pub transaction_data: SorobanTransactionData,
+    pub min_resource_fee: u32,
+}
+
+impl Rpc {
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+        }
+    }
+
+    fn request(&self, method: &str, params: Value) -> Result<Value> {
+        let response: Value = ureq::post(&self.url)
+            .send_json(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": method,
+                "params": params,
+            }))
+            .with_context(|| format!("{method} request to {} failed", self.url))?
+            .into_json()?;
+        if let Some(error) = response.get("error") {
+            bail!("{method} failed: {error}");
+        }
+        response
+            .get("result")
+            .cloned()
+            .ok_or_else(|| anyhow!("{method} returned no result"))
+    }
+
+    pub fn account_sequence(&self, account: &str) -> Result<i64> {
+        let account_id: AccountId = account
+            .parse()
+            .with_context(|| format!("invalid account `{account}`"))?;
+        let key = LedgerKey::Account(LedgerKeyAccount { account_id });
+        let result = self.request(
+            "getLedgerEntries",
+            json!({ "keys": [key.to_xdr_base64(Limits::none())?] }),
+        )?;
+        let entry = result["entries"]
+            .get(0)
+            .and_then(|entry| entry["xdr"].as_str())
+            .ok_or_else(|| anyhow!("account {account} not found"))?;
+        match LedgerEntryData::from_xdr_base64(entry, Limits::none())? {
+            LedgerEntryData::Account(entry) => Ok(entry.seq_num.0),
+            _ => bail!("unexpected ledger entry for account {account}"),
+        }
+    }
+
+    pub fn simulate(&self, envelope: &TransactionEnvelope) -> Result<Simulation> {
+        let result = self.request(
+            "simulateTransaction",
+            json!({ "transaction": envelope.to_xdr_base64(Limits::none())? }),
+        )?;
+        parse_simulation(&result)
+    }
+}
+
+pub fn parse_simulation(result: &Value) -> Result<Simulation> {
+    if let Some(error) = result["error"].as_str() {
+        bail!("simulation failed: {error}");
+    }
+    let call = &result["results"][0];
+    let auth = call["auth"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .map(|entry| {
+            let entry = entry
+                .as_str()
+                .ok_or_else(|| anyhow!("invalid auth entry"))?;
+            Ok(SorobanAuthorizationEntry::from_xdr_base64(
+                entry,
+                Limits::none(),
+            )?)
+        })
+        .collect::<Result<_>>()?;
+    Ok(Simulation {
+        result: call["xdr"]
+            .as_str()
+            .ok_or_else(|| anyhow!("simulation returned no result"))?
+            .to_string(),
+        auth,
+        transaction_data: SorobanTransactionData::from_xdr_base64(
+            result["transactionData"]
+                .as_str()
+                .ok_or_else(|| anyhow!("simulation returned no transaction data"))?,
+            Limits::none(),
+        )?,
+        min_resource_fee: result["minResourceFee"]
+            .as_str()
+            .ok_or_else(|| anyhow!("simulation returned no resource fee"))?
+            .parse()?,
+    })
+}
+
+// Adds what the simulation worked out, so the transaction can be signed and
+// sent.
+pub fn assemble(mut transaction: Transaction, simulation: Simulation) -> Result<Transaction> {
+    transaction.fee = transaction
+        .fee
+        .checked_add(simulation.min_resource_fee)
+        .ok_or_else(|| anyhow!("fee overflow"))?;
+    transaction.ext = TransactionExt::V1(simulation.transaction_data);
+    let mut operations = transaction.operations.to_vec();
+    if let OperationBody::InvokeHostFunction(op) = &mut operations[0].body {
+        op.auth = simulation.auth.try_into()?;
+    }
+    transaction.operations = operations.try_into()?;
+    Ok(transaction)
+}
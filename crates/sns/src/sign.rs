@@ -0,0 +1,42 @@
+use anyhow::{anyhow, Context, Result};
+use ed25519_dalek::{Signer, SigningKey};
+use sns_client::transaction_hash;
+use sns_client::xdr::{
+    DecoratedSignature, Signature, SignatureHint, Transaction, TransactionEnvelope,
+    TransactionV1Envelope,
+};
+use std::path::Path;
+
+// A key file holds one secret key in strkey form (`S...`).
+pub fn read_key(path: &Path) -> Result<SigningKey> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("cannot read key file {}", path.display()))?;
+    parse_key(contents.trim())
+}
+
+pub fn parse_key(secret: &str) -> Result<SigningKey> {
+    let key = stellar_strkey::ed25519::PrivateKey::from_string(secret)
+        .map_err(|_| anyhow!("key file does not hold a secret key"))?;
+    Ok(SigningKey::from_bytes(&key.0))
+}
+
+pub fn account(key: &SigningKey) -> String {
+    stellar_strkey::ed25519::PublicKey(key.verifying_key().to_bytes()).to_string()
+}
+
+pub fn sign(
+    transaction: Transaction,
+    key: &SigningKey,
+    network_passphrase: &str,
+) -> Result<TransactionEnvelope> {
+    let hash = transaction_hash(&transaction, network_passphrase)?;
+    let public_key = key.verifying_key().to_bytes();
+    let signature = DecoratedSignature {
+        hint: SignatureHint(public_key[28..].try_into()?),
+        signature: Signature(key.sign(&hash).to_bytes().try_into()?),
+    };
+    Ok(TransactionEnvelope::Tx(TransactionV1Envelope {
+        tx: transaction,
+        signatures: vec![signature].try_into()?,
+    }))
+}
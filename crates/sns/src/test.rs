@@ -0,0 +1,237 @@
+#![cfg(test)]
+
+use super::*;
+use ed25519_dalek::Verifier;
+use serde_json::json;
+use sns_client::xdr::{
+    ExtensionPoint, LedgerFootprint, ReadXdr, ScVal, SorobanResources, SorobanTransactionData,
+    TransactionEnvelope, TransactionExt,
+};
+use sns_client::{transaction_hash, ContentCodec, ContentHash, Memo, PaymentRecord};
+
+const ACCOUNT: &str = "GAAZI4TCR3TY5OJHCTJC2A4QSY6CJWJH5IAJTGKIN2ER7LBNVKOCCWN7";
+const CONTRACT: &str = "CAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAHK3M";
+
+fn parse(args: &[&str]) -> Result<Cli, clap::Error> {
+    Cli::try_parse_from(["sns"].iter().chain(args))
+}
+
+fn transaction_data(resource_fee: i64) -> SorobanTransactionData {
+    SorobanTransactionData {
+        ext: ExtensionPoint::V0,
+        resources: SorobanResources {
+            footprint: LedgerFootprint {
+                read_only: Default::default(),
+                read_write: Default::default(),
+            },
+            instructions: 1,
+            read_bytes: 2,
+            write_bytes: 3,
+        },
+        resource_fee,
+    }
+}
+
+#[test]
+fn test_parse_args() {
+    let cli = parse(&[
+        "register",
+        "alice.xlm",
+        "--years",
+        "2",
+        "--controller",
+        CONTRACT,
+        "--source",
+        ACCOUNT,
+    ])
+    .unwrap();
+    assert_eq!(cli.network.source.as_deref(), Some(ACCOUNT));
+    assert!(matches!(
+        cli.command,
+        Command::Register { years: 2, owner: None, referrer: None, ref name }
+            if name.to_string() == "alice.xlm"
+    ));
+
+    let cli = parse(&["admin", "pause", "resolver"]).unwrap();
+    assert!(matches!(
+        cli.command,
+        Command::Admin(AdminCommand::Pause {
+            contract: Target::Resolver
+        })
+    ));
+
+    assert!(parse(&["resolve", "Alice.xlm"]).is_err());
+    assert!(parse(&["set-record", "alice.xlm", "email", "a@b.c"]).is_err());
+    let cli = parse(&["set-addr", "alice.xlm", "60", "0x00ff"]).unwrap();
+    assert!(matches!(
+        cli.command,
+        Command::SetAddr { coin_type: 60, ref addr, .. } if addr.0 == [0x00, 0xff]
+    ));
+    assert!(parse(&["set-addr", "alice.xlm", "60", "0x0"]).is_err());
+    assert!(parse(&["set-addr", "alice.xlm", "60", "zz"]).is_err());
+    let cli = parse(&["set-public-key", "alice.xlm", "messaging", "x25519", "07"]).unwrap();
+    assert!(matches!(
+        cli.command,
+        Command::SetPublicKey {
+            key_type: KeyType::X25519,
+            ref purpose,
+            ..
+        } if purpose == "messaging"
+    ));
+    assert!(parse(&["set-public-key", "alice.xlm", "login", "rsa", "07"]).is_err());
+    let cli = parse(&[
+        "set-offchain",
+        "alice.xlm",
+        "--url",
+        "https://a.example.com",
+        "--url",
+        "https://b.example.com",
+        "00",
+    ])
+    .unwrap();
+    assert!(matches!(cli.command, Command::SetOffchain { ref urls, .. } if urls.len() == 2));
+    assert!(parse(&["set-offchain", "alice.xlm", "00"]).is_err());
+    let cli = parse(&["set-record", "*.alice.xlm", "name", "bob.xlm"]).unwrap();
+    assert!(matches!(
+        cli.command,
+        Command::SetRecord { ref name, .. } if name.to_string() == "*.alice.xlm"
+    ));
+    let cli = parse(&["admin", "pause", "controller"]).unwrap();
+    assert!(matches!(
+        cli.command,
+        Command::Admin(AdminCommand::Pause {
+            contract: Target::Controller
+        })
+    ));
+
+    let cli = parse(&["admin", "grant-role", "controller", "treasurer", ACCOUNT]).unwrap();
+    assert!(matches!(
+        cli.command,
+        Command::Admin(AdminCommand::GrantRole {
+            contract: Target::Controller,
+            role: Role::Treasurer,
+            ref account,
+        }) if account == ACCOUNT
+    ));
+    assert!(parse(&["admin", "renounce-role", "registry", "owner"]).is_err());
+}
+
+#[test]
+fn test_write_unsigned() {
+    let cli = parse(&[
+        "transfer",
+        "alice.xlm",
+        ACCOUNT,
+        "--registry",
+        CONTRACT,
+        "--source",
+        ACCOUNT,
+        "--sequence",
+        "41",
+    ])
+    .unwrap();
+    let envelope = TransactionEnvelope::from_xdr_base64(run(cli).unwrap(), Limits::none()).unwrap();
+    let TransactionEnvelope::Tx(envelope) = envelope else {
+        panic!("unexpected envelope");
+    };
+    assert_eq!(envelope.tx.seq_num.0, 42);
+    assert_eq!(envelope.tx.fee, 100);
+    assert!(envelope.signatures.is_empty());
+
+    // Missing contract ids and accounts are reported before any call.
+    let cli = parse(&["transfer", "alice.xlm", ACCOUNT, "--source", ACCOUNT]).unwrap();
+    assert!(run(cli).unwrap_err().to_string().contains("--registry"));
+    let cli = parse(&["transfer", "alice.xlm", ACCOUNT, "--registry", CONTRACT]).unwrap();
+    assert!(run(cli).unwrap_err().to_string().contains("--source"));
+}
+
+#[test]
+fn test_sign() {
+    let secret = stellar_strkey::ed25519::PrivateKey([7; 32]).to_string();
+    let key = sign::parse_key(&secret).unwrap();
+    assert!(sign::parse_key(ACCOUNT).is_err());
+
+    let account = sign::account(&key);
+    assert!(account.starts_with('G'));
+    let invoke = Registry::new(CONTRACT)
+        .unwrap()
+        .cancel_sell_offer(&Name::parse("alice.xlm").unwrap())
+        .unwrap();
+    let transaction = invoke_transaction(&account, 1, 100, invoke).unwrap();
+
+    let TransactionEnvelope::Tx(envelope) =
+        sign::sign(transaction.clone(), &key, TESTNET_PASSPHRASE).unwrap()
+    else {
+        panic!("unexpected envelope");
+    };
+    let signature = &envelope.signatures[0];
+    let public_key = key.verifying_key();
+    assert_eq!(signature.hint.0, public_key.to_bytes()[28..]);
+    let hash = transaction_hash(&transaction, TESTNET_PASSPHRASE).unwrap();
+    let signature = ed25519_dalek::Signature::from_slice(&signature.signature.0).unwrap();
+    assert!(public_key.verify(&hash, &signature).is_ok());
+}
+
+#[test]
+fn test_assemble() {
+    let result = ScVal::Bool(true).to_xdr_base64(Limits::none()).unwrap();
+    let simulation = rpc::parse_simulation(&json!({
+        "transactionData": transaction_data(1234).to_xdr_base64(Limits::none()).unwrap(),
+        "minResourceFee": "1234",
+        "results": [{ "auth": [], "xdr": result }],
+        "latestLedger": 100,
+    }))
+    .unwrap();
+    assert_eq!(simulation.result, result);
+    assert!(decode::<bool>(&simulation.result).unwrap());
+
+    let invoke = Registry::new(CONTRACT)
+        .unwrap()
+        .transfer(&Name::parse("alice.xlm").unwrap(), ACCOUNT)
+        .unwrap();
+    let transaction = invoke_transaction(ACCOUNT, 1, 100, invoke).unwrap();
+    let transaction = rpc::assemble(transaction, simulation).unwrap();
+    assert_eq!(transaction.fee, 1334);
+    assert_eq!(transaction.ext, TransactionExt::V1(transaction_data(1234)));
+
+    let error = rpc::parse_simulation(&json!({
+        "error": "HostError: Error(Contract, #3)",
+        "latestLedger": 100,
+    }))
+    .unwrap_err();
+    assert!(error.to_string().contains("Error(Contract, #3)"));
+}
+
+#[test]
+fn test_print() {
+    assert_eq!(print::record(&Record::NullRecord), "no record");
+    assert_eq!(
+        print::record(&Record::Text(b"hello".to_vec())),
+        "text: hello"
+    );
+    assert_eq!(
+        print::record(&Record::ContentHash(ContentHash {
+            codec: ContentCodec::Ipfs,
+            hash: vec![1, 112, 18, 32],
+        })),
+        "contenthash: ipfs 01701220"
+    );
+    assert_eq!(
+        print::record(&Record::Payment(PaymentRecord {
+            destination: ACCOUNT.to_string(),
+            muxed_id: Some(7),
+            memo: Memo::Id(42),
+        })),
+        format!("payment: {ACCOUNT} (muxed id 7), memo id 42")
+    );
+    assert_eq!(
+        print::offer(&Offer {
+            seller: ACCOUNT.to_string(),
+            name: b"alice".to_vec(),
+            tld: b"xlm".to_vec(),
+            price: 250,
+        }),
+        format!("alice.xlm for 250 by {ACCOUNT}")
+    );
+    assert_eq!(print::text(&[0xff, 0x00]), "0xff00");
+}